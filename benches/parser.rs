@@ -0,0 +1,132 @@
+//! Pull parser benchmarks.
+//!
+//! Run with `cargo bench --features=bench`.
+//!
+//! Benchmark data are synthetic FBX binaries generated by the writer, each of
+//! which stresses a particular shape of the data.
+
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use fbxcel::{
+    low::{v7400::ArrayAttributeEncoding, FbxVersion},
+    pull_parser::{
+        any::{from_seekable_reader, AnyParser},
+        v7400::{attribute::loaders::DirectLoader, Event},
+    },
+    writer::v7400::binary::{FbxFooter, Result, Writer},
+};
+
+/// Depth of the deep tree.
+const DEEP_TREE_DEPTH: usize = 1000;
+/// Number of the nodes in the wide tree.
+const WIDE_TREE_WIDTH: usize = 100_000;
+/// Number of elements of the huge arrays.
+const HUGE_ARRAY_LEN: usize = 1_000_000;
+/// Number of the string attributes.
+const STRINGS_COUNT: usize = 100_000;
+
+/// Writes nodes using the given function, and returns the FBX binary.
+fn generate(f: impl FnOnce(&mut Writer<Cursor<Vec<u8>>>) -> Result<()>) -> Vec<u8> {
+    let mut writer =
+        Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4).expect("Failed to create a writer");
+    f(&mut writer).expect("Failed to write nodes");
+    writer
+        .finalize_and_flush(&FbxFooter::default())
+        .expect("Failed to finalize")
+        .into_inner()
+}
+
+/// Generates a tree with deeply nested nodes.
+fn deep_tree() -> Vec<u8> {
+    generate(|writer| {
+        for _ in 0..DEEP_TREE_DEPTH {
+            writer.new_node("Node")?.append_i32(42)?;
+        }
+        for _ in 0..DEEP_TREE_DEPTH {
+            writer.close_node()?;
+        }
+        Ok(())
+    })
+}
+
+/// Generates a tree with many sibling nodes.
+fn wide_tree() -> Vec<u8> {
+    generate(|writer| {
+        writer.new_node("Objects")?;
+        for i in 0..WIDE_TREE_WIDTH {
+            writer.new_node("Node")?.append_i64(i as i64)?;
+            writer.close_node()?;
+        }
+        writer.close_node()
+    })
+}
+
+/// Generates a node with a huge array attribute.
+fn huge_array(encoding: ArrayAttributeEncoding) -> Vec<u8> {
+    generate(|writer| {
+        writer.new_node("Vertices")?.append_arr_f64_from_iter(
+            encoding,
+            (0..HUGE_ARRAY_LEN).map(|i| (i % 1000) as f64 * 0.5),
+        )?;
+        writer.close_node()
+    })
+}
+
+/// Generates a node with many string attributes.
+fn many_strings() -> Vec<u8> {
+    generate(|writer| {
+        let mut attrs = writer.new_node("Strings")?;
+        for i in 0..STRINGS_COUNT {
+            attrs.append_string_direct(&format!("Model::Object{}", i))?;
+        }
+        writer.close_node()
+    })
+}
+
+/// Parses the whole data, loading all attributes.
+fn parse(data: &[u8]) {
+    let mut parser = match from_seekable_reader(Cursor::new(data)).expect("Failed to parse") {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    loop {
+        match parser.next_event().expect("Failed to parse") {
+            Event::StartNode(start) => {
+                let mut attrs = start.attributes();
+                while let Some(attr) = attrs.load_next(DirectLoader).expect("Failed to parse") {
+                    black_box(attr);
+                }
+            }
+            Event::EndNode | Event::SkippedNode { .. } => {}
+            Event::EndFbx(footer) => {
+                black_box(footer.ok());
+                break;
+            }
+        }
+    }
+}
+
+/// Benchmarks the pull parser.
+fn bench_parser(c: &mut Criterion) {
+    let inputs = [
+        ("deep_tree", deep_tree()),
+        ("wide_tree", wide_tree()),
+        (
+            "huge_array_direct",
+            huge_array(ArrayAttributeEncoding::Direct),
+        ),
+        ("huge_array_zlib", huge_array(ArrayAttributeEncoding::Zlib)),
+        ("many_strings", many_strings()),
+    ];
+
+    let mut group = c.benchmark_group("parse");
+    for (name, data) in &inputs {
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(*name, |b| b.iter(|| parse(black_box(data))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parser);
+criterion_main!(benches);
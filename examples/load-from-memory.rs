@@ -0,0 +1,38 @@
+//! Loads FBX data from an in-memory buffer.
+//!
+//! This does not use the filesystem, so the same code works on targets
+//! without it, such as `wasm32-unknown-unknown`.
+//! In browsers, copy the `Uint8Array` into a `Vec<u8>` (for example by
+//! `js_sys::Uint8Array::to_vec()`) and pass it to `load()`.
+
+use std::io::{Cursor, Read};
+
+use fbxcel::tree::any::AnyTree;
+
+/// Loads the FBX data and returns the names of the top-level nodes.
+fn load(bytes: &[u8]) -> Result<Vec<String>, fbxcel::tree::any::Error> {
+    match AnyTree::from_seekable_reader(Cursor::new(bytes))? {
+        AnyTree::V7400(fbx_version, tree, _footer) => {
+            println!("FBX version = {:?}", fbx_version);
+            Ok(tree
+                .root()
+                .children()
+                .map(|node| node.name().to_owned())
+                .collect())
+        }
+        _ => panic!("FBX version unsupported by this example"),
+    }
+}
+
+pub fn main() {
+    env_logger::init();
+
+    let mut bytes = Vec::new();
+    if let Err(e) = std::io::stdin().read_to_end(&mut bytes) {
+        eprintln!("Usage: load-from-memory < <FBX_FILE>");
+        eprintln!("Failed to read the standard input: {}", e);
+        std::process::exit(1);
+    }
+    let names = load(&bytes).expect("Failed to load tree");
+    println!("top-level nodes = {:#?}", names);
+}
@@ -1,4 +1,4 @@
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::path::PathBuf;
 
 use fbxcel::tree::any::AnyTree;
 
@@ -12,10 +12,7 @@ pub fn main() {
             std::process::exit(1);
         }
     };
-    let file = File::open(path).expect("Failed to open file");
-    let reader = BufReader::new(file);
-
-    match AnyTree::from_seekable_reader(reader).expect("Failed to load tree") {
+    match AnyTree::from_path(path).expect("Failed to load tree") {
         AnyTree::V7400(fbx_version, tree, footer) => {
             println!("FBX version = {:#?}", fbx_version);
             println!("tree = {:#?}", tree);
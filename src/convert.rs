@@ -0,0 +1,128 @@
+//! Conversion between FBX versions.
+//!
+//! This module is enabled by `tree` and `writer` features.
+//!
+//! [`convert`] loads FBX data of any supported version into a tree, and
+//! writes it again as the target version.
+//! The node header layout (32-bit fields for FBX 7.4 and earlier, and 64-bit
+//! fields for FBX 7.5 and later) is chosen by the writer.
+//!
+//! When downgrading to 32-bit node headers, the data might not fit in.
+//! In such case, [`Error::Write`] is returned with the corresponding writer
+//! error (such as [`writer::v7400::binary::Error::FileTooLarge`]).
+
+use std::{
+    error, fmt,
+    io::{Read, Seek, Write},
+};
+
+use crate::{
+    low::FbxVersion,
+    tree::any::{self as any_tree, AnyTree},
+    writer::{
+        self,
+        v7400::binary::{FbxFooter, FooterPaddingRewrite},
+    },
+    ErrorCode,
+};
+
+/// Conversion result.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Conversion error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Failed to load the source data.
+    Load(any_tree::Error),
+    /// Failed to write the converted data.
+    Write(writer::v7400::binary::Error),
+}
+
+impl Error {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Load(e) => e.code(),
+            Error::Write(e) => e.code(),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Load(e) => Some(e),
+            Error::Write(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Load(e) => write!(f, "Failed to load the source data: {}", e),
+            Error::Write(e) => write!(f, "Failed to write the converted data: {}", e),
+        }
+    }
+}
+
+impl From<any_tree::Error> for Error {
+    #[inline]
+    fn from(e: any_tree::Error) -> Self {
+        Error::Load(e)
+    }
+}
+
+impl From<writer::v7400::binary::Error> for Error {
+    #[inline]
+    fn from(e: writer::v7400::binary::Error) -> Self {
+        Error::Write(e)
+    }
+}
+
+/// Converts the FBX data from the reader into the given target version, and
+/// writes it to the sink.
+///
+/// The unknown fields of the source footer are preserved if the footer is
+/// successfully read.
+/// Parser warnings are logged and ignored.
+///
+/// Returns the source FBX version and the sink.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// # use std::io::Cursor;
+/// # let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+/// # writer.new_node("Node")?;
+/// # writer.close_node()?;
+/// # let source = writer.finalize_and_flush(&Default::default())?.into_inner();
+/// let (source_version, sink) = fbxcel::convert::convert(
+///     Cursor::new(source),
+///     Cursor::new(Vec::new()),
+///     FbxVersion::V7_5,
+/// )?;
+/// assert_eq!(source_version, FbxVersion::V7_4);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn convert<W>(reader: impl Read + Seek, sink: W, target: FbxVersion) -> Result<(FbxVersion, W)>
+where
+    W: Write + Seek,
+{
+    let (source_version, tree, footer) = match AnyTree::from_seekable_reader(reader)? {
+        AnyTree::V7400(fbx_version, tree, footer) => (fbx_version, tree, footer),
+    };
+
+    let mut writer = writer::v7400::binary::Writer::new(sink, target)?;
+    writer.write_tree(&tree)?;
+    let footer = match &footer {
+        Ok(footer) => FbxFooter::from_parsed(footer, FooterPaddingRewrite::Correct),
+        Err(_) => FbxFooter::default(),
+    };
+    let sink = writer.finalize_and_flush(&footer)?;
+
+    Ok((source_version, sink))
+}
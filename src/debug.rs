@@ -0,0 +1,152 @@
+//! Debugging utilities.
+//!
+//! [`dump`] writes the pull parser events in human readable form, which is
+//! useful for diagnostics.
+
+use std::{fmt, io::Write};
+
+use crate::{
+    low::v7400::AttributeValue,
+    pull_parser::{
+        self,
+        v7400::{
+            attribute::loaders::{DirectLoader, TypeLoader},
+            Attributes, Event, Parser,
+        },
+        ParserSource,
+    },
+};
+
+/// Dump format of node attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DumpFormat {
+    /// Type only.
+    Type,
+    /// Value for primitive types, length for array, binary, and string.
+    Length,
+    /// Values for all types.
+    ///
+    /// Not recommended for large files because the output might be quite
+    /// large.
+    Full,
+}
+
+impl Default for DumpFormat {
+    #[inline]
+    fn default() -> Self {
+        DumpFormat::Type
+    }
+}
+
+/// Dumps the rest of the parser events to the given writer.
+///
+/// Each node is indented by its depth, and attributes are written in the
+/// given format.
+/// The parser is consumed until the end of the FBX data.
+///
+/// I/O errors of the writer are returned as parser errors.
+pub fn dump<R: ParserSource>(
+    parser: &mut Parser<R>,
+    out: &mut impl Write,
+    format: DumpFormat,
+) -> pull_parser::Result<()> {
+    let mut depth = parser.current_depth();
+
+    loop {
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                indent(out, depth)?;
+                writeln!(out, "Node start: {:?}", start.name())?;
+                depth += 1;
+
+                let attrs = start.attributes();
+                match format {
+                    DumpFormat::Type => dump_attributes_type(out, depth, attrs)?,
+                    DumpFormat::Length => dump_attributes_value(out, depth, attrs, false)?,
+                    DumpFormat::Full => dump_attributes_value(out, depth, attrs, true)?,
+                }
+            }
+            Event::EndNode => {
+                depth -= 1;
+                indent(out, depth)?;
+                writeln!(out, "Node end")?;
+            }
+            Event::SkippedNode { name, bytelen } => {
+                indent(out, depth)?;
+                writeln!(out, "Node skipped: {:?} ({} bytes)", name, bytelen)?;
+            }
+            Event::EndFbx(footer_res) => {
+                writeln!(out, "FBX end")?;
+                match footer_res {
+                    Ok(footer) => writeln!(out, "footer: {:#?}", footer)?,
+                    Err(e) => writeln!(out, "footer has an error: {:?}", e)?,
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the indent for the given depth.
+fn indent(out: &mut impl Write, depth: usize) -> pull_parser::Result<()> {
+    write!(out, "{:depth$}", "", depth = depth * 4)?;
+
+    Ok(())
+}
+
+/// Dumps types of the node attributes.
+fn dump_attributes_type<R: ParserSource>(
+    out: &mut impl Write,
+    depth: usize,
+    mut attrs: Attributes<'_, R>,
+) -> pull_parser::Result<()> {
+    while let Some(type_) = attrs.load_next(TypeLoader)? {
+        indent(out, depth)?;
+        writeln!(out, "Attribute: {:?}", type_)?;
+    }
+
+    Ok(())
+}
+
+/// Dumps values or lengths of the node attributes.
+///
+/// If `full` is false, only lengths are written for array, binary, and string
+/// attributes.
+fn dump_attributes_value<R: ParserSource>(
+    out: &mut impl Write,
+    depth: usize,
+    mut attrs: Attributes<'_, R>,
+    full: bool,
+) -> pull_parser::Result<()> {
+    while let Some(attr) = attrs.load_next(DirectLoader)? {
+        let type_ = attr.type_();
+        indent(out, depth)?;
+        let (len, value): (usize, &dyn fmt::Debug) = match &attr {
+            AttributeValue::ArrBool(v) => (v.len(), v),
+            AttributeValue::ArrI32(v) => (v.len(), v),
+            AttributeValue::ArrI64(v) => (v.len(), v),
+            AttributeValue::ArrF32(v) => (v.len(), v),
+            AttributeValue::ArrF64(v) => (v.len(), v),
+            AttributeValue::Binary(v) => (v.len(), v),
+            AttributeValue::String(v) => (v.len(), v),
+            _ => {
+                writeln!(out, "Attribute: {:?}", attr)?;
+                continue;
+            }
+        };
+        if full {
+            writeln!(
+                out,
+                "Attribute: type={:?}, len={}, value={:?}",
+                type_, len, value
+            )?;
+        } else {
+            writeln!(out, "Attribute: type={:?}, len={}", type_, len)?;
+        }
+    }
+
+    Ok(())
+}
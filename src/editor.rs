@@ -0,0 +1,365 @@
+//! In-place editing of existing FBX binary data.
+//!
+//! This module is enabled by `writer` feature.
+//!
+//! [`Editor`] locates a node by its path and rewrites its attributes without
+//! rewriting the whole file.
+//!
+//! # Limitations
+//!
+//! Only same-size rewrites are supported.
+//! FBX binary has no room to resize a node in place: node headers contain
+//! absolute end offsets, so changing the size of a node (or relocating it to
+//! the end of the file) requires rewriting the headers of all nodes after it,
+//! and the footer padding also depends on the file size.
+//! Therefore the new attributes must have the same byte length as the old
+//! ones, and [`Error::AttributesSizeMismatch`] is returned otherwise.
+//! To change the size, load the data into a tree and write it again.
+
+use std::{
+    error, fmt,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+};
+
+use crate::{
+    low::{
+        v7400::{ArrayAttributeEncoding, AttributeValue, NodeHeader},
+        FbxVersion,
+    },
+    pull_parser::{
+        self,
+        any::{from_seekable_reader, AnyParser},
+        v7400::Event,
+    },
+    writer::v7400::binary::{self as binary_writer, Writer},
+    ErrorCode,
+};
+
+/// Editor result.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Editor error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// New attributes have a different byte length from the old ones.
+    ///
+    /// This contains the byte length of the old attributes and the new
+    /// attributes.
+    AttributesSizeMismatch(u64, u64),
+    /// I/O error.
+    Io(io::Error),
+    /// Node is not found.
+    NodeNotFound(Vec<String>),
+    /// Parser error.
+    Parser(pull_parser::Error),
+    /// Parser creation error.
+    ParserCreation(pull_parser::any::Error),
+    /// Writer error.
+    Writer(binary_writer::Error),
+}
+
+impl Error {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::AttributesSizeMismatch(..) => ErrorCode::AttributesSizeMismatch,
+            Error::Io(_) => ErrorCode::Io,
+            Error::NodeNotFound(_) => ErrorCode::NodeNotFound,
+            Error::Parser(e) => e.code(),
+            Error::ParserCreation(e) => e.code(),
+            Error::Writer(e) => e.code(),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parser(e) => Some(e),
+            Error::ParserCreation(e) => Some(e),
+            Error::Writer(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AttributesSizeMismatch(old, new) => write!(
+                f,
+                "New attributes cannot be written in place: old={} bytes, new={} bytes",
+                old, new
+            ),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::NodeNotFound(path) => write!(f, "Node is not found: path={:?}", path),
+            Error::Parser(e) => write!(f, "Parser error: {}", e),
+            Error::ParserCreation(e) => write!(f, "Failed to create a parser: {}", e),
+            Error::Writer(e) => write!(f, "Failed to encode attributes: {}", e),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<pull_parser::Error> for Error {
+    #[inline]
+    fn from(e: pull_parser::Error) -> Self {
+        Error::Parser(e)
+    }
+}
+
+impl From<pull_parser::any::Error> for Error {
+    #[inline]
+    fn from(e: pull_parser::any::Error) -> Self {
+        Error::ParserCreation(e)
+    }
+}
+
+impl From<binary_writer::Error> for Error {
+    #[inline]
+    fn from(e: binary_writer::Error) -> Self {
+        Error::Writer(e)
+    }
+}
+
+/// Location of a node in FBX binary data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeLocation {
+    /// Offset of the node header.
+    header_offset: u64,
+    /// Offset of the node attributes.
+    attributes_offset: u64,
+    /// Number of the node attributes.
+    attributes_count: u64,
+    /// Byte length of the node attributes.
+    attributes_bytelen: u64,
+    /// End offset of the node.
+    end_offset: u64,
+}
+
+impl NodeLocation {
+    /// Returns the offset of the node header.
+    #[inline]
+    #[must_use]
+    pub fn header_offset(&self) -> u64 {
+        self.header_offset
+    }
+
+    /// Returns the offset of the node attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes_offset(&self) -> u64 {
+        self.attributes_offset
+    }
+
+    /// Returns the number of the node attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes_count(&self) -> u64 {
+        self.attributes_count
+    }
+
+    /// Returns the byte length of the node attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes_bytelen(&self) -> u64 {
+        self.attributes_bytelen
+    }
+
+    /// Returns the end offset of the node.
+    #[inline]
+    #[must_use]
+    pub fn end_offset(&self) -> u64 {
+        self.end_offset
+    }
+}
+
+/// In-place editor of FBX binary data.
+///
+/// The editor can only rewrite node attributes with ones of the same byte
+/// length.
+/// See [the module documentation][`self`] for detail.
+///
+/// # Examples
+///
+/// ```
+/// use fbxcel::{editor::Editor, low::v7400::AttributeValue};
+/// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+/// # writer.new_node("GlobalSettings")?;
+/// # writer.new_node("Version")?.append_i32(1000)?;
+/// # writer.close_node()?;
+/// # writer.close_node()?;
+/// # let file = writer.finalize_and_flush(&Default::default())?;
+///
+/// let mut editor = Editor::new(file)?;
+/// editor.patch_attributes(
+///     &["GlobalSettings", "Version"],
+///     &[AttributeValue::I32(1001)],
+/// )?;
+/// let file = editor.into_inner();
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Editor<F> {
+    /// Target file.
+    file: F,
+    /// FBX version.
+    fbx_version: FbxVersion,
+}
+
+impl<F: Read + Write + Seek> Editor<F> {
+    /// Creates a new editor for the given FBX binary data.
+    pub fn new(mut file: F) -> Result<Self> {
+        file.seek(SeekFrom::Start(0))?;
+        let fbx_version = from_seekable_reader(&mut file)?.fbx_version();
+
+        Ok(Self { file, fbx_version })
+    }
+
+    /// Returns the FBX version of the data.
+    #[inline]
+    #[must_use]
+    pub fn fbx_version(&self) -> FbxVersion {
+        self.fbx_version
+    }
+
+    /// Returns the inner file.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+
+    /// Locates the first node with the given path in depth-first order.
+    ///
+    /// The path is a list of node names from a top-level node.
+    /// Returns `Ok(None)` if no such node is found.
+    pub fn locate(&mut self, path: &[&str]) -> Result<Option<NodeLocation>> {
+        if path.is_empty() {
+            return Ok(None);
+        }
+        self.file.seek(SeekFrom::Start(0))?;
+        let AnyParser::V7400(mut parser) = from_seekable_reader(&mut self.file)?;
+        parser.set_warning_handler(|_, _| Ok(()));
+        let header_len = NodeHeader::bytelen(self.fbx_version);
+
+        // Number of the leading path components matched by the open nodes.
+        let mut matched = 0;
+        loop {
+            let depth = parser.current_depth();
+            let is_match = match parser.next_event()? {
+                Event::StartNode(start) => {
+                    if matched == depth && start.name() == path[depth] {
+                        matched += 1;
+                        if matched == path.len() {
+                            let header_offset = start.start_offset();
+                            return Ok(Some(NodeLocation {
+                                header_offset,
+                                attributes_offset: header_offset
+                                    + header_len
+                                    + start.name().len() as u64,
+                                attributes_count: start.attributes_count(),
+                                attributes_bytelen: start.attributes_bytelen(),
+                                end_offset: start.end_offset(),
+                            }));
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Event::EndNode => {
+                    matched = matched.min(depth - 1);
+                    continue;
+                }
+                Event::SkippedNode { .. } => continue,
+                Event::EndFbx(_) => return Ok(None),
+            };
+            if !is_match {
+                parser.skip_current_node()?;
+            }
+        }
+    }
+
+    /// Rewrites the attributes of the first node with the given path in
+    /// place.
+    ///
+    /// The number of attributes may change, but the byte length of the
+    /// encoded attributes must be same as the old one.
+    /// Otherwise, [`Error::AttributesSizeMismatch`] is returned and the data
+    /// is not modified.
+    /// Resizing or relocating the node is not supported.
+    ///
+    /// Note that array attributes are written without compression.
+    pub fn patch_attributes(
+        &mut self,
+        path: &[&str],
+        values: &[AttributeValue],
+    ) -> Result<NodeLocation> {
+        let location = self
+            .locate(path)?
+            .ok_or_else(|| Error::NodeNotFound(path.iter().map(|&v| v.to_owned()).collect()))?;
+        let encoded = encode_attributes(self.fbx_version, values)?;
+        if encoded.len() as u64 != location.attributes_bytelen {
+            return Err(Error::AttributesSizeMismatch(
+                location.attributes_bytelen,
+                encoded.len() as u64,
+            ));
+        }
+
+        // Update the number of attributes in the node header.
+        if self.fbx_version.has_64bit_node_header() {
+            self.file
+                .seek(SeekFrom::Start(location.header_offset + 8))?;
+            self.file.write_all(&(values.len() as u64).to_le_bytes())?;
+        } else {
+            let count = u32::try_from(values.len())
+                .map_err(|_| binary_writer::Error::TooManyAttributes(values.len()))?;
+            self.file
+                .seek(SeekFrom::Start(location.header_offset + 4))?;
+            self.file.write_all(&count.to_le_bytes())?;
+        }
+        self.file
+            .seek(SeekFrom::Start(location.attributes_offset))?;
+        self.file.write_all(&encoded)?;
+        self.file.flush()?;
+
+        Ok(NodeLocation {
+            attributes_count: values.len() as u64,
+            ..location
+        })
+    }
+}
+
+/// Encodes the given attributes for the given FBX version.
+fn encode_attributes(fbx_version: FbxVersion, values: &[AttributeValue]) -> Result<Vec<u8>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), fbx_version)?;
+    writer.set_toc_recording(true);
+    {
+        let mut attrs = writer.new_node("_")?;
+        for value in values {
+            attrs.append_value_with_encoding(value, Some(ArrayAttributeEncoding::Direct))?;
+        }
+    }
+    writer.close_node()?;
+    let (sink, toc) = writer.finalize_with_toc(&Default::default())?;
+    let entry = &toc[0];
+    let start = entry.header_offset() + NodeHeader::bytelen(fbx_version) + 1;
+    let mut end = entry.end_offset();
+    if values.is_empty() {
+        // Node without attributes has the node end marker.
+        end -= NodeHeader::bytelen(fbx_version);
+    }
+
+    Ok(sink.into_inner()[(start as usize)..(end as usize)].to_vec())
+}
@@ -0,0 +1,227 @@
+//! Machine-readable error codes.
+
+use std::fmt;
+
+/// Error category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// Uncategorized error.
+    Other,
+    /// I/O error.
+    Io,
+    /// Invalid or unsupported FBX header.
+    Header,
+    /// Invalid data.
+    Data,
+    /// Invalid operation.
+    Operation,
+    /// Warning considered critical.
+    Warning,
+    /// Tree error.
+    Tree,
+    /// Write error.
+    Write,
+    /// Edit error.
+    Edit,
+}
+
+impl ErrorCategory {
+    /// Returns the stable string representation.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::Other => "other",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Header => "header",
+            ErrorCategory::Data => "data",
+            ErrorCategory::Operation => "operation",
+            ErrorCategory::Warning => "warning",
+            ErrorCategory::Tree => "tree",
+            ErrorCategory::Write => "write",
+            ErrorCategory::Edit => "edit",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Defines `ErrorCode` and its stable representations.
+macro_rules! error_codes {
+    ($(
+        $(#[$meta:meta])*
+        $variant:ident => ($num:expr, $category:ident, $name:expr),
+    )*) => {
+        /// Machine-readable error code.
+        ///
+        /// Numeric and string representations are stable, so they can be
+        /// used to map failures to user-facing messages or telemetry without
+        /// matching `Display` output.
+        ///
+        /// Error types in this crate provide `code()` method to get the code.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ErrorCode {
+            $(
+                $(#[$meta])*
+                $variant,
+            )*
+        }
+
+        impl ErrorCode {
+            /// Returns the stable numeric code.
+            ///
+            /// The hundreds digit indicates the category.
+            #[must_use]
+            pub fn as_u32(self) -> u32 {
+                match self {
+                    $(ErrorCode::$variant => $num,)*
+                }
+            }
+
+            /// Returns the stable string code.
+            ///
+            /// The string is `{category}.{name}` style, such as
+            /// `data.broken_compression`.
+            #[must_use]
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $name,)*
+                }
+            }
+
+            /// Returns the category of the error.
+            #[must_use]
+            pub fn category(self) -> ErrorCategory {
+                match self {
+                    $(ErrorCode::$variant => ErrorCategory::$category,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// Uncategorized error.
+    Other => (0, Other, "other"),
+    /// I/O error.
+    Io => (100, Io, "io"),
+    /// FBX magic binary is not detected.
+    MagicNotDetected => (200, Header, "header.magic_not_detected"),
+    /// Unsupported FBX version.
+    UnsupportedFbxVersion => (201, Header, "header.unsupported_fbx_version"),
+    /// ASCII FBX is not supported.
+    AsciiFbxNotSupported => (202, Header, "header.ascii_fbx_not_supported"),
+    /// Broken compressed data.
+    BrokenCompression => (300, Data, "data.broken_compression"),
+    /// Broken FBX footer.
+    BrokenFbxFooter => (301, Data, "data.broken_fbx_footer"),
+    /// Invalid array attribute encoding.
+    InvalidArrayAttributeEncoding => (302, Data, "data.invalid_array_attribute_encoding"),
+    /// Invalid node attribute type code.
+    InvalidAttributeTypeCode => (303, Data, "data.invalid_attribute_type_code"),
+    /// Invalid node name encoding.
+    InvalidNodeNameEncoding => (304, Data, "data.invalid_node_name_encoding"),
+    /// Parser limit exceeded.
+    LimitExceeded => (305, Data, "data.limit_exceeded"),
+    /// Node attribute error.
+    NodeAttributeError => (306, Data, "data.node_attribute_error"),
+    /// Node length mismatch.
+    NodeLengthMismatch => (307, Data, "data.node_length_mismatch"),
+    /// Unexpected attribute value or type.
+    UnexpectedAttribute => (308, Data, "data.unexpected_attribute"),
+    /// Attempt to use an aborted parser.
+    ParserAlreadyAborted => (400, Operation, "operation.parser_already_aborted"),
+    /// Attempt to use a finished parser.
+    ParserAlreadyFinished => (401, Operation, "operation.parser_already_finished"),
+    /// Unsupported FBX version for the parser.
+    ParserUnsupportedFbxVersion => (402, Operation, "operation.parser_unsupported_fbx_version"),
+    /// Attempt to seek to the node attribute out of range.
+    AttributeIndexOutOfRange => (403, Operation, "operation.attribute_index_out_of_range"),
+    /// Warning considered critical.
+    CriticalWarning => (500, Warning, "warning.critical"),
+    /// Attempt to load a tree with a bad parser.
+    BadParser => (600, Tree, "tree.bad_parser"),
+    /// Attempt to set the node attribute out of range in a tree.
+    TreeAttributeIndexOutOfRange => (601, Tree, "tree.attribute_index_out_of_range"),
+    /// Attempt to set the node attribute of a different type in a tree.
+    AttributeTypeMismatch => (602, Tree, "tree.attribute_type_mismatch"),
+    /// Node attribute is too long to write.
+    AttributeTooLong => (700, Write, "write.attribute_too_long"),
+    /// Compression error on writing.
+    CompressionFailed => (701, Write, "write.compression_failed"),
+    /// File is too large to write.
+    FileTooLarge => (702, Write, "write.file_too_large"),
+    /// There are no nodes to close.
+    NoNodesToClose => (703, Write, "write.no_nodes_to_close"),
+    /// Node name is too long to write.
+    NodeNameTooLong => (704, Write, "write.node_name_too_long"),
+    /// Too many array attribute elements to write.
+    TooManyArrayAttributeElements => (705, Write, "write.too_many_array_attribute_elements"),
+    /// Too many attributes to write.
+    TooManyAttributes => (706, Write, "write.too_many_attributes"),
+    /// Top-level nodes are misordered.
+    ToplevelNodeOrder => (707, Write, "write.toplevel_node_order"),
+    /// There remains unclosed nodes.
+    UnclosedNode => (708, Write, "write.unclosed_node"),
+    /// Unsupported FBX version for the writer.
+    WriterUnsupportedFbxVersion => (709, Write, "write.unsupported_fbx_version"),
+    /// User-defined error on writing.
+    UserDefined => (710, Write, "write.user_defined"),
+    /// Verification of the written data failed.
+    VerificationFailed => (711, Write, "write.verification_failed"),
+    /// Node name is rejected by the node name policy.
+    InvalidNodeName => (712, Write, "write.invalid_node_name"),
+    /// Raw attribute payload is inconsistent with the attribute type.
+    InvalidRawAttribute => (713, Write, "write.invalid_raw_attribute"),
+    /// Node cannot be copied from a parser.
+    CopyFailed => (714, Write, "write.copy_failed"),
+    /// New attributes cannot be written in place.
+    AttributesSizeMismatch => (800, Edit, "edit.attributes_size_mismatch"),
+    /// Node to edit is not found.
+    NodeNotFound => (801, Edit, "edit.node_not_found"),
+}
+
+impl fmt::Display for ErrorCode {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_matches_code() {
+        let codes = [
+            ErrorCode::Other,
+            ErrorCode::Io,
+            ErrorCode::MagicNotDetected,
+            ErrorCode::BrokenCompression,
+            ErrorCode::ParserAlreadyAborted,
+            ErrorCode::CriticalWarning,
+            ErrorCode::BadParser,
+            ErrorCode::TreeAttributeIndexOutOfRange,
+            ErrorCode::AttributeTypeMismatch,
+            ErrorCode::VerificationFailed,
+            ErrorCode::NodeNotFound,
+        ];
+        for code in codes {
+            assert_eq!(
+                code.as_u32() / 100,
+                code.category() as u32,
+                "The hundreds digit should indicate the category: code={:?}",
+                code
+            );
+            let category = code.as_str().split('.').next();
+            assert_eq!(category, Some(code.category().as_str()));
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Entry point to open FBX files.
+//!
+//! This module is enabled by `fs` feature.
+//!
+//! [`Fbx`] opens files with buffered readers, and returns parsers or trees of
+//! any supported version.
+
+use std::{fs::File, io::BufReader, path::Path};
+
+use crate::{
+    low::HeaderError,
+    pull_parser::{
+        self,
+        any::{from_seekable_reader, AnyParser},
+        reader::SeekableSource,
+        v7400::ParserConfig,
+    },
+};
+
+/// Default capacity of the buffered reader.
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
+/// Parser source of a file.
+pub type FileSource = SeekableSource<BufReader<File>>;
+
+/// Entry point to open FBX files.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fbxcel::Fbx;
+///
+/// let parser = Fbx::open("sample.fbx")?;
+///
+/// let parser = Fbx::options()
+///     .buffer_capacity(64 * 1024)
+///     .open("sample.fbx")?;
+/// # Ok::<_, fbxcel::pull_parser::any::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fbx;
+
+impl Fbx {
+    /// Opens the file with the default options and creates a parser.
+    ///
+    /// See [`OpenOptions::open`] for detail.
+    #[inline]
+    pub fn open(path: impl AsRef<Path>) -> pull_parser::any::Result<AnyParser<FileSource>> {
+        OpenOptions::new().open(path)
+    }
+
+    /// Returns the default options to open files.
+    #[inline]
+    #[must_use]
+    pub fn options() -> OpenOptions {
+        OpenOptions::new()
+    }
+}
+
+/// Options to open FBX files.
+///
+/// This is created by [`Fbx::options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpenOptions {
+    /// Capacity of the buffered reader.
+    buffer_capacity: usize,
+    /// Parser configuration.
+    parser_config: ParserConfig,
+}
+
+impl OpenOptions {
+    /// Creates a new `OpenOptions` with the default values.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+            parser_config: ParserConfig::default(),
+        }
+    }
+
+    /// Sets the capacity of the buffered reader.
+    ///
+    /// The default is 8 KiB.
+    #[inline]
+    pub fn buffer_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    /// Sets the parser configuration.
+    #[inline]
+    pub fn parser_config(&mut self, config: ParserConfig) -> &mut Self {
+        self.parser_config = config;
+        self
+    }
+
+    /// Opens the file and creates a parser.
+    ///
+    /// Files are always read as seekable sources.
+    /// I/O errors on opening the file are returned as [`HeaderError::Io`].
+    pub fn open(&self, path: impl AsRef<Path>) -> pull_parser::any::Result<AnyParser<FileSource>> {
+        let file = File::open(path).map_err(HeaderError::from)?;
+        let reader = BufReader::with_capacity(self.buffer_capacity, file);
+        let mut parser = from_seekable_reader(reader)?;
+        match &mut parser {
+            AnyParser::V7400(parser) => parser.set_config(self.parser_config),
+        }
+
+        Ok(parser)
+    }
+
+    /// Opens the file and loads a tree.
+    ///
+    /// Parser warnings are logged and ignored.
+    #[cfg(feature = "tree")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+    pub fn load_tree(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> crate::tree::any::Result<crate::tree::any::AnyTree> {
+        let parser = self.open(path)?;
+        crate::tree::any::AnyTree::from_parser(parser)
+    }
+}
+
+impl Default for OpenOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
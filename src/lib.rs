@@ -12,12 +12,61 @@
 //!
 //! [`writer`] module provides writer types.
 //! To use `writer` module, enable `writer` feature.
+//!
+//! [`convert`] module provides conversion between FBX versions.
+//! To use `convert` module, enable both `tree` and `writer` features.
+//!
+//! [`debug`] module provides debugging utilities such as parser events dump.
+//!
+//! [`editor`] module provides in-place editing of existing FBX binary data.
+//! To use `editor` module, enable `writer` feature.
+//!
+//! [`ErrorCode`] provides machine-readable codes for errors in this crate.
+//!
+//! [`Fbx`] opens FBX files and creates parsers or trees.
+//! To use it, enable `fs` feature.
+//!
+//! [`prelude`] module re-exports commonly used traits and types.
+//!
+//! [`load`] function loads FBX data of any supported version, as a parser or
+//! a tree.
+//!
+//! [`probe`] function collects file-level metadata (such as FBX version and
+//! top-level node names) without loading the whole document.
 #![cfg_attr(feature = "docsrs", feature(doc_cfg))]
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
+#[cfg(feature = "fs")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "fs")))]
+pub use self::fs::Fbx;
+pub use self::{
+    error_code::{ErrorCategory, ErrorCode},
+    load::{load, LoadError, LoadLevel, Loaded},
+    probe::{probe, ProbeError, ProbeInfo},
+};
+
+#[cfg(all(feature = "tree", feature = "writer"))]
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(feature = "tree", feature = "writer")))
+)]
+pub mod convert;
+pub mod debug;
+#[cfg(feature = "writer")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+pub mod editor;
+mod error_code;
+#[cfg(feature = "fs")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "fs")))]
+pub mod fs;
+mod load;
 pub mod low;
+#[doc(hidden)]
+pub mod macro_support;
+pub mod prelude;
+mod probe;
 pub mod pull_parser;
 #[cfg(feature = "tree")]
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
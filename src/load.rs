@@ -0,0 +1,179 @@
+//! Version-agnostic loading of FBX data.
+
+use std::{
+    error, fmt,
+    io::{Read, Seek},
+};
+
+#[cfg(feature = "tree")]
+use crate::tree::{self, any::AnyTree};
+use crate::{
+    low::FbxVersion,
+    pull_parser::{
+        self,
+        any::{from_seekable_reader, AnyParser},
+        reader::SeekableSource,
+    },
+    ErrorCode,
+};
+
+/// Level of the data loaded by [`load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LoadLevel {
+    /// Reads the FBX header and creates a parser.
+    Parser,
+    /// Loads the whole document as a tree.
+    #[cfg(feature = "tree")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+    Tree,
+}
+
+/// Data loaded by [`load`].
+// `Loaded` is usually consumed right after loading, so boxing the parser is
+// not worth an extra allocation.
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum Loaded<R> {
+    /// Parser with the FBX header already read.
+    Parser(AnyParser<SeekableSource<R>>),
+    /// Tree.
+    #[cfg(feature = "tree")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+    Tree(AnyTree),
+}
+
+impl<R: Read + Seek> Loaded<R> {
+    /// Returns the FBX version.
+    #[must_use]
+    pub fn fbx_version(&self) -> FbxVersion {
+        match self {
+            Loaded::Parser(parser) => parser.fbx_version(),
+            #[cfg(feature = "tree")]
+            Loaded::Tree(tree) => tree.fbx_version(),
+        }
+    }
+
+    /// Returns the parser if the data is loaded as a parser.
+    #[must_use]
+    pub fn into_parser(self) -> Option<AnyParser<SeekableSource<R>>> {
+        match self {
+            Loaded::Parser(parser) => Some(parser),
+            #[cfg(feature = "tree")]
+            Loaded::Tree(_) => None,
+        }
+    }
+
+    /// Returns the tree if the data is loaded as a tree.
+    #[cfg(feature = "tree")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+    #[must_use]
+    pub fn into_tree(self) -> Option<AnyTree> {
+        match self {
+            Loaded::Parser(_) => None,
+            Loaded::Tree(tree) => Some(tree),
+        }
+    }
+}
+
+impl<R: Read + Seek> fmt::Debug for Loaded<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loaded::Parser(parser) => f
+                .debug_tuple("Parser")
+                .field(&parser.parser_version())
+                .finish(),
+            #[cfg(feature = "tree")]
+            Loaded::Tree(tree) => f.debug_tuple("Tree").field(&tree.fbx_version()).finish(),
+        }
+    }
+}
+
+/// Loads the FBX data of any supported version, up to the given level.
+///
+/// The FBX header is read only once.
+/// A parser returned by [`LoadLevel::Parser`] can be used to inspect the
+/// header, and then to load the tree by [`AnyTree::from_parser`] without
+/// reading the header again.
+///
+/// Parser warnings are logged and ignored when loading a tree.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fbxcel::{load, LoadLevel};
+///
+/// let file = std::io::BufReader::new(std::fs::File::open("sample.fbx")?);
+/// let loaded = load(file, LoadLevel::Parser)?;
+/// println!("FBX version: {}", loaded.fbx_version());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`AnyTree::from_parser`]: `crate::tree::any::AnyTree::from_parser`
+pub fn load<R: Read + Seek>(reader: R, level: LoadLevel) -> Result<Loaded<R>, LoadError> {
+    let parser = from_seekable_reader(reader)?;
+    match level {
+        LoadLevel::Parser => Ok(Loaded::Parser(parser)),
+        #[cfg(feature = "tree")]
+        LoadLevel::Tree => Ok(Loaded::Tree(AnyTree::from_parser(parser)?)),
+    }
+}
+
+/// Load error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// Parser creation error.
+    ParserCreation(pull_parser::any::Error),
+    /// Tree load error.
+    #[cfg(feature = "tree")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+    Tree(tree::any::Error),
+}
+
+impl LoadError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LoadError::ParserCreation(e) => e.code(),
+            #[cfg(feature = "tree")]
+            LoadError::Tree(e) => e.code(),
+        }
+    }
+}
+
+impl error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LoadError::ParserCreation(e) => Some(e),
+            #[cfg(feature = "tree")]
+            LoadError::Tree(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::ParserCreation(e) => write!(f, "Failed to create a parser: {}", e),
+            #[cfg(feature = "tree")]
+            LoadError::Tree(e) => write!(f, "Failed to load a tree: {}", e),
+        }
+    }
+}
+
+impl From<pull_parser::any::Error> for LoadError {
+    #[inline]
+    fn from(e: pull_parser::any::Error) -> Self {
+        LoadError::ParserCreation(e)
+    }
+}
+
+#[cfg(feature = "tree")]
+impl From<tree::any::Error> for LoadError {
+    #[inline]
+    fn from(e: tree::any::Error) -> Self {
+        LoadError::Tree(e)
+    }
+}
@@ -5,7 +5,7 @@ use std::{error, fmt, io};
 use byteorder::{LittleEndian, ReadBytesExt};
 use log::info;
 
-use crate::{low::FbxVersion, pull_parser::ParserVersion};
+use crate::{low::FbxVersion, pull_parser::ParserVersion, ErrorCode};
 
 /// Magic binary length.
 const MAGIC_LEN: usize = 23;
@@ -20,6 +20,20 @@ pub enum HeaderError {
     Io(io::Error),
     /// Magic binary is not detected.
     MagicNotDetected,
+    /// The data seems to be ASCII FBX, which is not supported.
+    AsciiFbxNotSupported,
+}
+
+impl HeaderError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            HeaderError::Io(_) => ErrorCode::Io,
+            HeaderError::MagicNotDetected => ErrorCode::MagicNotDetected,
+            HeaderError::AsciiFbxNotSupported => ErrorCode::AsciiFbxNotSupported,
+        }
+    }
 }
 
 impl error::Error for HeaderError {}
@@ -29,6 +43,9 @@ impl fmt::Display for HeaderError {
         match self {
             HeaderError::Io(e) => e.fmt(f),
             HeaderError::MagicNotDetected => f.write_str("FBX magic binary is not detected"),
+            HeaderError::AsciiFbxNotSupported => {
+                f.write_str("ASCII FBX is detected, but only binary FBX is supported")
+            }
         }
     }
 }
@@ -56,6 +73,9 @@ impl FbxHeader {
         let mut magic_buf = [0u8; MAGIC_LEN];
         reader.read_exact(&mut magic_buf)?;
         if magic_buf != *MAGIC {
+            if is_ascii_fbx(&magic_buf) {
+                return Err(HeaderError::AsciiFbxNotSupported);
+            }
             return Err(HeaderError::MagicNotDetected);
         }
 
@@ -95,6 +115,25 @@ impl FbxHeader {
     }
 }
 
+/// Checks whether the given leading bytes look like ASCII FBX.
+///
+/// ASCII FBX files usually start with a comment such as
+/// `; FBX 7.4.0 project file`, or with the first node
+/// `FBXHeaderExtension:`, possibly after UTF-8 BOM and whitespaces.
+fn is_ascii_fbx(head: &[u8]) -> bool {
+    /// UTF-8 BOM.
+    const BOM: &[u8] = b"\xef\xbb\xbf";
+
+    let head = head.strip_prefix(BOM).unwrap_or(head);
+    let start = head
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(head.len());
+    let head = &head[start..];
+
+    head.starts_with(b"; FBX") || head.starts_with(b"FBXHeaderExtension")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +173,23 @@ mod tests {
             "Header should not be read too much if the magic is not detected"
         );
     }
+
+    #[test]
+    fn ascii_fbx() {
+        let sources: [&[u8]; 3] = [
+            b"; FBX 7.4.0 project file\n; ----\n",
+            b"\xef\xbb\xbf; FBX 7.5.0 project file\n",
+            b"\r\nFBXHeaderExtension:  {\n",
+        ];
+        for source in sources {
+            assert!(
+                matches!(
+                    FbxHeader::load(source),
+                    Err(HeaderError::AsciiFbxNotSupported)
+                ),
+                "ASCII FBX should be reported by `AsciiFbxNotSupported`: source={:?}",
+                String::from_utf8_lossy(source)
+            );
+        }
+    }
 }
@@ -1,17 +1,24 @@
 //! Low-level or primitive data types for FBX 7.4 and compatible versions.
 
 pub use self::{
-    array_attribute::ArrayAttributeEncoding,
-    attribute::{type_::AttributeType, value::AttributeValue},
+    array_attribute::{ArrayAttributeEncoding, ParseArrayAttributeEncodingError},
+    attribute::{
+        type_::{AttributeType, ParseAttributeTypeError},
+        value::AttributeValue,
+    },
     fbx_footer::FbxFooter,
+    name_class::{split_name_class, split_name_class_bytes, NameClass},
 };
+
 pub(crate) use self::{
-    array_attribute::ArrayAttributeHeader, node_header::NodeHeader,
+    array_attribute::ArrayAttributeHeader,
+    node_header::{NodeHeader, NodeHeaderError},
     special_attribute::SpecialAttributeHeader,
 };
 
 mod array_attribute;
 mod attribute;
 mod fbx_footer;
+mod name_class;
 mod node_header;
 mod special_attribute;
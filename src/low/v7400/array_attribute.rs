@@ -1,6 +1,6 @@
 //! Low-level data types related to array type node attributes.
 
-use std::io;
+use std::{error, fmt, io, str};
 
 use crate::pull_parser::{
     error::{Compression, DataError},
@@ -20,6 +20,17 @@ pub enum ArrayAttributeEncoding {
 }
 
 impl ArrayAttributeEncoding {
+    /// Returns the human-readable name, such as `zlib`.
+    ///
+    /// This is same as the `Display` output, and can be parsed by `FromStr`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArrayAttributeEncoding::Direct => "direct",
+            ArrayAttributeEncoding::Zlib => "zlib",
+        }
+    }
+
     /// Creates a new `ArrayEncoding` from the given raw value.
     #[inline]
     #[must_use]
@@ -44,6 +55,54 @@ impl ArrayAttributeEncoding {
     }
 }
 
+impl fmt::Display for ArrayAttributeEncoding {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl str::FromStr for ArrayAttributeEncoding {
+    type Err = ParseArrayAttributeEncodingError;
+
+    /// Parses the human-readable name returned by
+    /// [`as_str`][`Self::as_str`].
+    ///
+    /// The name is case-insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::low::v7400::ArrayAttributeEncoding;
+    ///
+    /// assert_eq!("zlib".parse(), Ok(ArrayAttributeEncoding::Zlib));
+    /// assert_eq!("Direct".parse(), Ok(ArrayAttributeEncoding::Direct));
+    /// assert!("gzip".parse::<ArrayAttributeEncoding>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [ArrayAttributeEncoding::Direct, ArrayAttributeEncoding::Zlib]
+            .iter()
+            .copied()
+            .find(|encoding| encoding.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseArrayAttributeEncodingError { name: s.to_owned() })
+    }
+}
+
+/// An error which can be returned when parsing an [`ArrayAttributeEncoding`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseArrayAttributeEncodingError {
+    /// Unknown name.
+    name: String,
+}
+
+impl error::Error for ParseArrayAttributeEncodingError {}
+
+impl fmt::Display for ParseArrayAttributeEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown array attribute encoding: {:?}", self.name)
+    }
+}
+
 impl From<ArrayAttributeEncoding> for Compression {
     // Panics if the encoding is `Direct` (i.e. not compressed).
     #[inline]
@@ -90,3 +149,46 @@ impl FromReader for ArrayAttributeHeader {
         })
     }
 }
+
+#[cfg(feature = "writer")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+impl ArrayAttributeHeader {
+    /// Byte length of the serialized header.
+    pub(crate) const BYTELEN: usize = 12;
+
+    /// Returns the serialized header.
+    #[must_use]
+    pub(crate) fn to_bytes(self) -> [u8; Self::BYTELEN] {
+        let mut bytes = [0; Self::BYTELEN];
+        bytes[0..4].copy_from_slice(&self.elements_count.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.encoding.to_u32().to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.bytelen.to_le_bytes());
+        bytes
+    }
+
+    /// Writes the header to the given writer.
+    #[inline]
+    pub(crate) fn write_to(self, mut writer: impl io::Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+#[cfg(all(test, feature = "writer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_roundtrip() {
+        let header = ArrayAttributeHeader {
+            elements_count: 42,
+            encoding: ArrayAttributeEncoding::Zlib,
+            bytelen: 0x1234_5678,
+        };
+        let mut bytes = Vec::new();
+        header.write_to(&mut bytes).expect("Should never fail");
+        assert_eq!(bytes.len(), ArrayAttributeHeader::BYTELEN);
+        let read = ArrayAttributeHeader::from_reader(&mut &bytes[..])
+            .expect("Should be able to read the written header");
+        assert_eq!(read, header);
+    }
+}
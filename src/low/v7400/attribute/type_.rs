@@ -1,6 +1,6 @@
 //! Node attribute type.
 
-use std::io;
+use std::{error, fmt, io, str};
 
 use crate::pull_parser::{error::DataError, v7400::FromReader, Error as ParserError};
 
@@ -36,7 +36,49 @@ pub enum AttributeType {
 }
 
 impl AttributeType {
+    /// All attribute types.
+    const ALL: [AttributeType; 13] = [
+        AttributeType::Bool,
+        AttributeType::I16,
+        AttributeType::I32,
+        AttributeType::I64,
+        AttributeType::F32,
+        AttributeType::F64,
+        AttributeType::ArrBool,
+        AttributeType::ArrI32,
+        AttributeType::ArrI64,
+        AttributeType::ArrF32,
+        AttributeType::ArrF64,
+        AttributeType::Binary,
+        AttributeType::String,
+    ];
+
+    /// Returns the human-readable name, such as `f64 array`.
+    ///
+    /// This is same as the `Display` output, and can be parsed by `FromStr`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttributeType::Bool => "bool",
+            AttributeType::I16 => "i16",
+            AttributeType::I32 => "i32",
+            AttributeType::I64 => "i64",
+            AttributeType::F32 => "f32",
+            AttributeType::F64 => "f64",
+            AttributeType::ArrBool => "bool array",
+            AttributeType::ArrI32 => "i32 array",
+            AttributeType::ArrI64 => "i64 array",
+            AttributeType::ArrF32 => "f32 array",
+            AttributeType::ArrF64 => "f64 array",
+            AttributeType::Binary => "binary",
+            AttributeType::String => "string",
+        }
+    }
+
     /// Creates an `AttributeType` from the given type code.
+    ///
+    /// This is the inverse of [`type_code`][`Self::type_code`], and they
+    /// are checked to be consistent by tests.
     #[must_use]
     pub(crate) fn from_type_code(code: u8) -> Option<Self> {
         match code {
@@ -58,8 +100,6 @@ impl AttributeType {
     }
 
     /// Returns the type code.
-    #[cfg(feature = "writer")]
-    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
     #[must_use]
     pub(crate) fn type_code(self) -> u8 {
         match self {
@@ -80,6 +120,54 @@ impl AttributeType {
     }
 }
 
+impl fmt::Display for AttributeType {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl str::FromStr for AttributeType {
+    type Err = ParseAttributeTypeError;
+
+    /// Parses the human-readable name returned by
+    /// [`as_str`][`Self::as_str`].
+    ///
+    /// The name is case-insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::low::v7400::AttributeType;
+    ///
+    /// assert_eq!("f64 array".parse(), Ok(AttributeType::ArrF64));
+    /// assert_eq!("String".parse(), Ok(AttributeType::String));
+    /// assert!("f64 vector".parse::<AttributeType>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|ty| ty.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseAttributeTypeError { name: s.to_owned() })
+    }
+}
+
+/// An error which can be returned when parsing an [`AttributeType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAttributeTypeError {
+    /// Unknown name.
+    name: String,
+}
+
+impl error::Error for ParseAttributeTypeError {}
+
+impl fmt::Display for ParseAttributeTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown node attribute type: {:?}", self.name)
+    }
+}
+
 impl FromReader for AttributeType {
     fn from_reader(reader: &mut impl io::Read) -> Result<Self, ParserError> {
         let type_code = u8::from_reader(reader)?;
@@ -88,3 +176,29 @@ impl FromReader for AttributeType {
         Ok(attr_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_code_roundtrip() {
+        for ty in AttributeType::ALL {
+            assert_eq!(AttributeType::from_type_code(ty.type_code()), Some(ty));
+        }
+        assert_eq!(AttributeType::from_type_code(b'T'), None);
+        for code in 0..=u8::MAX {
+            if let Some(ty) = AttributeType::from_type_code(code) {
+                assert_eq!(ty.type_code(), code);
+            }
+        }
+    }
+
+    #[test]
+    fn name_roundtrip() {
+        for ty in AttributeType::ALL {
+            assert_eq!(ty.to_string().parse(), Ok(ty));
+            assert_eq!(ty.to_string().to_uppercase().parse(), Ok(ty));
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Node attribute value.
 
+use std::hash::{Hash, Hasher};
+
 use crate::low::v7400::AttributeType;
 
 /// Node attribute value.
@@ -228,9 +230,70 @@ impl AttributeValue {
         "Returns the reference to the inner binary data, if available.\n\nReturns `Err(type)` on type mismatch.",
     }
 
+    /// Returns the raw bytes of the array or binary value, if available.
+    ///
+    /// Elements are in the native byte order, and `bool` elements are
+    /// represented as `0` and `1`.
+    ///
+    /// Returns `None` for non-array values (including string).
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "bytemuck")))]
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AttributeValue::ArrBool(v) => Some(bytemuck::cast_slice(v)),
+            AttributeValue::ArrI32(v) => Some(bytemuck::cast_slice(v)),
+            AttributeValue::ArrI64(v) => Some(bytemuck::cast_slice(v)),
+            AttributeValue::ArrF32(v) => Some(bytemuck::cast_slice(v)),
+            AttributeValue::ArrF64(v) => Some(bytemuck::cast_slice(v)),
+            AttributeValue::Binary(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the array or binary value as a slice of the given plain old
+    /// data type, if possible.
+    ///
+    /// The slice is reinterpreted from the [raw bytes][`Self::as_bytes`]
+    /// without copying, so `T` is not required to be the element type of the
+    /// value (for example, `ArrF32` can be viewed as `[[f32; 3]]`).
+    ///
+    /// Returns `None` if the value is not an array or binary, or the bytes
+    /// cannot be reinterpreted as `[T]` due to size or alignment mismatch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::low::v7400::AttributeValue;
+    /// let value = AttributeValue::from(vec![0.0f64, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// let vertices: &[[f64; 3]] = value.as_pod_slice().unwrap();
+    /// assert_eq!(vertices, [[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "bytemuck")))]
+    #[must_use]
+    pub fn as_pod_slice<T: bytemuck::AnyBitPattern>(&self) -> Option<&[T]> {
+        bytemuck::try_cast_slice(self.as_bytes()?).ok()
+    }
+
     /// Compares attribute values strictly.
     ///
     /// "Strictly" means, `f32` and `f64` values are compared bitwise.
+    /// Therefore NaNs with the same bit pattern are equal, and `0.0` and
+    /// `-0.0` are different.
+    ///
+    /// Values of different types are never equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::low::v7400::AttributeValue;
+    /// let nan = AttributeValue::F64(f64::NAN);
+    /// assert!(nan.strict_eq(&nan));
+    /// assert_ne!(nan, nan);
+    /// assert!(!AttributeValue::F64(0.0).strict_eq(&AttributeValue::F64(-0.0)));
+    /// ```
+    #[must_use]
     pub fn strict_eq(&self, other: &Self) -> bool {
         use AttributeValue::*;
 
@@ -244,19 +307,148 @@ impl AttributeValue {
             (ArrBool(l), ArrBool(r)) => l == r,
             (ArrI32(l), ArrI32(r)) => l == r,
             (ArrI64(l), ArrI64(r)) => l == r,
-            (ArrF32(l), ArrF32(r)) => l
-                .iter()
-                .map(|v| v.to_bits())
-                .eq(r.iter().map(|v| v.to_bits())),
-            (ArrF64(l), ArrF64(r)) => l
-                .iter()
-                .map(|v| v.to_bits())
-                .eq(r.iter().map(|v| v.to_bits())),
+            (ArrF32(l), ArrF32(r)) => {
+                l.len() == r.len()
+                    && l.iter()
+                        .map(|v| v.to_bits())
+                        .eq(r.iter().map(|v| v.to_bits()))
+            }
+            (ArrF64(l), ArrF64(r)) => {
+                l.len() == r.len()
+                    && l.iter()
+                        .map(|v| v.to_bits())
+                        .eq(r.iter().map(|v| v.to_bits()))
+            }
             (Binary(l), Binary(r)) => l == r,
             (String(l), String(r)) => l == r,
             _ => false,
         }
     }
+
+    /// Compares attribute values with the given absolute tolerance.
+    ///
+    /// `f32` and `f64` values (including array elements) are equal if the
+    /// absolute difference is less than or equal to `tolerance`.
+    /// NaNs are equal to each other (regardless of the bit pattern), and not
+    /// equal to any other values.
+    /// Values of other types are compared exactly.
+    ///
+    /// Values of different types are never equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::low::v7400::AttributeValue;
+    /// let l = AttributeValue::from(vec![0.0f64, 1.0, f64::NAN]);
+    /// let r = AttributeValue::from(vec![1e-9f64, 1.0, f64::NAN]);
+    /// assert!(l.approx_eq(&r, 1e-6));
+    /// assert!(!l.approx_eq(&r, 0.0));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        use AttributeValue::*;
+
+        /// Compares floating point values with the tolerance.
+        fn float_eq(l: f64, r: f64, tolerance: f64) -> bool {
+            match (l.is_nan(), r.is_nan()) {
+                (true, true) => true,
+                (false, false) => l == r || (l - r).abs() <= tolerance,
+                _ => false,
+            }
+        }
+
+        match (self, other) {
+            (F32(l), F32(r)) => float_eq(f64::from(*l), f64::from(*r), tolerance),
+            (F64(l), F64(r)) => float_eq(*l, *r, tolerance),
+            (ArrF32(l), ArrF32(r)) => {
+                l.len() == r.len()
+                    && l.iter()
+                        .zip(r)
+                        .all(|(l, r)| float_eq(f64::from(*l), f64::from(*r), tolerance))
+            }
+            (ArrF64(l), ArrF64(r)) => {
+                l.len() == r.len() && l.iter().zip(r).all(|(l, r)| float_eq(*l, *r, tolerance))
+            }
+            _ => self.strict_eq(other),
+        }
+    }
+
+    /// Feeds the value into the given hasher, with floating point values
+    /// quantized by the given step.
+    ///
+    /// `f32` and `f64` values (including array elements) are hashed as the
+    /// nearest multiple of `step`, and all NaNs are hashed as the same value.
+    /// Values of other types are hashed exactly.
+    /// The type of the value is also hashed.
+    ///
+    /// This can be used to bucket values for deduplication.
+    /// Note that values close to a boundary of quantization can be hashed
+    /// differently even if they are equal by
+    /// [`approx_eq`][`Self::approx_eq`] with small tolerance, so candidates
+    /// should be confirmed by `approx_eq` after bucketing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not a positive finite value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::low::v7400::AttributeValue;
+    /// use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+    ///
+    /// let hash = |value: &AttributeValue| {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     value.hash_quantized(1e-3, &mut hasher);
+    ///     hasher.finish()
+    /// };
+    /// let l = AttributeValue::from(vec![0.0f64, 1.0]);
+    /// let r = AttributeValue::from(vec![1e-6f64, 1.0 - 1e-6]);
+    /// assert_eq!(hash(&l), hash(&r));
+    /// ```
+    pub fn hash_quantized<H: Hasher>(&self, step: f64, state: &mut H) {
+        use AttributeValue::*;
+
+        /// Hashes the quantized floating point value.
+        fn hash_float<H: Hasher>(v: f64, step: f64, state: &mut H) {
+            if v.is_nan() {
+                state.write_u8(0);
+            } else {
+                state.write_u8(1);
+                // `+ 0.0` normalizes `-0.0` to `0.0`.
+                ((v / step).round() + 0.0).to_bits().hash(state);
+            }
+        }
+
+        assert!(
+            step.is_finite() && step > 0.0,
+            "Quantization step should be positive and finite: step={:?}",
+            step
+        );
+        self.type_().hash(state);
+        match self {
+            Bool(v) => v.hash(state),
+            I16(v) => v.hash(state),
+            I32(v) => v.hash(state),
+            I64(v) => v.hash(state),
+            F32(v) => hash_float(f64::from(*v), step, state),
+            F64(v) => hash_float(*v, step, state),
+            ArrBool(v) => v.hash(state),
+            ArrI32(v) => v.hash(state),
+            ArrI64(v) => v.hash(state),
+            ArrF32(v) => {
+                v.len().hash(state);
+                v.iter()
+                    .for_each(|v| hash_float(f64::from(*v), step, state));
+            }
+            ArrF64(v) => {
+                v.len().hash(state);
+                v.iter().for_each(|v| hash_float(*v, step, state));
+            }
+            Binary(v) => v.hash(state),
+            String(v) => v.hash(state),
+        }
+    }
 }
 
 /// Implement `From` trait.
@@ -299,3 +491,34 @@ impl_from! { map: &[f32], ArrF32, v, v.to_owned() }
 impl_from! { map: &[f64], ArrF64, v, v.to_owned() }
 impl_from! { map: &[u8], Binary, v, v.to_owned() }
 impl_from! { map: &str, String, v, v.to_owned() }
+impl_from! { map: &String, String, v, v.clone() }
+impl_from! { map: Box<str>, String, v, v.into() }
+impl_from! { map: std::borrow::Cow<'_, str>, String, v, v.into_owned() }
+
+/// Implement `From` trait for arrays and boxed slices.
+macro_rules! impl_from_array {
+    ($ty_elem:ty, $variant:ident) => {
+        impl<const N: usize> From<[$ty_elem; N]> for AttributeValue {
+            #[inline]
+            fn from(v: [$ty_elem; N]) -> Self {
+                AttributeValue::$variant(v.to_vec())
+            }
+        }
+
+        impl<const N: usize> From<&[$ty_elem; N]> for AttributeValue {
+            #[inline]
+            fn from(v: &[$ty_elem; N]) -> Self {
+                AttributeValue::$variant(v.to_vec())
+            }
+        }
+
+        impl_from! { map: Box<[$ty_elem]>, $variant, v, v.into_vec() }
+    };
+}
+
+impl_from_array! { bool, ArrBool }
+impl_from_array! { i32, ArrI32 }
+impl_from_array! { i64, ArrI64 }
+impl_from_array! { f32, ArrF32 }
+impl_from_array! { f64, ArrF64 }
+impl_from_array! { u8, Binary }
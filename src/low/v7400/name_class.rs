@@ -0,0 +1,126 @@
+//! Object name and class.
+//!
+//! Object names in FBX data are often stored as `"Name\u{0}\u{1}Class"`.
+
+use std::fmt;
+
+/// Separator between name and class.
+const SEPARATOR: &[u8] = b"\x00\x01";
+
+/// Splits the given bytes into name and class parts.
+///
+/// Returns `(bytes, None)` if the separator `"\u{0}\u{1}"` is not found.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::low::v7400::split_name_class_bytes;
+/// assert_eq!(
+///     split_name_class_bytes(b"Cube\x00\x01Model"),
+///     (&b"Cube"[..], Some(&b"Model"[..]))
+/// );
+/// assert_eq!(split_name_class_bytes(b"Cube"), (&b"Cube"[..], None));
+/// ```
+#[must_use]
+pub fn split_name_class_bytes(bytes: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match bytes
+        .windows(SEPARATOR.len())
+        .position(|window| window == SEPARATOR)
+    {
+        Some(pos) => (&bytes[..pos], Some(&bytes[(pos + SEPARATOR.len())..])),
+        None => (bytes, None),
+    }
+}
+
+/// Splits the given string into name and class parts.
+///
+/// Returns `(s, None)` if the separator `"\u{0}\u{1}"` is not found.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::low::v7400::split_name_class;
+/// assert_eq!(split_name_class("Cube\u{0}\u{1}Model"), ("Cube", Some("Model")));
+/// assert_eq!(split_name_class("Cube"), ("Cube", None));
+/// ```
+#[must_use]
+pub fn split_name_class(s: &str) -> (&str, Option<&str>) {
+    // The separator consists of ASCII characters, so the both sides are
+    // valid UTF-8 strings.
+    match s.find("\u{0}\u{1}") {
+        Some(pos) => (&s[..pos], Some(&s[(pos + SEPARATOR.len())..])),
+        None => (s, None),
+    }
+}
+
+/// Object name and class.
+///
+/// This represents the `"Name\u{0}\u{1}Class"` convention used in object
+/// names.
+/// The [`Display`][`fmt::Display`] impl formats the value in that convention,
+/// so it can be used to write object names.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::low::v7400::NameClass;
+/// let name_class = NameClass::parse("Cube\u{0}\u{1}Model");
+/// assert_eq!(name_class.name(), "Cube");
+/// assert_eq!(name_class.class(), Some("Model"));
+/// assert_eq!(name_class.to_string(), "Cube\u{0}\u{1}Model");
+///
+/// let name_only = NameClass::new("Cube", None);
+/// assert_eq!(name_only.to_string(), "Cube");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NameClass<'a> {
+    /// Name.
+    name: &'a str,
+    /// Class.
+    class: Option<&'a str>,
+}
+
+impl<'a> NameClass<'a> {
+    /// Creates a new `NameClass`.
+    #[inline]
+    #[must_use]
+    pub fn new(name: &'a str, class: Option<&'a str>) -> Self {
+        Self { name, class }
+    }
+
+    /// Parses the given string.
+    ///
+    /// If the separator is not found, the whole string is considered as a
+    /// name.
+    #[inline]
+    #[must_use]
+    pub fn parse(s: &'a str) -> Self {
+        let (name, class) = split_name_class(s);
+        Self { name, class }
+    }
+
+    /// Returns the name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the class.
+    #[inline]
+    #[must_use]
+    pub fn class(&self) -> Option<&'a str> {
+        self.class
+    }
+}
+
+impl fmt::Display for NameClass<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+        if let Some(class) = self.class {
+            f.write_str("\u{0}\u{1}")?;
+            f.write_str(class)?;
+        }
+        Ok(())
+    }
+}
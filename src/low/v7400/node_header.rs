@@ -1,8 +1,13 @@
 //! Node header.
 
-use crate::pull_parser::{
-    v7400::{FromParser, Parser},
-    Error as ParserError, ParserSource,
+use std::convert::TryFrom;
+
+use crate::{
+    low::FbxVersion,
+    pull_parser::{
+        v7400::{FromParser, Parser},
+        Error as ParserError, ParserSource,
+    },
 };
 
 /// Node header.
@@ -18,7 +23,79 @@ pub(crate) struct NodeHeader {
     pub(crate) bytelen_name: u8,
 }
 
+/// Node header validation error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum NodeHeaderError {
+    /// Node name is too long.
+    ///
+    /// This contains the byte length of the name.
+    #[cfg(feature = "writer")]
+    NameTooLong(usize),
+    /// End offset does not fit in the header field.
+    EndOffsetTooLarge(u64),
+    /// Too many attributes for the header field.
+    TooManyAttributes(u64),
+    /// Attributes are too long for the header field.
+    AttributesTooLong(u64),
+    /// Node ends beyond the end of the file.
+    ///
+    /// This contains the end offset of the node and the file length.
+    BeyondFileEnd(u64, u64),
+}
+
 impl NodeHeader {
+    /// Creates a new header for a node with the given name length.
+    ///
+    /// Other fields are set to zero, and should be updated when the node is
+    /// closed.
+    #[cfg(feature = "writer")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+    pub(crate) fn with_name_len(bytelen_name: usize) -> Result<Self, NodeHeaderError> {
+        let bytelen_name =
+            u8::try_from(bytelen_name).map_err(|_| NodeHeaderError::NameTooLong(bytelen_name))?;
+        Ok(Self {
+            end_offset: 0,
+            num_attributes: 0,
+            bytelen_attributes: 0,
+            bytelen_name,
+        })
+    }
+
+    /// Validates the header for the given FBX version.
+    ///
+    /// This checks the following:
+    ///
+    /// * For FBX versions with 32-bit node headers, all fields fit in `u32`.
+    /// * If the file length is given, the node ends before the end of the
+    ///   file.
+    ///
+    /// The parser validates headers on read, and the writer validates them on
+    /// write.
+    pub(crate) fn validate(
+        &self,
+        fbx_version: FbxVersion,
+        file_len: Option<u64>,
+    ) -> Result<(), NodeHeaderError> {
+        if !fbx_version.has_64bit_node_header() {
+            if u32::try_from(self.end_offset).is_err() {
+                return Err(NodeHeaderError::EndOffsetTooLarge(self.end_offset));
+            }
+            if u32::try_from(self.num_attributes).is_err() {
+                return Err(NodeHeaderError::TooManyAttributes(self.num_attributes));
+            }
+            if u32::try_from(self.bytelen_attributes).is_err() {
+                return Err(NodeHeaderError::AttributesTooLong(self.bytelen_attributes));
+            }
+        }
+        if let Some(file_len) = file_len {
+            if self.end_offset > file_len {
+                return Err(NodeHeaderError::BeyondFileEnd(self.end_offset, file_len));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Checks whether the entry indicates end of a node.
     #[inline]
     #[must_use]
@@ -29,6 +106,21 @@ impl NodeHeader {
             && self.bytelen_name == 0
     }
 
+    /// Returns the byte length of a node header for the given FBX version.
+    ///
+    /// This does not include the node name.
+    #[cfg(feature = "writer")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+    #[inline]
+    #[must_use]
+    pub(crate) const fn bytelen(fbx_version: FbxVersion) -> u64 {
+        if fbx_version.has_64bit_node_header() {
+            25
+        } else {
+            13
+        }
+    }
+
     /// Returns node end marker.
     #[cfg(feature = "writer")]
     #[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
@@ -49,18 +141,18 @@ impl FromParser for NodeHeader {
     where
         R: ParserSource,
     {
-        let (end_offset, num_attributes, bytelen_attributes) = if parser.fbx_version().raw() < 7500
-        {
-            let eo = u64::from(parser.parse::<u32>()?);
-            let na = u64::from(parser.parse::<u32>()?);
-            let ba = u64::from(parser.parse::<u32>()?);
-            (eo, na, ba)
-        } else {
-            let eo = parser.parse::<u64>()?;
-            let na = parser.parse::<u64>()?;
-            let ba = parser.parse::<u64>()?;
-            (eo, na, ba)
-        };
+        let (end_offset, num_attributes, bytelen_attributes) =
+            if !parser.fbx_version().has_64bit_node_header() {
+                let eo = u64::from(parser.parse::<u32>()?);
+                let na = u64::from(parser.parse::<u32>()?);
+                let ba = u64::from(parser.parse::<u32>()?);
+                (eo, na, ba)
+            } else {
+                let eo = parser.parse::<u64>()?;
+                let na = parser.parse::<u64>()?;
+                let ba = parser.parse::<u64>()?;
+                (eo, na, ba)
+            };
         let bytelen_name = parser.parse::<u8>()?;
 
         Ok(Self {
@@ -71,3 +163,37 @@ impl FromParser for NodeHeader {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let header = NodeHeader {
+            end_offset: u64::from(u32::MAX) + 1,
+            num_attributes: 1,
+            bytelen_attributes: 8,
+            bytelen_name: 4,
+        };
+        assert_eq!(
+            header.validate(FbxVersion::V7_4, None),
+            Err(NodeHeaderError::EndOffsetTooLarge(header.end_offset))
+        );
+        assert_eq!(header.validate(FbxVersion::V7_5, None), Ok(()));
+        assert_eq!(
+            header.validate(FbxVersion::V7_5, Some(42)),
+            Err(NodeHeaderError::BeyondFileEnd(header.end_offset, 42))
+        );
+
+        let header = NodeHeader {
+            num_attributes: u64::from(u32::MAX) + 1,
+            end_offset: 42,
+            ..header
+        };
+        assert_eq!(
+            header.validate(FbxVersion::V7_4, Some(42)),
+            Err(NodeHeaderError::TooManyAttributes(header.num_attributes))
+        );
+    }
+}
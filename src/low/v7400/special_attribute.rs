@@ -18,3 +18,23 @@ impl FromReader for SpecialAttributeHeader {
         Ok(Self { bytelen })
     }
 }
+
+#[cfg(feature = "writer")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+impl SpecialAttributeHeader {
+    /// Byte length of the serialized header.
+    pub(crate) const BYTELEN: usize = 4;
+
+    /// Returns the serialized header.
+    #[inline]
+    #[must_use]
+    pub(crate) fn to_bytes(self) -> [u8; Self::BYTELEN] {
+        self.bytelen.to_le_bytes()
+    }
+
+    /// Writes the header to the given writer.
+    #[inline]
+    pub(crate) fn write_to(self, mut writer: impl io::Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
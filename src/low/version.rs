@@ -1,16 +1,48 @@
 //! FBX version type.
 
+use std::fmt;
+
 /// FBX version.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FbxVersion(u32);
 
 impl FbxVersion {
+    /// Version 7.0.
+    pub const V7_0: Self = FbxVersion(7000);
+
+    /// Version 7.1.
+    pub const V7_1: Self = FbxVersion(7100);
+
+    /// Version 7.2.
+    pub const V7_2: Self = FbxVersion(7200);
+
+    /// Version 7.3.
+    pub const V7_3: Self = FbxVersion(7300);
+
     /// Version 7.4.
     pub const V7_4: Self = FbxVersion(7400);
 
     /// Version 7.5.
     pub const V7_5: Self = FbxVersion(7500);
 
+    /// Version 7.6.
+    pub const V7_6: Self = FbxVersion(7600);
+
+    /// Version 7.7.
+    pub const V7_7: Self = FbxVersion(7700);
+
+    /// Known released versions, in ascending order.
+    pub const KNOWN_VERSIONS: [Self; 8] = [
+        Self::V7_0,
+        Self::V7_1,
+        Self::V7_2,
+        Self::V7_3,
+        Self::V7_4,
+        Self::V7_5,
+        Self::V7_6,
+        Self::V7_7,
+    ];
+
     /// Creates a new `FbxVersion`.
     #[inline]
     #[must_use]
@@ -55,6 +87,45 @@ impl FbxVersion {
         let minor = self.minor();
         (major, minor)
     }
+
+    /// Returns whether the version is one of the known released versions.
+    #[inline]
+    #[must_use]
+    pub fn is_known(self) -> bool {
+        Self::KNOWN_VERSIONS.contains(&self)
+    }
+
+    /// Returns whether the version can be read by the pull parser.
+    ///
+    /// Note that only FBX 7.4 and 7.5 are tested, but other 7.x versions are
+    /// accepted too.
+    #[inline]
+    #[must_use]
+    pub const fn is_supported_by_parser(self) -> bool {
+        self.major() == 7
+    }
+
+    /// Returns whether the version can be written by the writer.
+    #[inline]
+    #[must_use]
+    pub const fn is_supported_by_writer(self) -> bool {
+        self.major() == 7
+    }
+
+    /// Returns whether the node header of the version uses 64-bit fields.
+    ///
+    /// This is `true` for FBX 7.5 and later, and `false` for earlier versions.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn has_64bit_node_header(self) -> bool {
+        self.raw() >= Self::V7_5.raw()
+    }
+}
+
+impl fmt::Display for FbxVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
 }
 
 #[cfg(test)]
@@ -76,4 +147,23 @@ mod tests {
             "Should return major and minor version"
         );
     }
+
+    #[test]
+    fn known_versions() {
+        assert!(FbxVersion::KNOWN_VERSIONS
+            .windows(2)
+            .all(|pair| pair[0] < pair[1]));
+        assert!(FbxVersion::V7_4.is_known());
+        assert!(!FbxVersion(7450).is_known());
+        assert!(!FbxVersion::V7_4.has_64bit_node_header());
+        assert!(FbxVersion::V7_5.has_64bit_node_header());
+        assert!(FbxVersion::V7_7.is_supported_by_parser());
+        assert!(!FbxVersion(6100).is_supported_by_writer());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(FbxVersion::V7_4.to_string(), "7.4");
+        assert_eq!(FbxVersion::V7_7.to_string(), "7.7");
+    }
 }
@@ -0,0 +1,71 @@
+//! Helpers used by exported macros.
+//!
+//! This is not a part of public API.
+
+/// Returns `true` if the given string is an integer literal without a type
+/// suffix.
+///
+/// The string is expected to be the result of `stringify!()` for an
+/// expression, so a leading minus sign (possibly followed by spaces) is
+/// accepted.
+#[must_use]
+pub const fn is_unsuffixed_int_literal(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    // Skip the sign.
+    while i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b' ') {
+        i += 1;
+    }
+    if i == bytes.len() || !bytes[i].is_ascii_digit() {
+        return false;
+    }
+
+    // Detect the radix prefix.
+    let is_hex = i + 1 < bytes.len() && bytes[i] == b'0' && bytes[i + 1] == b'x';
+    if i + 1 < bytes.len()
+        && bytes[i] == b'0'
+        && (bytes[i + 1] == b'x' || bytes[i + 1] == b'o' || bytes[i + 1] == b'b')
+    {
+        i += 2;
+    }
+
+    // Skip the digits.
+    while i < bytes.len()
+        && (bytes[i].is_ascii_digit()
+            || bytes[i] == b'_'
+            || (is_hex && bytes[i].is_ascii_hexdigit()))
+    {
+        i += 1;
+    }
+
+    // If something remains, it is a type suffix, a fraction, or an exponent.
+    i == bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsuffixed_int_literal() {
+        assert!(is_unsuffixed_int_literal("42"));
+        assert!(is_unsuffixed_int_literal("- 42"));
+        assert!(is_unsuffixed_int_literal("-42"));
+        assert!(is_unsuffixed_int_literal("1_000"));
+        assert!(is_unsuffixed_int_literal("0xff"));
+        assert!(is_unsuffixed_int_literal("0b1010"));
+    }
+
+    #[test]
+    fn not_unsuffixed_int_literal() {
+        assert!(!is_unsuffixed_int_literal("42i64"));
+        assert!(!is_unsuffixed_int_literal("- 42_i16"));
+        assert!(!is_unsuffixed_int_literal("0xffi32"));
+        assert!(!is_unsuffixed_int_literal("1.5"));
+        assert!(!is_unsuffixed_int_literal("1e5"));
+        assert!(!is_unsuffixed_int_literal("\"42\""));
+        assert!(!is_unsuffixed_int_literal("x"));
+        assert!(!is_unsuffixed_int_literal("vec![0; 4]"));
+    }
+}
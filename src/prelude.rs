@@ -0,0 +1,15 @@
+//! Commonly used traits and types.
+//!
+//! ```
+//! use fbxcel::prelude::*;
+//! ```
+
+#[cfg(feature = "fs")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "fs")))]
+pub use crate::fs::Fbx;
+pub use crate::pull_parser::{
+    any::AnyParser, v7400::LoadAttribute, ParserSource, SeekableParserSource,
+};
+#[cfg(feature = "tree")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
+pub use crate::tree::any::AnyTree;
@@ -0,0 +1,213 @@
+//! Quick probe of FBX files.
+
+use std::{
+    error, fmt,
+    io::{Read, Seek},
+};
+
+use crate::{
+    low::{self, FbxVersion},
+    pull_parser::{
+        self,
+        any::AnyParser,
+        v7400::{Event, Parser},
+        ParserSource,
+    },
+    ErrorCode,
+};
+
+/// Name of the top-level node which contains objects.
+const OBJECTS_NODE_NAME: &str = "Objects";
+
+/// Name of the top-level node which contains animation takes.
+const TAKES_NODE_NAME: &str = "Takes";
+
+/// File-level metadata collected by [`probe`].
+#[derive(Debug)]
+pub struct ProbeInfo {
+    /// FBX version.
+    fbx_version: FbxVersion,
+    /// Names of the top-level nodes, in the document order.
+    toplevel_node_names: Vec<String>,
+    /// Number of direct children of the `Objects` node, if the node exists.
+    objects_count: Option<usize>,
+    /// FBX footer.
+    footer: Result<Box<low::v7400::FbxFooter>, pull_parser::Error>,
+}
+
+impl ProbeInfo {
+    /// Returns the FBX version.
+    #[inline]
+    #[must_use]
+    pub fn fbx_version(&self) -> FbxVersion {
+        self.fbx_version
+    }
+
+    /// Returns the names of the top-level nodes, in the document order.
+    #[inline]
+    #[must_use]
+    pub fn toplevel_node_names(&self) -> &[String] {
+        &self.toplevel_node_names
+    }
+
+    /// Returns the number of the top-level nodes.
+    #[inline]
+    #[must_use]
+    pub fn toplevel_nodes_count(&self) -> usize {
+        self.toplevel_node_names.len()
+    }
+
+    /// Returns the number of direct children of the `Objects` node.
+    ///
+    /// Returns `None` if the document has no `Objects` node.
+    #[inline]
+    #[must_use]
+    pub fn objects_count(&self) -> Option<usize> {
+        self.objects_count
+    }
+
+    /// Returns `true` if the document has a top-level `Objects` node.
+    #[inline]
+    #[must_use]
+    pub fn has_objects(&self) -> bool {
+        self.has_toplevel_node(OBJECTS_NODE_NAME)
+    }
+
+    /// Returns `true` if the document has a top-level `Takes` node.
+    #[inline]
+    #[must_use]
+    pub fn has_takes(&self) -> bool {
+        self.has_toplevel_node(TAKES_NODE_NAME)
+    }
+
+    /// Returns `true` if the document has a top-level node with the given name.
+    #[must_use]
+    pub fn has_toplevel_node(&self, name: &str) -> bool {
+        self.toplevel_node_names.iter().any(|v| v == name)
+    }
+
+    /// Returns the FBX footer.
+    #[inline]
+    pub fn footer(&self) -> Result<&low::v7400::FbxFooter, &pull_parser::Error> {
+        self.footer.as_deref()
+    }
+}
+
+/// Probes the FBX data and collects file-level metadata.
+///
+/// This reads the FBX header, the top-level node headers, the direct children
+/// headers of the `Objects` node, and the FBX footer.
+/// Other nodes and all node attributes are skipped without being read, so
+/// this is much cheaper than loading the whole document.
+///
+/// If only the FBX version is necessary, use [`FbxHeader::load`] instead.
+/// It works also for non-seekable readers.
+///
+/// Warnings are ignored.
+///
+/// [`FbxHeader::load`]: `crate::low::FbxHeader::load`
+pub fn probe<R: Read + Seek>(reader: R) -> Result<ProbeInfo, ProbeError> {
+    match pull_parser::any::from_seekable_reader(reader)? {
+        AnyParser::V7400(mut parser) => probe_v7400(&mut parser),
+    }
+}
+
+/// Probes the FBX data using the given v7400 parser.
+fn probe_v7400<R: ParserSource>(parser: &mut Parser<R>) -> Result<ProbeInfo, ProbeError> {
+    let fbx_version = parser.fbx_version();
+    let mut toplevel_node_names = Vec::new();
+    let mut objects_count = None;
+
+    loop {
+        let name = match parser.next_event()? {
+            Event::StartNode(start) => start.name().to_owned(),
+            Event::EndNode => {
+                unreachable!("Top-level nodes should be closed by `skip_current_node()`")
+            }
+            Event::SkippedNode { .. } => continue,
+            Event::EndFbx(footer) => {
+                return Ok(ProbeInfo {
+                    fbx_version,
+                    toplevel_node_names,
+                    objects_count,
+                    footer,
+                })
+            }
+        };
+        if name == OBJECTS_NODE_NAME && objects_count.is_none() {
+            objects_count = Some(count_children(parser)?);
+        } else {
+            parser.skip_current_node()?;
+        }
+        toplevel_node_names.push(name);
+    }
+}
+
+/// Counts and skips the children of the current node, and closes the node.
+fn count_children<R: ParserSource>(parser: &mut Parser<R>) -> Result<usize, pull_parser::Error> {
+    let mut count = 0;
+    loop {
+        match parser.next_event()? {
+            Event::StartNode(_) => {
+                count += 1;
+                parser.skip_current_node()?;
+            }
+            Event::EndNode => return Ok(count),
+            Event::SkippedNode { .. } => {}
+            Event::EndFbx(_) => unreachable!("The current node should be closed before `EndFbx`"),
+        }
+    }
+}
+
+/// Probe error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ProbeError {
+    /// Parser creation error.
+    ParserCreation(pull_parser::any::Error),
+    /// Parser error.
+    Parser(pull_parser::Error),
+}
+
+impl ProbeError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ProbeError::ParserCreation(e) => e.code(),
+            ProbeError::Parser(e) => e.code(),
+        }
+    }
+}
+
+impl error::Error for ProbeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ProbeError::ParserCreation(e) => Some(e),
+            ProbeError::Parser(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProbeError::ParserCreation(e) => write!(f, "Failed to create a parser: {}", e),
+            ProbeError::Parser(e) => write!(f, "Parser error: {}", e),
+        }
+    }
+}
+
+impl From<pull_parser::any::Error> for ProbeError {
+    #[inline]
+    fn from(e: pull_parser::any::Error) -> Self {
+        ProbeError::ParserCreation(e)
+    }
+}
+
+impl From<pull_parser::Error> for ProbeError {
+    #[inline]
+    fn from(e: pull_parser::Error) -> Self {
+        ProbeError::Parser(e)
+    }
+}
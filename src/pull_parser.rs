@@ -85,7 +85,7 @@
 pub use self::{
     error::{Error, Result, Warning},
     position::SyntacticPosition,
-    reader::ParserSource,
+    reader::{ParserSource, SeekableParserSource},
     version::ParserVersion,
 };
 
@@ -87,3 +87,24 @@ pub fn from_seekable_reader<R: Read + Seek>(mut reader: R) -> Result<AnyParser<S
         }
     }
 }
+
+/// Opens the file and creates a parser.
+///
+/// The file is read with a buffered reader as a seekable source.
+/// This is same as [`Fbx::open`][`crate::Fbx::open`], and to customize the
+/// buffer size or the parser configuration, use
+/// [`Fbx::options`][`crate::Fbx::options`].
+///
+/// # Examples
+///
+/// ```no_run
+/// let parser = fbxcel::pull_parser::any::from_path("sample.fbx")?;
+/// println!("FBX version: {}", parser.fbx_version());
+/// # Ok::<_, fbxcel::pull_parser::any::Error>(())
+/// ```
+#[cfg(feature = "fs")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "fs")))]
+#[inline]
+pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<AnyParser<crate::fs::FileSource>> {
+    crate::fs::Fbx::open(path)
+}
@@ -2,7 +2,10 @@
 
 use std::{error, fmt};
 
-use crate::low::{FbxVersion, HeaderError};
+use crate::{
+    low::{FbxVersion, HeaderError},
+    ErrorCode,
+};
 
 /// AnyTree load result.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -17,6 +20,17 @@ pub enum Error {
     UnsupportedVersion(FbxVersion),
 }
 
+impl Error {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Header(e) => e.code(),
+            Error::UnsupportedVersion(_) => ErrorCode::UnsupportedFbxVersion,
+        }
+    }
+}
+
 impl error::Error for Error {
     #[inline]
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
@@ -7,7 +7,7 @@ use std::{error, fmt, io};
 use crate::pull_parser::SyntacticPosition;
 
 pub use self::{
-    data::{Compression, DataError},
+    data::{Compression, DataError, Limit},
     operation::OperationError,
     warning::Warning,
 };
@@ -34,6 +34,13 @@ impl Error {
         self.repr.error.kind()
     }
 
+    /// Returns the machine-readable error code.
+    #[inline]
+    #[must_use]
+    pub fn code(&self) -> crate::ErrorCode {
+        self.repr.error.code()
+    }
+
     /// Returns a reference to the inner error container.
     #[inline]
     #[must_use]
@@ -185,6 +192,17 @@ impl ErrorContainer {
         }
     }
 
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> crate::ErrorCode {
+        match self {
+            ErrorContainer::Data(e) => e.code(),
+            ErrorContainer::Io(_) => crate::ErrorCode::Io,
+            ErrorContainer::Operation(e) => e.code(),
+            ErrorContainer::Warning(_) => crate::ErrorCode::CriticalWarning,
+        }
+    }
+
     /// Returns `&dyn std::error::Error`.
     #[must_use]
     pub fn as_error(&self) -> &(dyn 'static + error::Error) {
@@ -4,6 +4,8 @@
 
 use std::{error, fmt, string::FromUtf8Error};
 
+use crate::ErrorCode;
+
 /// Data error.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -25,6 +27,8 @@ pub enum DataError {
     ///
     /// This error indicates that the node name is non-valid UTF-8.
     InvalidNodeNameEncoding(FromUtf8Error),
+    /// A limit specified by the parser configuration is exceeded.
+    LimitExceeded(Limit),
     /// Node attribute error.
     ///
     /// This error indicates that some error happened while reading node
@@ -46,6 +50,24 @@ pub enum DataError {
     UnexpectedAttribute(String, String),
 }
 
+impl DataError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            DataError::BrokenCompression(..) => ErrorCode::BrokenCompression,
+            DataError::BrokenFbxFooter => ErrorCode::BrokenFbxFooter,
+            DataError::InvalidArrayAttributeEncoding(_) => ErrorCode::InvalidArrayAttributeEncoding,
+            DataError::InvalidAttributeTypeCode(_) => ErrorCode::InvalidAttributeTypeCode,
+            DataError::InvalidNodeNameEncoding(_) => ErrorCode::InvalidNodeNameEncoding,
+            DataError::LimitExceeded(_) => ErrorCode::LimitExceeded,
+            DataError::NodeAttributeError => ErrorCode::NodeAttributeError,
+            DataError::NodeLengthMismatch(..) => ErrorCode::NodeLengthMismatch,
+            DataError::UnexpectedAttribute(..) => ErrorCode::UnexpectedAttribute,
+        }
+    }
+}
+
 impl error::Error for DataError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
@@ -74,6 +96,7 @@ impl fmt::Display for DataError {
             DataError::InvalidNodeNameEncoding(e) => {
                 write!(f, "Invalid node name encoding: {:?}", e)
             }
+            DataError::LimitExceeded(limit) => write!(f, "Limit exceeded: {}", limit),
             DataError::NodeAttributeError => {
                 write!(f, "Some error occured while reading node attributes")
             }
@@ -97,3 +120,38 @@ pub enum Compression {
     /// ZLIB compression.
     Zlib,
 }
+
+/// Parser limit.
+///
+/// Each variant has the limit value specified by the parser configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// Maximum node depth.
+    Depth(usize),
+    /// Maximum number of nodes.
+    NodeCount(u64),
+    /// Maximum byte length of the attributes of a single node.
+    AttributeBytelen(u64),
+    /// Maximum byte length of the data after the FBX footer.
+    TrailingDataLength(u64),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Depth(v) => write!(f, "node depth exceeds {}", v),
+            Limit::NodeCount(v) => write!(f, "number of nodes exceeds {}", v),
+            Limit::AttributeBytelen(v) => {
+                write!(f, "byte length of node attributes exceeds {}", v)
+            }
+            Limit::TrailingDataLength(v) => {
+                write!(
+                    f,
+                    "byte length of the data after the FBX footer exceeds {}",
+                    v
+                )
+            }
+        }
+    }
+}
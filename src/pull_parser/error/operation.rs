@@ -2,7 +2,7 @@
 
 use std::{error, fmt};
 
-use crate::{low::FbxVersion, pull_parser::ParserVersion};
+use crate::{low::FbxVersion, pull_parser::ParserVersion, ErrorCode};
 
 /// Invalid operation.
 #[derive(Debug)]
@@ -14,6 +14,23 @@ pub enum OperationError {
     AlreadyFinished,
     /// Attempt to create a parser with unsupported FBX version.
     UnsupportedFbxVersion(ParserVersion, FbxVersion),
+    /// Attempt to seek to the node attribute out of range.
+    ///
+    /// This contains the requested index and the number of attributes.
+    AttributeIndexOutOfRange(u64, u64),
+}
+
+impl OperationError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            OperationError::AlreadyAborted => ErrorCode::ParserAlreadyAborted,
+            OperationError::AlreadyFinished => ErrorCode::ParserAlreadyFinished,
+            OperationError::UnsupportedFbxVersion(..) => ErrorCode::ParserUnsupportedFbxVersion,
+            OperationError::AttributeIndexOutOfRange(..) => ErrorCode::AttributeIndexOutOfRange,
+        }
+    }
 }
 
 impl error::Error for OperationError {}
@@ -33,6 +50,11 @@ impl fmt::Display for OperationError {
                 "Unsupported FBX version: parser={:?}, fbx={:?}",
                 parser, fbx
             ),
+            OperationError::AttributeIndexOutOfRange(index, count) => write!(
+                f,
+                "Attribute index out of range: index={}, count={}",
+                index, count
+            ),
         }
     }
 }
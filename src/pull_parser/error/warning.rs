@@ -7,6 +7,10 @@ use std::{error, fmt};
 #[non_exhaustive]
 pub enum Warning {
     /// Node name is empty.
+    ///
+    /// Some exporters emit nodes without names.
+    /// If the warning handler ignores this, the node is parsed (and loaded
+    /// into a tree) with the empty name as usual.
     EmptyNodeName,
     /// Extra (unexpected) node end marker found.
     ExtraNodeEndMarker,
@@ -18,12 +22,42 @@ pub enum Warning {
     /// third-party exporters emits them wrongly with `0x00` and `0x01`, and
     /// those will be ignored by official SDK and tools.
     IncorrectBooleanRepresentation,
+    /// Incorrect boolean representation in an array attribute.
+    ///
+    /// This contains the number of incorrect elements, and the index of the
+    /// first one.
+    /// Only elements read by the loader are checked.
+    ///
+    /// See [`IncorrectBooleanRepresentation`][`Self::IncorrectBooleanRepresentation`]
+    /// for detail.
+    IncorrectBooleanArrayRepresentation(u32, u32),
     /// Footer padding length is invalid.
     InvalidFooterPaddingLength(usize, usize),
     /// Missing a node end marker where the marker is expected.
     MissingNodeEndMarker,
     /// Unexpected value for footer fields (mainly for unknown fields).
     UnexpectedFooterFieldValue,
+    /// Unread node attributes are implicitly skipped.
+    ///
+    /// This contains the number of the skipped attributes.
+    UnreadAttributesSkipped(u64),
+    /// Extra data found after the FBX footer.
+    ///
+    /// This contains the byte length of the data.
+    TrailingData(u64),
+    /// Node attributes run beyond their declared byte length.
+    ///
+    /// This contains the excess byte length.
+    AttributesLengthExceeded(u64),
+    /// Extra data found after the declared number of node attributes.
+    ///
+    /// This contains the byte length of the data.
+    ExtraAttributeData(u64),
+    /// Declared node attributes start at or beyond the declared end of the
+    /// attributes.
+    ///
+    /// This contains the number of such attributes.
+    MissingAttributeData(u64),
 }
 
 impl error::Error for Warning {}
@@ -36,6 +70,11 @@ impl fmt::Display for Warning {
             Warning::IncorrectBooleanRepresentation => {
                 write!(f, "Incorrect boolean representation")
             }
+            Warning::IncorrectBooleanArrayRepresentation(count, first_index) => write!(
+                f,
+                "Incorrect boolean representation in array: count={}, first_index={}",
+                count, first_index
+            ),
             Warning::InvalidFooterPaddingLength(expected, got) => write!(
                 f,
                 "Invalid footer padding length: expected {} bytes, got {} bytes",
@@ -43,6 +82,29 @@ impl fmt::Display for Warning {
             ),
             Warning::MissingNodeEndMarker => write!(f, "Missing node end marker"),
             Warning::UnexpectedFooterFieldValue => write!(f, "Unexpected footer field value"),
+            Warning::UnreadAttributesSkipped(count) => write!(
+                f,
+                "Unread node attributes are implicitly skipped: count={}",
+                count
+            ),
+            Warning::TrailingData(len) => {
+                write!(f, "Extra data found after the FBX footer: {} bytes", len)
+            }
+            Warning::AttributesLengthExceeded(len) => write!(
+                f,
+                "Node attributes run beyond their declared byte length: {} bytes",
+                len
+            ),
+            Warning::ExtraAttributeData(len) => write!(
+                f,
+                "Extra data found after the node attributes: {} bytes",
+                len
+            ),
+            Warning::MissingAttributeData(count) => write!(
+                f,
+                "Node attributes start beyond their declared byte length: count={}",
+                count
+            ),
         }
     }
 }
@@ -12,15 +12,17 @@
 //! types or modules will provide simple functions to automatically wrap readers
 //! if necessary.
 
-use std::io;
+use std::{io, num::NonZeroUsize};
 
 pub use self::{
     position_cache::PositionCacheReader,
     source::{PlainSource, SeekableSource},
+    window::WindowReader,
 };
 
 mod position_cache;
 mod source;
+mod window;
 
 /// A trait for types which can be data sources.
 ///
@@ -94,6 +96,61 @@ pub trait ParserSource: Sized + io::Read {
             .expect("Attempt to skip backward");
         self.skip_distance(distance)
     }
+
+    /// Returns the total length of the source in bytes, if known.
+    ///
+    /// The length is a position of the end of the source, i.e. it is
+    /// comparable with [`position()`][`Self::position`].
+    ///
+    /// The parser uses this to detect broken node lengths early.
+    /// The default implementation returns `None`.
+    #[inline]
+    #[must_use]
+    fn total_len(&self) -> Option<u64> {
+        None
+    }
+
+    /// Sets the maximum distance to skip by reading instead of seeking, if
+    /// the source can seek.
+    ///
+    /// Short skips (such as padding between attributes) are frequent, and
+    /// seeking for them can be expensive: it may cost a syscall, and
+    /// [`std::io::BufReader`] discards its buffer on seek.
+    /// Skips no longer than the threshold read and discard the data instead,
+    /// so buffered readers can consume the skipped bytes from their buffer.
+    ///
+    /// `None` means the default of the source.
+    /// The parser calls this with
+    /// [`ParserConfig::skip_small_threshold`][`crate::pull_parser::v7400::ParserConfig::skip_small_threshold`].
+    /// The default implementation does nothing.
+    #[inline]
+    fn set_skip_small_threshold(&mut self, threshold: Option<u64>) {
+        let _ = threshold;
+    }
+
+    /// Sets the size of the buffer used for skipping by reading, if the
+    /// source uses one.
+    ///
+    /// Smaller buffers reduce the stack usage, and larger buffers reduce the
+    /// number of reads.
+    ///
+    /// `None` means the default of the source.
+    /// The parser calls this with
+    /// [`ParserConfig::skip_buffer_size`][`crate::pull_parser::v7400::ParserConfig::skip_buffer_size`].
+    /// The default implementation does nothing.
+    #[inline]
+    fn set_skip_buffer_size(&mut self, size: Option<NonZeroUsize>) {
+        let _ = size;
+    }
+}
+
+/// A trait for data sources which can seek to arbitrary positions.
+///
+/// This is implemented for [`SeekableSource`] and [`PositionCacheReader`]
+/// with readers implementing [`std::io::Seek`].
+pub trait SeekableParserSource: ParserSource {
+    /// Seeks to the given position, which may be behind the current position.
+    fn seek_to(&mut self, pos: u64) -> io::Result<()>;
 }
 
 impl<R: ParserSource> ParserSource for &mut R {
@@ -111,4 +168,26 @@ impl<R: ParserSource> ParserSource for &mut R {
     fn skip_to(&mut self, pos: u64) -> io::Result<()> {
         (**self).skip_to(pos)
     }
+
+    #[inline]
+    fn total_len(&self) -> Option<u64> {
+        (**self).total_len()
+    }
+
+    #[inline]
+    fn set_skip_small_threshold(&mut self, threshold: Option<u64>) {
+        (**self).set_skip_small_threshold(threshold)
+    }
+
+    #[inline]
+    fn set_skip_buffer_size(&mut self, size: Option<NonZeroUsize>) {
+        (**self).set_skip_buffer_size(size)
+    }
+}
+
+impl<R: SeekableParserSource> SeekableParserSource for &mut R {
+    #[inline]
+    fn seek_to(&mut self, pos: u64) -> io::Result<()> {
+        (**self).seek_to(pos)
+    }
 }
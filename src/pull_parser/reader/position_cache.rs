@@ -2,7 +2,7 @@
 
 use std::io::{self, SeekFrom};
 
-use crate::pull_parser::ParserSource;
+use crate::pull_parser::{reader::SeekableParserSource, ParserSource};
 
 /// A reader with position cache.
 ///
@@ -100,6 +100,40 @@ impl<R: io::Read> PositionCacheReader<R> {
         Ok(())
     }
 
+    /// Seeks to the given position, which may be behind the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::reader::PositionCacheReader;
+    /// use fbxcel::pull_parser::ParserSource;
+    ///
+    /// let msg = "Hello, world!";
+    /// let mut reader = std::io::Cursor::new(msg);
+    /// let mut reader = PositionCacheReader::new(&mut reader);
+    ///
+    /// reader.skip_distance(7).expect("Failed to skip");
+    /// reader.seek_to(2).expect("Failed to seek");
+    /// assert_eq!(reader.position(), 2);
+    /// ```
+    pub fn seek_to(&mut self, pos: u64) -> io::Result<()>
+    where
+        R: io::Seek,
+    {
+        let current = self.position as u64;
+        if pos >= current {
+            return self.skip_distance(pos - current);
+        }
+        let mut distance = current - pos;
+        while distance > 0 {
+            let part = std::cmp::min(distance, i64::MAX as u64);
+            self.inner.seek(SeekFrom::Current(-(part as i64)))?;
+            self.position -= part as usize;
+            distance -= part;
+        }
+        Ok(())
+    }
+
     /// Advances the position counter.
     #[inline]
     fn advance(&mut self, n: usize) {
@@ -135,6 +169,13 @@ impl<R: io::Read> ParserSource for PositionCacheReader<R> {
     }
 }
 
+impl<R: io::Read + io::Seek> SeekableParserSource for PositionCacheReader<R> {
+    #[inline]
+    fn seek_to(&mut self, pos: u64) -> io::Result<()> {
+        self.seek_to(pos)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,11 @@
 //! Wrapper types for parser sources.
 
-use std::io;
+use std::{io, num::NonZeroUsize};
 
-use crate::pull_parser::{reader::PositionCacheReader, ParserSource};
+use crate::pull_parser::{
+    reader::{PositionCacheReader, SeekableParserSource},
+    ParserSource,
+};
 
 /// Source with plain reader backend.
 ///
@@ -16,6 +19,15 @@ use crate::pull_parser::{reader::PositionCacheReader, ParserSource};
 pub struct PlainSource<R> {
     /// Inner reader.
     inner: PositionCacheReader<R>,
+    /// Size of the buffer used for skipping.
+    ///
+    /// `None` means [`MAX_SKIP_BUFFER_SIZE`][`Self::MAX_SKIP_BUFFER_SIZE`].
+    skip_buf_size: Option<NonZeroUsize>,
+}
+
+impl<R> PlainSource<R> {
+    /// Maximum size of the buffer used for skipping.
+    pub const MAX_SKIP_BUFFER_SIZE: usize = PLAIN_SKIP_BUF_LEN;
 }
 
 impl<R: io::Read> PlainSource<R> {
@@ -25,6 +37,7 @@ impl<R: io::Read> PlainSource<R> {
     pub fn new(inner: R) -> Self {
         Self {
             inner: PositionCacheReader::new(inner),
+            skip_buf_size: None,
         }
     }
 
@@ -51,6 +64,7 @@ impl<R: io::Read> PlainSource<R> {
     pub fn with_offset(inner: R, offset: usize) -> Self {
         Self {
             inner: PositionCacheReader::with_offset(inner, offset),
+            skip_buf_size: None,
         }
     }
 }
@@ -80,14 +94,37 @@ impl<R: io::Read> ParserSource for PlainSource<R> {
         self.inner.position() as u64
     }
 
-    #[inline]
     fn skip_distance(&mut self, distance: u64) -> io::Result<()> {
-        // NOTE: `self.inner.take(distance)` is E0507.
-        io::copy(
-            &mut io::Read::take(&mut self.inner, distance),
-            &mut io::sink(),
-        )?;
-        Ok(())
+        let mut buf = [0; PLAIN_SKIP_BUF_LEN];
+        let buf_size = self
+            .skip_buf_size
+            .map_or(buf.len(), |size| std::cmp::min(size.get(), buf.len()));
+        skip_by_reading(&mut self.inner, distance, &mut buf[..buf_size])
+    }
+
+    /// Sets the size of the buffer used for skipping.
+    ///
+    /// Skipped data is read by chunks of this size into a stack buffer.
+    /// The size is capped at [`MAX_SKIP_BUFFER_SIZE`][`Self::MAX_SKIP_BUFFER_SIZE`],
+    /// and `None` (default) means the maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::reader::PlainSource;
+    /// use fbxcel::pull_parser::ParserSource;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut reader = std::io::Cursor::new("Hello, world!");
+    /// let mut reader = PlainSource::new(&mut reader);
+    /// reader.set_skip_buffer_size(NonZeroUsize::new(4));
+    ///
+    /// reader.skip_distance(7).expect("Failed to skip");
+    /// assert_eq!(reader.position(), 7);
+    /// ```
+    #[inline]
+    fn set_skip_buffer_size(&mut self, size: Option<NonZeroUsize>) {
+        self.skip_buf_size = size;
     }
 }
 
@@ -102,6 +139,18 @@ impl<R: io::Read> ParserSource for PlainSource<R> {
 pub struct SeekableSource<R> {
     /// Inner reader.
     inner: PositionCacheReader<R>,
+    /// Total length of the source.
+    ///
+    /// `None` if the length could not be determined.
+    total_len: Option<u64>,
+    /// Maximum distance to skip by reading instead of seeking.
+    skip_small_threshold: u64,
+}
+
+impl<R> SeekableSource<R> {
+    /// Default threshold for
+    /// [`set_skip_small_threshold`][`ParserSource::set_skip_small_threshold`].
+    pub const DEFAULT_SKIP_SMALL_THRESHOLD: u64 = 64;
 }
 
 impl<R: io::Read + io::Seek> SeekableSource<R> {
@@ -109,13 +158,14 @@ impl<R: io::Read + io::Seek> SeekableSource<R> {
     #[inline]
     #[must_use]
     pub fn new(inner: R) -> Self {
-        Self {
-            inner: PositionCacheReader::new(inner),
-        }
+        Self::with_offset(inner, 0)
     }
 
     /// Creates a new `SeekableSource` with the given offset.
     ///
+    /// The total length of the source is measured by seeking to the end of
+    /// the reader and back.
+    ///
     /// # Examples
     ///
     /// ```
@@ -131,16 +181,61 @@ impl<R: io::Read + io::Seek> SeekableSource<R> {
     /// std::io::copy(&mut reader, &mut std::io::sink())
     ///     .expect("Should never fail");
     /// assert_eq!(reader.position(), len + 42);
+    /// assert_eq!(reader.total_len(), Some(len + 42));
     /// ```
-    #[inline]
     #[must_use]
-    pub fn with_offset(inner: R, offset: usize) -> Self {
+    pub fn with_offset(mut inner: R, offset: usize) -> Self {
+        let total_len = remaining_len(&mut inner)
+            .ok()
+            .map(|len| len + offset as u64);
         Self {
             inner: PositionCacheReader::with_offset(inner, offset),
+            total_len,
+            skip_small_threshold: Self::DEFAULT_SKIP_SMALL_THRESHOLD,
         }
     }
 }
 
+/// Size of the stack buffer used for skipping in [`PlainSource`].
+const PLAIN_SKIP_BUF_LEN: usize = 8 * 1024;
+
+/// Size of the stack buffer used for skipping by reading in
+/// [`SeekableSource`].
+const SKIP_BUF_LEN: usize = 64;
+
+/// Skips the given distance by reading and discarding the data into the given
+/// buffer.
+///
+/// Stops silently at the end of the reader.
+fn skip_by_reading(
+    reader: &mut impl io::Read,
+    mut distance: u64,
+    buf: &mut [u8],
+) -> io::Result<()> {
+    while distance > 0 {
+        let len = std::cmp::min(buf.len() as u64, distance) as usize;
+        match reader.read(&mut buf[..len]) {
+            Ok(0) => break,
+            Ok(read) => distance -= read as u64,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the length from the current position to the end of the reader.
+///
+/// The position of the reader is restored.
+fn remaining_len<R: io::Seek>(reader: &mut R) -> io::Result<u64> {
+    let current = reader.stream_position()?;
+    let end = reader.seek(io::SeekFrom::End(0))?;
+    if end != current {
+        reader.seek(io::SeekFrom::Start(current))?;
+    }
+    Ok(end.saturating_sub(current))
+}
+
 impl<R: io::Read> io::Read for SeekableSource<R> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
@@ -166,10 +261,47 @@ impl<R: io::Read + io::Seek> ParserSource for SeekableSource<R> {
         self.inner.position() as u64
     }
 
-    #[inline]
     fn skip_distance(&mut self, distance: u64) -> io::Result<()> {
-        // `PositionCacheReader<R>::skip_distance` will be available only when
-        // `R: io::Seek`, and it will use `io::Seek::seek` efficiently.
-        self.inner.skip_distance(distance)
+        if distance > self.skip_small_threshold {
+            // `PositionCacheReader<R>::skip_distance` will be available only
+            // when `R: io::Seek`, and it will use `io::Seek::seek` efficiently.
+            return self.inner.skip_distance(distance);
+        }
+        skip_by_reading(&mut self.inner, distance, &mut [0; SKIP_BUF_LEN])
+    }
+
+    /// Sets the maximum distance to skip by reading instead of seeking.
+    ///
+    /// `Some(0)` makes all skips seek.
+    /// `None` means [`DEFAULT_SKIP_SMALL_THRESHOLD`][`Self::DEFAULT_SKIP_SMALL_THRESHOLD`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::reader::SeekableSource;
+    /// use fbxcel::pull_parser::ParserSource;
+    ///
+    /// let reader = std::io::Cursor::new("Hello, world!");
+    /// let mut reader = SeekableSource::new(std::io::BufReader::new(reader));
+    /// reader.set_skip_small_threshold(Some(16));
+    ///
+    /// reader.skip_distance(7).expect("Failed to skip");
+    /// assert_eq!(reader.position(), 7);
+    /// ```
+    #[inline]
+    fn set_skip_small_threshold(&mut self, threshold: Option<u64>) {
+        self.skip_small_threshold = threshold.unwrap_or(Self::DEFAULT_SKIP_SMALL_THRESHOLD);
+    }
+
+    #[inline]
+    fn total_len(&self) -> Option<u64> {
+        self.total_len
+    }
+}
+
+impl<R: io::Read + io::Seek> SeekableParserSource for SeekableSource<R> {
+    #[inline]
+    fn seek_to(&mut self, pos: u64) -> io::Result<()> {
+        self.inner.seek_to(pos)
     }
 }
@@ -0,0 +1,194 @@
+//! Offset-limited reader.
+
+use std::{io, num::NonZeroUsize};
+
+use crate::pull_parser::ParserSource;
+
+/// A parser source restricted to a byte range.
+///
+/// Reads and skips never go beyond the end offset of the window, and
+/// [`position`][`ParserSource::position`] returns the same offset as the
+/// inner source.
+/// This is useful to hand a bounded view to sub-parsers (such as loaders for
+/// embedded payloads), so that they cannot run off the end of their region.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::pull_parser::reader::{PlainSource, WindowReader};
+/// use std::io::Read;
+/// use fbxcel::pull_parser::ParserSource;
+///
+/// let mut source = PlainSource::new(std::io::Cursor::new("Hello, world!"));
+/// source.skip_distance(7).expect("Failed to skip");
+/// let mut window = WindowReader::new(&mut source, 12);
+///
+/// let mut buf = String::new();
+/// window.read_to_string(&mut buf).expect("Failed to read");
+/// assert_eq!(buf, "world");
+/// assert_eq!(window.position(), 12);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WindowReader<R> {
+    /// Inner source.
+    inner: R,
+    /// End offset of the window.
+    end: u64,
+}
+
+impl<R: ParserSource> WindowReader<R> {
+    /// Creates a new `WindowReader` which ends at the given offset.
+    ///
+    /// The window starts at the current position of the inner source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the end offset is behind the current position.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: R, end: u64) -> Self {
+        assert!(
+            inner.position() <= end,
+            "The window should not end before the current position"
+        );
+        Self { inner, end }
+    }
+
+    /// Returns the end offset of the window.
+    #[inline]
+    #[must_use]
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Returns the number of bytes remaining in the window.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.end - self.inner.position()
+    }
+
+    /// Unwraps the wrapper and returns the inner source.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ParserSource> io::Read for WindowReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.remaining();
+        let len = if (buf.len() as u64) > remaining {
+            remaining as usize
+        } else {
+            buf.len()
+        };
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+impl<R: ParserSource + io::BufRead> io::BufRead for WindowReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let remaining = self.remaining();
+        let buf = self.inner.fill_buf()?;
+        let len = if (buf.len() as u64) > remaining {
+            remaining as usize
+        } else {
+            buf.len()
+        };
+        Ok(&buf[..len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        assert!(
+            (amt as u64) <= self.remaining(),
+            "Attempt to consume beyond the end of the window"
+        );
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: ParserSource> ParserSource for WindowReader<R> {
+    #[inline]
+    fn position(&self) -> u64 {
+        self.inner.position()
+    }
+
+    fn skip_distance(&mut self, distance: u64) -> io::Result<()> {
+        if distance > self.remaining() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Attempt to skip beyond the end of the window",
+            ));
+        }
+        self.inner.skip_distance(distance)
+    }
+
+    #[inline]
+    fn total_len(&self) -> Option<u64> {
+        Some(
+            self.inner
+                .total_len()
+                .map_or(self.end, |len| std::cmp::min(len, self.end)),
+        )
+    }
+    #[inline]
+    fn set_skip_small_threshold(&mut self, threshold: Option<u64>) {
+        self.inner.set_skip_small_threshold(threshold)
+    }
+
+    #[inline]
+    fn set_skip_buffer_size(&mut self, size: Option<NonZeroUsize>) {
+        self.inner.set_skip_buffer_size(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Cursor, Read};
+
+    use crate::pull_parser::reader::PlainSource;
+
+    #[test]
+    fn read_within_window() {
+        let mut source = PlainSource::new(Cursor::new((0..=255).collect::<Vec<u8>>()));
+        source.skip_distance(16).expect("Should never fail");
+        let mut window = WindowReader::new(&mut source, 32);
+        assert_eq!(window.remaining(), 16);
+
+        let mut buf = Vec::new();
+        window
+            .read_to_end(&mut buf)
+            .expect("Read from `Cursor<Vec<u8>>` should never fail");
+        assert_eq!(buf, (16..32).collect::<Vec<u8>>());
+        assert_eq!(window.position(), 32);
+        assert_eq!(window.remaining(), 0);
+        assert_eq!(
+            source.position(),
+            32,
+            "The inner source should not read beyond the window"
+        );
+    }
+
+    #[test]
+    fn skip_within_window() {
+        let mut source = PlainSource::new(Cursor::new((0..=255).collect::<Vec<u8>>()));
+        let mut window = WindowReader::new(&mut source, 32);
+        window
+            .skip_to(30)
+            .expect("Skip within the window should succeed");
+        assert_eq!(window.position(), 30);
+        let err = window
+            .skip_distance(3)
+            .expect_err("Skip beyond the window should fail");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            window.position(),
+            30,
+            "Failed skip should not move the position"
+        );
+    }
+}
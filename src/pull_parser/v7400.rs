@@ -5,11 +5,17 @@
 pub(crate) use self::read::{FromParser, FromReader};
 pub use self::{
     attribute::{Attributes, LoadAttribute},
-    event::{Event, StartNode},
-    parser::{from_reader, from_seekable_reader, Parser},
+    config::ParserConfig,
+    event::{AttributeLoadPolicy, Event, OwnedEvent, OwnedStartNode, StartNode},
+    header_extension::{load_header_extension, CreationTimeStamp, HeaderExtension, SceneInfo},
+    parser::{from_reader, from_seekable_reader, Bookmark, Parser},
+    stream::EventStream,
 };
 
 pub mod attribute;
+mod config;
 mod event;
+mod header_extension;
 mod parser;
 mod read;
+mod stream;
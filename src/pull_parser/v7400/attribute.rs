@@ -3,11 +3,14 @@
 use std::io;
 
 use crate::{
-    low::v7400::{ArrayAttributeHeader, AttributeType, SpecialAttributeHeader},
+    low::v7400::{
+        ArrayAttributeEncoding, ArrayAttributeHeader, AttributeType, AttributeValue,
+        SpecialAttributeHeader,
+    },
     pull_parser::{
-        error::DataError,
+        error::{DataError, OperationError},
         v7400::{FromReader, Parser},
-        ParserSource, Result, SyntacticPosition, Warning,
+        ParserSource, Result, SeekableParserSource, SyntacticPosition, Warning,
     },
 };
 
@@ -20,6 +23,16 @@ mod loader;
 pub mod loaders;
 
 /// Node attributes reader.
+///
+/// # Unread attributes
+///
+/// If the reader is dropped without reading all the attributes, the rest
+/// attributes are skipped silently when the next event is requested.
+/// Nodes with such implicit skips are counted by
+/// [`Parser::nodes_with_unread_attributes_count`], and the skips can be reported as
+/// warnings by [`ParserConfig::warn_unread_attributes`][`super::ParserConfig::warn_unread_attributes`].
+///
+/// To skip the rest attributes intentionally, use [`finish`][`Self::finish`].
 #[derive(Debug)]
 pub struct Attributes<'a, R> {
     /// Total number of attributes of the current node.
@@ -31,6 +44,16 @@ pub struct Attributes<'a, R> {
     /// This is almost same as "end offset of the previous attribute (if
     /// available)".
     next_attr_start_offset: u64,
+    /// Beginning offset of the first attribute.
+    start_offset: u64,
+    /// Encoding of the last loaded attribute, if it is an array.
+    last_array_encoding: Option<ArrayAttributeEncoding>,
+    /// Number of the attributes which have been loaded at least once.
+    ///
+    /// Warnings are not reported again for these attributes.
+    read_count: u64,
+    /// Whether the end of the attributes has already been checked.
+    end_checked: bool,
     /// Parser.
     parser: &'a mut Parser<R>,
 }
@@ -45,10 +68,26 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
             total_count,
             rest_count: total_count,
             next_attr_start_offset: pos,
+            start_offset: pos,
+            last_array_encoding: None,
+            read_count: 0,
+            // Attributes without data are already reported on the node
+            // start.
+            end_checked: total_count != 0 && pos == parser.current_attributes_end_offset(),
             parser,
         }
     }
 
+    /// Returns the encoding of the last loaded attribute, if it is an array.
+    ///
+    /// This is useful to keep the representation of the array attributes on
+    /// rewriting.
+    #[inline]
+    #[must_use]
+    pub fn last_array_encoding(&self) -> Option<ArrayAttributeEncoding> {
+        self.last_array_encoding
+    }
+
     /// Returns the total number of attributes.
     #[inline]
     #[must_use]
@@ -63,6 +102,128 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         self.rest_count
     }
 
+    /// Returns the index of the attribute which would be read next.
+    ///
+    /// This is same as `total_count() - rest_count()`.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.total_count - self.rest_count
+    }
+
+    /// Seeks to the attribute with the given index, so that it would be read
+    /// next.
+    ///
+    /// This is available only for seekable sources, and allows reading an
+    /// already read attribute again with another loader (for example, after
+    /// probing the type with [`TypeLoader`][`loaders::TypeLoader`]).
+    /// Seeking to `total_count()` skips all the rest attributes.
+    ///
+    /// Seeking forward skips the attributes between without loading the
+    /// values, and no warnings are reported for them.
+    /// Seeking backward rewinds the reader to the first attribute and skips
+    /// the preceding attributes in the same way, so the cost depends on the
+    /// number of the preceding attributes rather than on their values.
+    ///
+    /// Warnings are reported only once for each attribute, even if it is
+    /// read again after seeking backward.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::{v7400::{attribute::loaders::{DirectLoader, TypeLoader}, Event, Parser}, Result, SeekableParserSource};
+    /// use fbxcel::low::v7400::AttributeType;
+    /// # fn f<R: SeekableParserSource>(parser: &mut Parser<R>) -> Result<()> {
+    /// if let Event::StartNode(start) = parser.next_event()? {
+    ///     let mut attrs = start.attributes();
+    ///     if attrs.load_next(TypeLoader)? == Some(AttributeType::String) {
+    ///         attrs.seek_to_attribute(0)?;
+    ///         let value = attrs.load_next(DirectLoader)?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn seek_to_attribute(&mut self, index: u64) -> Result<()>
+    where
+        R: SeekableParserSource,
+    {
+        if index > self.total_count {
+            return Err(OperationError::AttributeIndexOutOfRange(index, self.total_count).into());
+        }
+        if index < self.position() {
+            self.do_with_health_check(|this, _, _| {
+                this.parser.reader().seek_to(this.start_offset)?;
+                this.rest_count = this.total_count;
+                this.next_attr_start_offset = this.start_offset;
+                this.parser
+                    .set_current_rest_attributes_count(this.total_count);
+                Ok(())
+            })?;
+        }
+        while self.position() < index {
+            self.skip_next()?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes reading attributes, and skips the rest attributes explicitly.
+    ///
+    /// Unlike dropping the reader, this is not counted as an implicit skip
+    /// and does not report warnings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::{v7400::{attribute::loaders::DirectLoader, Event, Parser}, ParserSource, Result};
+    /// # fn f<R: ParserSource>(parser: &mut Parser<R>) -> Result<()> {
+    /// if let Event::StartNode(start) = parser.next_event()? {
+    ///     let mut attrs = start.attributes();
+    ///     // Only the first attribute is necessary.
+    ///     let first = attrs.load_next(DirectLoader)?;
+    ///     attrs.finish();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn finish(self) {
+        self.parser.finish_current_attributes();
+    }
+
+    /// Skips the next attribute without loading the value.
+    fn skip_next(&mut self) -> Result<()> {
+        self.do_with_health_check(|this, start_pos, attr_index| {
+            let attr_type = match this.read_next_attr_type()? {
+                Some(v) => v,
+                None => return Ok(()),
+            };
+            this.last_array_encoding = None;
+            let size = match attr_type {
+                AttributeType::Bool => 1,
+                AttributeType::I16 => 2,
+                AttributeType::I32 | AttributeType::F32 => 4,
+                AttributeType::I64 | AttributeType::F64 => 8,
+                AttributeType::ArrBool
+                | AttributeType::ArrI32
+                | AttributeType::ArrI64
+                | AttributeType::ArrF32
+                | AttributeType::ArrF64 => {
+                    let header = ArrayAttributeHeader::from_reader(this.parser.reader())?;
+                    this.last_array_encoding = Some(header.encoding);
+                    u64::from(header.bytelen)
+                }
+                AttributeType::Binary | AttributeType::String => {
+                    let header = this.parser.parse::<SpecialAttributeHeader>()?;
+                    u64::from(header.bytelen)
+                }
+            };
+            this.update_next_attr_start_offset(size);
+            this.check_attributes_end(start_pos, attr_index)
+        })
+    }
+
     /// Updates the next attribute start offset according to the given size (in
     /// bytes).
     fn update_next_attr_start_offset(&mut self, size: u64) {
@@ -87,7 +248,7 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         match f(self, start_pos, attr_index) {
             Ok(v) => Ok(v),
             Err(e) => {
-                let err_pos = self.position(start_pos, attr_index);
+                let err_pos = self.syntactic_position(start_pos, attr_index);
                 self.parser.set_aborted(err_pos.clone());
                 Err(e.and_position(err_pos))
             }
@@ -99,6 +260,7 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         if self.rest_count() == 0 {
             return Ok(None);
         }
+        self.check_attribute_start()?;
 
         // Skip the previous attribute value if it remains.
         if self.parser.reader().position() < self.next_attr_start_offset {
@@ -111,6 +273,7 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         // Update this count after parsing is done, so that
         // `total_count - rest_count` is same as attribute index during parsing.
         self.rest_count -= 1;
+        self.parser.mark_current_attribute_read();
 
         Ok(Some(attr_type))
     }
@@ -125,8 +288,33 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
                 Some(v) => v,
                 None => return Ok(None),
             };
-            this.load_next_impl(attr_type, loader, start_pos, attr_index)
-                .map(Some)
+            let value = this.load_next_impl(attr_type, loader, start_pos, attr_index)?;
+            this.end_attribute(start_pos, attr_index)?;
+            Ok(Some(value))
+        })
+    }
+
+    /// Lets the loader created by the given function load the next node
+    /// attribute.
+    ///
+    /// The function receives the index and the type of the next attribute,
+    /// and returns the loader for it.
+    /// If the function returns an error, the parser is aborted as if the
+    /// loader failed.
+    pub fn load_next_with<V, F>(&mut self, f: F) -> Result<Option<V::Output>>
+    where
+        V: LoadAttribute,
+        F: FnOnce(usize, AttributeType) -> Result<V>,
+    {
+        self.do_with_health_check(|this, start_pos, attr_index| {
+            let attr_type = match this.read_next_attr_type()? {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+            let loader = f(attr_index, attr_type)?;
+            let value = this.load_next_impl(attr_type, loader, start_pos, attr_index)?;
+            this.end_attribute(start_pos, attr_index)?;
+            Ok(Some(value))
         })
     }
 
@@ -144,8 +332,9 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
                 Some(v) => v,
                 None => return Ok(None),
             };
-            this.load_next_buffered_impl(attr_type, loader, start_pos, attr_index)
-                .map(Some)
+            let value = this.load_next_buffered_impl(attr_type, loader, start_pos, attr_index)?;
+            this.end_attribute(start_pos, attr_index)?;
+            Ok(Some(value))
         })
     }
 
@@ -160,15 +349,20 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
     where
         V: LoadAttribute,
     {
+        self.last_array_encoding = None;
         match attr_type {
             AttributeType::Bool => {
                 let raw = self.parser.parse::<u8>()?;
                 let value = (raw & 1) != 0;
                 self.update_next_attr_start_offset(0);
-                if raw != b'T' && raw != b'Y' {
-                    self.parser.warn(
+                if raw != b'T'
+                    && raw != b'Y'
+                    && !self.parser.config().ignore_incorrect_boolean_representation
+                {
+                    self.warn(
                         Warning::IncorrectBooleanRepresentation,
-                        self.position(start_pos, attr_index),
+                        start_pos,
+                        attr_index,
                     )?;
                 }
                 loader.load_bool(value)
@@ -199,74 +393,60 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
                 loader.load_f64(value)
             }
             AttributeType::ArrBool => {
-                let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
-                self.update_next_attr_start_offset(u64::from(header.bytelen));
-                let reader = AttributeStreamDecoder::create(header.encoding, self.parser.reader())?;
-                let count = header.elements_count;
-                let mut iter = BooleanArrayAttributeValues::new(reader, count);
-                let res = loader.load_seq_bool(&mut iter, count as usize)?;
-                // Save `has_error` to make `iter` discardable before
-                // `self.parser.warn()` call.
-                let has_error = iter.has_error();
-                if iter.has_incorrect_boolean_value() {
-                    self.parser.warn(
-                        Warning::IncorrectBooleanRepresentation,
-                        self.position(start_pos, attr_index),
-                    )?;
+                let (res, has_error, incorrect_boolean_values) =
+                    self.load_array(|reader, count| {
+                        let mut iter = BooleanArrayAttributeValues::new(reader, count);
+                        let res = loader.load_seq_bool(&mut iter, count as usize)?;
+                        // Save `has_error` to make `iter` discardable before
+                        // `self.warn()` call.
+                        Ok((res, iter.has_error(), iter.incorrect_boolean_values()))
+                    })?;
+                if let Some((count, first_index)) = incorrect_boolean_values {
+                    if !self.parser.config().ignore_incorrect_boolean_representation {
+                        self.warn(
+                            Warning::IncorrectBooleanArrayRepresentation(count, first_index),
+                            start_pos,
+                            attr_index,
+                        )?;
+                    }
                 }
                 if has_error {
                     return Err(DataError::NodeAttributeError.into());
                 }
                 Ok(res)
             }
-            AttributeType::ArrI32 => {
-                let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
-                self.update_next_attr_start_offset(u64::from(header.bytelen));
-                let reader = AttributeStreamDecoder::create(header.encoding, self.parser.reader())?;
-                let count = header.elements_count;
+            AttributeType::ArrI32 => self.load_array(|reader, count| {
                 let mut iter = ArrayAttributeValues::<_, i32>::new(reader, count);
                 let res = loader.load_seq_i32(&mut iter, count as usize)?;
                 if iter.has_error() {
                     return Err(DataError::NodeAttributeError.into());
                 }
                 Ok(res)
-            }
-            AttributeType::ArrI64 => {
-                let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
-                self.update_next_attr_start_offset(u64::from(header.bytelen));
-                let reader = AttributeStreamDecoder::create(header.encoding, self.parser.reader())?;
-                let count = header.elements_count;
+            }),
+            AttributeType::ArrI64 => self.load_array(|reader, count| {
                 let mut iter = ArrayAttributeValues::<_, i64>::new(reader, count);
                 let res = loader.load_seq_i64(&mut iter, count as usize)?;
                 if iter.has_error() {
                     return Err(DataError::NodeAttributeError.into());
                 }
                 Ok(res)
-            }
-            AttributeType::ArrF32 => {
-                let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
-                self.update_next_attr_start_offset(u64::from(header.bytelen));
-                let reader = AttributeStreamDecoder::create(header.encoding, self.parser.reader())?;
-                let count = header.elements_count;
+            }),
+            AttributeType::ArrF32 => self.load_array(|reader, count| {
                 let mut iter = ArrayAttributeValues::<_, f32>::new(reader, count);
                 let res = loader.load_seq_f32(&mut iter, count as usize)?;
                 if iter.has_error() {
                     return Err(DataError::NodeAttributeError.into());
                 }
                 Ok(res)
-            }
-            AttributeType::ArrF64 => {
-                let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
-                self.update_next_attr_start_offset(u64::from(header.bytelen));
-                let reader = AttributeStreamDecoder::create(header.encoding, self.parser.reader())?;
-                let count = header.elements_count;
+            }),
+            AttributeType::ArrF64 => self.load_array(|reader, count| {
                 let mut iter = ArrayAttributeValues::<_, f64>::new(reader, count);
                 let res = loader.load_seq_f64(&mut iter, count as usize)?;
                 if iter.has_error() {
                     return Err(DataError::NodeAttributeError.into());
                 }
                 Ok(res)
-            }
+            }),
             AttributeType::Binary => {
                 let header = self.parser.parse::<SpecialAttributeHeader>()?;
                 let bytelen = u64::from(header.bytelen);
@@ -288,6 +468,88 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         }
     }
 
+    /// Reads an array attribute header, and calls the given function with
+    /// the decoded stream of the array elements and the elements count.
+    ///
+    /// The read buffer for compressed arrays is borrowed from the parser, and
+    /// it is returned to the parser even if the function fails, so that the
+    /// allocation is retained.
+    fn load_array<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(AttributeStreamDecoder<'_, &mut R>, u32) -> Result<T>,
+    {
+        let header = ArrayAttributeHeader::from_reader(self.parser.reader())?;
+        self.update_next_attr_start_offset(u64::from(header.bytelen));
+        self.last_array_encoding = Some(header.encoding);
+
+        let mut read_buf = self.parser.take_compressed_buf();
+        let res = AttributeStreamDecoder::create(
+            header.encoding,
+            header.bytelen,
+            self.parser.reader(),
+            &mut read_buf,
+        )
+        .and_then(|reader| f(reader, header.elements_count));
+        self.parser.restore_compressed_buf(read_buf);
+        res
+    }
+
+    /// Checks if the last attribute ends at the declared end of the
+    /// attributes, if the last attribute is loaded and the check is enabled.
+    ///
+    /// The check is done only once even if the last attribute is read again.
+    fn check_attributes_end(&mut self, start_pos: u64, attr_index: usize) -> Result<()> {
+        if self.rest_count != 0 || self.end_checked || !self.parser.config().strict_attribute_count
+        {
+            return Ok(());
+        }
+        self.end_checked = true;
+        let expected = self.parser.current_attributes_end_offset();
+        let actual = self.next_attr_start_offset;
+        let warning = match actual.cmp(&expected) {
+            std::cmp::Ordering::Equal => return Ok(()),
+            std::cmp::Ordering::Less => Warning::ExtraAttributeData(expected - actual),
+            std::cmp::Ordering::Greater => Warning::AttributesLengthExceeded(actual - expected),
+        };
+        self.parser
+            .warn(warning, self.syntactic_position(start_pos, attr_index))
+    }
+
+    /// Checks if the next attribute starts before the declared end of the
+    /// attributes, if the check is enabled.
+    ///
+    /// The check is done only once for the node, and then the end of the last
+    /// attribute is not checked.
+    fn check_attribute_start(&mut self) -> Result<()> {
+        if self.end_checked || !self.parser.config().strict_attribute_count {
+            return Ok(());
+        }
+        if self.next_attr_start_offset < self.parser.current_attributes_end_offset() {
+            return Ok(());
+        }
+        self.end_checked = true;
+        let pos = self.syntactic_position(self.next_attr_start_offset, self.position() as usize);
+        self.parser
+            .warn(Warning::MissingAttributeData(self.rest_count), pos)
+    }
+
+    /// Finishes loading the attribute.
+    fn end_attribute(&mut self, start_pos: u64, attr_index: usize) -> Result<()> {
+        self.check_attributes_end(start_pos, attr_index)?;
+        self.read_count = self.read_count.max(self.position());
+        Ok(())
+    }
+
+    /// Reports the warning for the attribute, unless the attribute has been
+    /// loaded before.
+    fn warn(&mut self, warning: Warning, start_pos: u64, attr_index: usize) -> Result<()> {
+        if (attr_index as u64) < self.read_count {
+            return Ok(());
+        }
+        self.parser
+            .warn(warning, self.syntactic_position(start_pos, attr_index))
+    }
+
     /// Internal implementation of `load_next_buffered`.
     fn load_next_buffered_impl<V>(
         &mut self,
@@ -326,7 +588,7 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
     /// Returns the syntactic position of the attribute currently reading.
     #[inline]
     #[must_use]
-    fn position(&self, start_pos: u64, index: usize) -> SyntacticPosition {
+    fn syntactic_position(&self, start_pos: u64, index: usize) -> SyntacticPosition {
         SyntacticPosition {
             component_byte_pos: start_pos,
             attribute_index: Some(index),
@@ -334,6 +596,35 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         }
     }
 
+    /// Loads all the rest attributes as [`AttributeValue`]s.
+    ///
+    /// This is same as loading the attributes by [`DirectLoader`] until
+    /// no attributes remain.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel::pull_parser::{v7400::{Event, Parser}, ParserSource, Result};
+    /// # fn f<R: ParserSource>(parser: &mut Parser<R>) -> Result<()> {
+    /// if let Event::StartNode(start) = parser.next_event()? {
+    ///     let name = start.name().to_owned();
+    ///     let attrs = start.attributes().load_all_direct()?;
+    ///     println!("{}: {:?}", name, attrs);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`DirectLoader`]: `loaders::DirectLoader`
+    pub fn load_all_direct(&mut self) -> Result<Vec<AttributeValue>> {
+        let mut values = Vec::new();
+        while let Some(value) = self.load_next(loaders::DirectLoader)? {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+
     /// Creates an iterator emitting attribute values.
     #[inline]
     pub fn iter<V, I>(&mut self, loaders: I) -> iter::BorrowedIter<'_, 'a, R, I::IntoIter>
@@ -344,6 +635,46 @@ impl<'a, R: 'a + ParserSource> Attributes<'a, R> {
         iter::BorrowedIter::new(self, loaders.into_iter())
     }
 
+    /// Creates an iterator emitting attribute values, using loaders created
+    /// lazily by the given function.
+    ///
+    /// The function receives the index and the type of each attribute, and
+    /// returns the loader for it.
+    /// This is useful to load attributes whose types vary, since the loader
+    /// can be chosen after the type is known.
+    ///
+    /// See [`load_next_with`][`Self::load_next_with`] for detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{
+    /// #     low::v7400::{AttributeType, AttributeValue},
+    /// #     pull_parser::{error::DataError, v7400::{attribute::loaders::DirectLoader, Attributes}, ParserSource, Result},
+    /// # };
+    /// // Loads a property node (`P`) whose first four attributes are strings.
+    /// fn load_property<R: ParserSource>(attrs: &mut Attributes<'_, R>) -> Result<Vec<AttributeValue>> {
+    ///     attrs
+    ///         .iter_with(|index, ty| match (index, ty) {
+    ///             (0..=3, AttributeType::String) | (4.., _) => Ok(DirectLoader),
+    ///             (_, ty) => Err(DataError::UnexpectedAttribute(
+    ///                 "string".into(),
+    ///                 format!("{:?}", ty),
+    ///             )
+    ///             .into()),
+    ///         })
+    ///         .collect()
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter_with<V, F>(&mut self, f: F) -> iter::BorrowedIterWith<'_, 'a, R, F>
+    where
+        V: LoadAttribute,
+        F: FnMut(usize, AttributeType) -> Result<V>,
+    {
+        iter::BorrowedIterWith::new(self, f)
+    }
+
     /// Creates an iterator emitting attribute values with buffered I/O.
     #[inline]
     pub fn iter_buffered<V, I>(
@@ -13,27 +13,44 @@ use crate::{
 /// Attribute stream decoder.
 // `io::BufRead` is not implemented for `ZlibDecoder`.
 #[derive(Debug)]
-pub(crate) enum AttributeStreamDecoder<R> {
+pub(crate) enum AttributeStreamDecoder<'b, R> {
     /// Direct stream.
     Direct(R),
     /// Zlib-decoded stream.
-    Zlib(ZlibDecoder<R>),
+    Zlib(ZlibDecoder<PooledBufReader<'b, io::Take<R>>>),
 }
 
-impl<R: io::Read> AttributeStreamDecoder<R> {
+impl<'b, R: io::Read> AttributeStreamDecoder<'b, R> {
     /// Creates a new decoded reader.
-    pub(crate) fn create(encoding: ArrayAttributeEncoding, reader: R) -> Result<Self> {
+    ///
+    /// `bytelen` is the length of the encoded array in bytes.
+    ///
+    /// Compressed data is decoded while it is read, and `read_buf` is used as
+    /// the buffer for reading the compressed data.
+    /// The buffer should be non-empty, and it is expected to be reused among
+    /// array attributes to reduce allocation.
+    /// Note that the decoder itself cannot be reused, since `libflate` does
+    /// not support resetting the decoder.
+    pub(crate) fn create(
+        encoding: ArrayAttributeEncoding,
+        bytelen: u32,
+        reader: R,
+        read_buf: &'b mut [u8],
+    ) -> Result<Self> {
         match encoding {
             ArrayAttributeEncoding::Direct => Ok(AttributeStreamDecoder::Direct(reader)),
-            ArrayAttributeEncoding::Zlib => Ok(AttributeStreamDecoder::Zlib(
-                ZlibDecoder::new(reader)
-                    .map_err(|e| DataError::BrokenCompression(encoding.into(), e.into()))?,
-            )),
+            ArrayAttributeEncoding::Zlib => {
+                let reader = PooledBufReader::new(reader.take(u64::from(bytelen)), read_buf);
+                Ok(AttributeStreamDecoder::Zlib(
+                    ZlibDecoder::new(reader)
+                        .map_err(|e| DataError::BrokenCompression(encoding.into(), e.into()))?,
+                ))
+            }
         }
     }
 }
 
-impl<R: io::Read> io::Read for AttributeStreamDecoder<R> {
+impl<R: io::Read> io::Read for AttributeStreamDecoder<'_, R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match self {
             AttributeStreamDecoder::Direct(reader) => reader.read(buf),
@@ -42,6 +59,73 @@ impl<R: io::Read> io::Read for AttributeStreamDecoder<R> {
     }
 }
 
+/// Buffered reader with a borrowed buffer.
+///
+/// Unlike [`std::io::BufReader`], this does not own the buffer, so that the
+/// buffer can be reused among readers.
+#[derive(Debug)]
+pub(crate) struct PooledBufReader<'b, R> {
+    /// Inner reader.
+    inner: R,
+    /// Buffer.
+    buf: &'b mut [u8],
+    /// Position of the next byte to be consumed in the buffer.
+    pos: usize,
+    /// Length of the valid data in the buffer.
+    filled: usize,
+}
+
+impl<'b, R: io::Read> PooledBufReader<'b, R> {
+    /// Creates a new `PooledBufReader`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty.
+    #[inline]
+    #[must_use]
+    fn new(inner: R, buf: &'b mut [u8]) -> Self {
+        assert!(!buf.is_empty(), "Read buffer should not be empty");
+        Self {
+            inner,
+            buf,
+            pos: 0,
+            filled: 0,
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for PooledBufReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Bypass the buffer for large reads.
+        if self.pos == self.filled && buf.len() >= self.buf.len() {
+            return self.inner.read(buf);
+        }
+        let len = {
+            let available = io::BufRead::fill_buf(self)?;
+            let len = std::cmp::min(available.len(), buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        io::BufRead::consume(self, len);
+        Ok(len)
+    }
+}
+
+impl<R: io::Read> io::BufRead for PooledBufReader<'_, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.filled {
+            self.filled = self.inner.read(self.buf)?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = std::cmp::min(self.pos + amt, self.filled);
+    }
+}
+
 /// Array attribute values iterator for `{i,f}{32,64}` array.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct ArrayAttributeValues<R, E> {
@@ -130,15 +214,17 @@ impl_array_attr_values! { f64, read_f64 }
 pub(crate) struct BooleanArrayAttributeValues<R> {
     /// Decoded reader.
     reader: R,
-    // `total_elements`: unused.
-    ///// Number of total elements.
-    //total_elements: u32,
+    /// Number of total elements.
+    total_elements: u32,
     /// Number of rest elements.
     rest_elements: u32,
     /// Whether an error is happened.
     has_error: bool,
-    /// Whether the attribute has incorrect boolean value representation.
-    has_incorrect_boolean_value: bool,
+    /// Number of elements with incorrect boolean value representation.
+    incorrect_boolean_count: u32,
+    /// Index of the first element with incorrect boolean value
+    /// representation.
+    first_incorrect_boolean_index: Option<u32>,
 }
 
 impl<R: io::Read> BooleanArrayAttributeValues<R> {
@@ -148,19 +234,23 @@ impl<R: io::Read> BooleanArrayAttributeValues<R> {
     pub(crate) fn new(reader: R, total_elements: u32) -> Self {
         Self {
             reader,
-            //total_elements,
+            total_elements,
             rest_elements: total_elements,
             has_error: false,
-            has_incorrect_boolean_value: false,
+            incorrect_boolean_count: 0,
+            first_incorrect_boolean_index: None,
         }
     }
 
-    /// Returns whether the attribute has incorrect boolean value
-    /// representation.
+    /// Returns the number of elements with incorrect boolean value
+    /// representation, and the index of the first one, if exists.
+    ///
+    /// Only elements already read are checked.
     #[inline]
     #[must_use]
-    pub(crate) fn has_incorrect_boolean_value(&self) -> bool {
-        self.has_incorrect_boolean_value
+    pub(crate) fn incorrect_boolean_values(&self) -> Option<(u32, u32)> {
+        self.first_incorrect_boolean_index
+            .map(|first| (self.incorrect_boolean_count, first))
     }
 
     /// Returns whether an error happened or not.
@@ -182,12 +272,14 @@ impl<R: io::Read> Iterator for BooleanArrayAttributeValues<R> {
         }
         match self.reader.read_u8() {
             Ok(raw) => {
+                let index = self.total_elements - self.rest_elements;
                 self.rest_elements = self
                     .rest_elements
                     .checked_sub(1)
                     .expect("This should be executed only when there are rest elements");
                 if raw != b'T' && raw != b'Y' {
-                    self.has_incorrect_boolean_value = true;
+                    self.incorrect_boolean_count += 1;
+                    self.first_incorrect_boolean_index.get_or_insert(index);
                 }
                 let v = (raw & 1) != 0;
                 Some(Ok(v))
@@ -206,3 +298,40 @@ impl<R: io::Read> Iterator for BooleanArrayAttributeValues<R> {
 }
 
 impl<R: io::Read> std::iter::FusedIterator for BooleanArrayAttributeValues<R> {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use libflate::zlib::Encoder as ZlibEncoder;
+
+    use super::*;
+
+    #[test]
+    fn zlib_stream_with_small_read_buffer() -> std::io::Result<()> {
+        let data = (0..1000_u32)
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<u8>>();
+        let mut encoder = ZlibEncoder::new(Vec::new())?;
+        encoder.write_all(&data)?;
+        let mut compressed = encoder.finish().into_result()?;
+        let bytelen = compressed.len() as u32;
+        compressed.extend_from_slice(b"next");
+
+        let mut source = &compressed[..];
+        let mut read_buf = [0; 3];
+        let mut decoded = Vec::new();
+        AttributeStreamDecoder::create(
+            ArrayAttributeEncoding::Zlib,
+            bytelen,
+            &mut source,
+            &mut read_buf,
+        )
+        .expect("Should never fail")
+        .read_to_end(&mut decoded)?;
+        assert_eq!(decoded, data);
+        // Data after the array should not be consumed.
+        assert_eq!(source, b"next");
+        Ok(())
+    }
+}
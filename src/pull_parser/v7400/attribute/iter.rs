@@ -3,9 +3,12 @@
 use std::io;
 use std::iter;
 
-use crate::pull_parser::{
-    v7400::attribute::{loader::LoadAttribute, Attributes},
-    ParserSource, Result,
+use crate::{
+    low::v7400::AttributeType,
+    pull_parser::{
+        v7400::attribute::{loader::LoadAttribute, Attributes},
+        ParserSource, Result,
+    },
 };
 
 /// Creates size hint from the given attributes and loaders.
@@ -107,6 +110,62 @@ where
 {
 }
 
+/// Node attributes iterator with loaders created lazily.
+#[derive(Debug)]
+pub struct BorrowedIterWith<'a, 'r, R, F> {
+    /// Attributes.
+    attributes: &'a mut Attributes<'r, R>,
+    /// Loader factory.
+    factory: F,
+}
+
+impl<'a, 'r, R, F, V> BorrowedIterWith<'a, 'r, R, F>
+where
+    R: ParserSource,
+    F: FnMut(usize, AttributeType) -> Result<V>,
+    V: LoadAttribute,
+{
+    /// Creates a new iterator.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(attributes: &'a mut Attributes<'r, R>, factory: F) -> Self {
+        Self {
+            attributes,
+            factory,
+        }
+    }
+}
+
+impl<'a, 'r, R, F, V> Iterator for BorrowedIterWith<'a, 'r, R, F>
+where
+    R: ParserSource,
+    F: FnMut(usize, AttributeType) -> Result<V>,
+    V: LoadAttribute,
+{
+    type Item = Result<V::Output>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.attributes
+            .load_next_with(&mut self.factory)
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.attributes.rest_count() as usize;
+        (rest, Some(rest))
+    }
+}
+
+impl<'a, 'r, R, F, V> iter::FusedIterator for BorrowedIterWith<'a, 'r, R, F>
+where
+    R: ParserSource,
+    F: FnMut(usize, AttributeType) -> Result<V>,
+    V: LoadAttribute,
+{
+}
+
 /// Node attributes iterator with buffered I/O.
 #[derive(Debug)]
 pub struct BorrowedIterBuffered<'a, 'r, R, I> {
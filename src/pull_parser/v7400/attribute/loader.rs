@@ -2,7 +2,11 @@
 
 use std::{fmt, io};
 
-use crate::pull_parser::{error::DataError, Result};
+use crate::pull_parser::{
+    error::DataError,
+    v7400::attribute::loaders::{AndThen, Map},
+    Result,
+};
 
 /// A trait for attribute loader types.
 ///
@@ -129,4 +133,53 @@ pub trait LoadAttribute: Sized + fmt::Debug {
     fn load_string_buffered(self, reader: impl io::BufRead, len: u64) -> Result<Self::Output> {
         self.load_string(reader, len)
     }
+
+    /// Creates a loader which converts the loaded value by the given
+    /// function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::pull_parser::v7400::{attribute::loaders::PrimitiveLoader, LoadAttribute};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// struct ObjectId(i64);
+    ///
+    /// let loader = PrimitiveLoader::<i64>::new().map(ObjectId);
+    /// assert_eq!(loader.load_i64(42).ok(), Some(ObjectId(42)));
+    /// ```
+    #[inline]
+    fn map<F, U>(self, f: F) -> Map<Self, F>
+    where
+        F: FnOnce(Self::Output) -> U,
+    {
+        Map::new(self, f)
+    }
+
+    /// Creates a loader which converts the loaded value by the given
+    /// fallible function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use fbxcel::pull_parser::{
+    ///     error::DataError,
+    ///     v7400::{attribute::loaders::PrimitiveLoader, LoadAttribute},
+    /// };
+    ///
+    /// let loader = PrimitiveLoader::<i64>::new().and_then(|v| {
+    ///     u32::try_from(v).map_err(|_| {
+    ///         DataError::UnexpectedAttribute("u32".into(), v.to_string()).into()
+    ///     })
+    /// });
+    /// assert!(loader.load_i64(-1).is_err());
+    /// ```
+    #[inline]
+    fn and_then<F, U>(self, f: F) -> AndThen<Self, F>
+    where
+        F: FnOnce(Self::Output) -> Result<U>,
+    {
+        AndThen::new(self, f)
+    }
 }
@@ -1,11 +1,13 @@
 //! Node attribute loaders.
 
 pub use self::{
+    adapter::{AndThen, Map},
     direct::DirectLoader,
-    single::{ArrayLoader, BinaryLoader, PrimitiveLoader, StringLoader},
+    single::{ArrayLoader, BinaryLoader, PrimitiveLoader, RawStringLoader, StringLoader},
     type_::TypeLoader,
 };
 
+mod adapter;
 mod direct;
 mod single;
 mod type_;
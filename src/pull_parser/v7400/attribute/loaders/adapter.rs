@@ -0,0 +1,196 @@
+//! Loader adapters.
+
+use std::{fmt, io};
+
+use crate::pull_parser::{v7400::LoadAttribute, Result};
+
+/// Loader adapter which converts the output of the inner loader.
+///
+/// This is created by [`LoadAttribute::map`].
+#[derive(Clone, Copy)]
+pub struct Map<V, F> {
+    /// Inner loader.
+    loader: V,
+    /// Conversion.
+    f: F,
+}
+
+impl<V, F> Map<V, F> {
+    /// Creates a new `Map`.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(loader: V, f: F) -> Self {
+        Self { loader, f }
+    }
+}
+
+impl<V: fmt::Debug, F> fmt::Debug for Map<V, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("loader", &self.loader).finish()
+    }
+}
+
+/// Loader adapter which converts the output of the inner loader with a
+/// fallible function.
+///
+/// This is created by [`LoadAttribute::and_then`].
+#[derive(Clone, Copy)]
+pub struct AndThen<V, F> {
+    /// Inner loader.
+    loader: V,
+    /// Conversion.
+    f: F,
+}
+
+impl<V, F> AndThen<V, F> {
+    /// Creates a new `AndThen`.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(loader: V, f: F) -> Self {
+        Self { loader, f }
+    }
+}
+
+impl<V: fmt::Debug, F> fmt::Debug for AndThen<V, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AndThen")
+            .field("loader", &self.loader)
+            .finish()
+    }
+}
+
+/// Implements `LoadAttribute` methods forwarding values to the inner loader.
+macro_rules! impl_forward_load_attribute {
+    ($convert:expr) => {
+        fn expecting(&self) -> String {
+            self.loader.expecting()
+        }
+
+        #[inline]
+        fn load_bool(self, v: bool) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_bool(v))
+        }
+
+        #[inline]
+        fn load_i16(self, v: i16) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_i16(v))
+        }
+
+        #[inline]
+        fn load_i32(self, v: i32) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_i32(v))
+        }
+
+        #[inline]
+        fn load_i64(self, v: i64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_i64(v))
+        }
+
+        #[inline]
+        fn load_f32(self, v: f32) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_f32(v))
+        }
+
+        #[inline]
+        fn load_f64(self, v: f64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_f64(v))
+        }
+
+        #[inline]
+        fn load_seq_bool(
+            self,
+            iter: impl Iterator<Item = Result<bool>>,
+            len: usize,
+        ) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_seq_bool(iter, len))
+        }
+
+        #[inline]
+        fn load_seq_i32(
+            self,
+            iter: impl Iterator<Item = Result<i32>>,
+            len: usize,
+        ) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_seq_i32(iter, len))
+        }
+
+        #[inline]
+        fn load_seq_i64(
+            self,
+            iter: impl Iterator<Item = Result<i64>>,
+            len: usize,
+        ) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_seq_i64(iter, len))
+        }
+
+        #[inline]
+        fn load_seq_f32(
+            self,
+            iter: impl Iterator<Item = Result<f32>>,
+            len: usize,
+        ) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_seq_f32(iter, len))
+        }
+
+        #[inline]
+        fn load_seq_f64(
+            self,
+            iter: impl Iterator<Item = Result<f64>>,
+            len: usize,
+        ) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_seq_f64(iter, len))
+        }
+
+        #[inline]
+        fn load_binary(self, reader: impl io::Read, len: u64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_binary(reader, len))
+        }
+
+        #[inline]
+        fn load_binary_buffered(self, reader: impl io::BufRead, len: u64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_binary_buffered(reader, len))
+        }
+
+        #[inline]
+        fn load_string(self, reader: impl io::Read, len: u64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_string(reader, len))
+        }
+
+        #[inline]
+        fn load_string_buffered(self, reader: impl io::BufRead, len: u64) -> Result<Self::Output> {
+            $convert(self.f, self.loader.load_string_buffered(reader, len))
+        }
+    };
+}
+
+/// Applies the conversion to the successfully loaded value.
+#[inline]
+fn map_output<T, U>(f: impl FnOnce(T) -> U, res: Result<T>) -> Result<U> {
+    res.map(f)
+}
+
+/// Applies the fallible conversion to the successfully loaded value.
+#[inline]
+fn and_then_output<T, U>(f: impl FnOnce(T) -> Result<U>, res: Result<T>) -> Result<U> {
+    res.and_then(f)
+}
+
+impl<V, F, U> LoadAttribute for Map<V, F>
+where
+    V: LoadAttribute,
+    F: FnOnce(V::Output) -> U,
+{
+    type Output = U;
+
+    impl_forward_load_attribute!(map_output);
+}
+
+impl<V, F, U> LoadAttribute for AndThen<V, F>
+where
+    V: LoadAttribute,
+    F: FnOnce(V::Output) -> Result<U>,
+{
+    type Output = U;
+
+    impl_forward_load_attribute!(and_then_output);
+}
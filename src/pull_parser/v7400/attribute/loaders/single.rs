@@ -10,6 +10,15 @@ use crate::pull_parser::{v7400::LoadAttribute, Result};
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PrimitiveLoader<T>(std::marker::PhantomData<T>);
 
+impl<T> PrimitiveLoader<T> {
+    /// Creates a new `PrimitiveLoader`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
 /// Generates `LoadAttribute` implementations for `PrimitiveLoader<T>`.
 macro_rules! impl_load_attribute_for_primitives {
     ($ty:ty, $method_name:ident, $expecting_type:expr) => {
@@ -41,6 +50,15 @@ impl_load_attribute_for_primitives!(f64, load_f64, "single f64");
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ArrayLoader<T>(std::marker::PhantomData<T>);
 
+impl<T> ArrayLoader<T> {
+    /// Creates a new `ArrayLoader`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
 /// Generates `LoadAttribute` implementations for `PrimitiveLoader<T>`.
 macro_rules! impl_load_attribute_for_arrays {
     ($ty:ty, $method_name:ident, $expecting_type:expr) => {
@@ -106,3 +124,27 @@ impl LoadAttribute for StringLoader {
         Ok(buf)
     }
 }
+
+/// Loader for a string as raw bytes.
+///
+/// Unlike [`StringLoader`], this does not require the string to be valid
+/// UTF-8.
+/// To split the `"Name\u{0}\u{1}Class"` convention, use
+/// [`split_name_class_bytes`][`crate::low::v7400::split_name_class_bytes`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawStringLoader;
+
+impl LoadAttribute for RawStringLoader {
+    type Output = Vec<u8>;
+
+    fn expecting(&self) -> String {
+        "string".into()
+    }
+
+    #[inline]
+    fn load_string(self, mut reader: impl io::Read, len: u64) -> Result<Self::Output> {
+        let mut buf = Vec::with_capacity(len as usize);
+        reader.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+}
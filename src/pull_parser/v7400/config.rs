@@ -0,0 +1,176 @@
+//! Parser configuration.
+
+use std::num::NonZeroUsize;
+
+/// Parser configuration.
+///
+/// To create a configuration, modify fields of the default value.
+///
+/// ```
+/// # use fbxcel::pull_parser::v7400::ParserConfig;
+/// let mut config = ParserConfig::default();
+/// config.max_depth = Some(64);
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParserConfig {
+    /// Maximum node depth.
+    ///
+    /// Top-level nodes are at depth 1.
+    /// `None` means unlimited.
+    pub max_depth: Option<usize>,
+    /// Maximum number of nodes in the document.
+    ///
+    /// `None` means unlimited.
+    pub max_node_count: Option<u64>,
+    /// Maximum byte length of the attributes of a single node.
+    ///
+    /// `None` means unlimited.
+    pub max_attribute_bytelen: Option<u64>,
+    /// Whether to report a warning when unread attributes are implicitly
+    /// skipped.
+    ///
+    /// If `true`, [`Warning::UnreadAttributesSkipped`] is reported when the
+    /// next event is requested while some attributes of the current node
+    /// remain unread.
+    /// Use [`Attributes::finish`] to skip the rest attributes explicitly.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [`Warning::UnreadAttributesSkipped`]:
+    /// `crate::pull_parser::error::Warning::UnreadAttributesSkipped`
+    /// [`Attributes::finish`]: `super::Attributes::finish`
+    pub warn_unread_attributes: bool,
+    /// Size (in bytes) of the read buffer for compressed array attributes.
+    ///
+    /// Compressed arrays are decoded while they are read through this
+    /// buffer, and the buffer is reused across array attributes.
+    /// Smaller buffers are useful for low-memory environments, and larger
+    /// buffers reduce the number of reads from the source.
+    ///
+    /// `None` means the default (8 KiB).
+    pub decompression_buffer_size: Option<NonZeroUsize>,
+    /// Size (in bytes) of the buffer used for skipping by reading.
+    ///
+    /// This is passed to [`ParserSource::set_skip_buffer_size`], and is
+    /// effective only for sources which skip data by reading, such as
+    /// [`PlainSource`].
+    ///
+    /// `None` means the default of the source.
+    ///
+    /// [`ParserSource::set_skip_buffer_size`]:
+    /// `crate::pull_parser::ParserSource::set_skip_buffer_size`
+    /// [`PlainSource`]: `crate::pull_parser::reader::PlainSource`
+    pub skip_buffer_size: Option<NonZeroUsize>,
+    /// Whether to read the data after the FBX footer.
+    ///
+    /// If `true`, all the data after the FBX footer is read to the end of the
+    /// source when the footer is successfully read.
+    /// The data can be retrieved by [`Parser::trailing_data`], and
+    /// [`Warning::TrailingData`] is reported if the data is not empty.
+    ///
+    /// This is `false` by default, since the source might contain other
+    /// data to be read by the user after the FBX document.
+    ///
+    /// Use [`max_trailing_data_len`][`Self::max_trailing_data_len`] to limit
+    /// the memory usage.
+    ///
+    /// [`Parser::trailing_data`]: `super::Parser::trailing_data`
+    /// [`Warning::TrailingData`]:
+    /// `crate::pull_parser::error::Warning::TrailingData`
+    pub read_trailing_data: bool,
+    /// Maximum byte length of the data after the FBX footer.
+    ///
+    /// This is used only when [`read_trailing_data`][`Self::read_trailing_data`]
+    /// is enabled.
+    /// If the trailing data is longer than this, reading the FBX footer fails
+    /// with [`Limit::TrailingDataLength`] error.
+    ///
+    /// `None` means unlimited.
+    ///
+    /// [`Limit::TrailingDataLength`]:
+    /// `crate::pull_parser::error::Limit::TrailingDataLength`
+    pub max_trailing_data_len: Option<u64>,
+    /// Whether to ignore incorrect boolean representation silently.
+    ///
+    /// Boolean values are always normalized by the lowest bit of the raw
+    /// value.
+    /// If `true`, [`Warning::IncorrectBooleanRepresentation`] and
+    /// [`Warning::IncorrectBooleanArrayRepresentation`] are not reported.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [`Warning::IncorrectBooleanRepresentation`]:
+    /// `crate::pull_parser::error::Warning::IncorrectBooleanRepresentation`
+    /// [`Warning::IncorrectBooleanArrayRepresentation`]:
+    /// `crate::pull_parser::error::Warning::IncorrectBooleanArrayRepresentation`
+    pub ignore_incorrect_boolean_representation: bool,
+    /// Whether to cross-validate the number and the byte length of node
+    /// attributes.
+    ///
+    /// If `true`, the end of the last attribute is compared with the byte
+    /// length declared in the node header, and
+    /// [`Warning::AttributesLengthExceeded`] or
+    /// [`Warning::ExtraAttributeData`] is reported on mismatch.
+    /// If an attribute starts at or beyond the declared end of the
+    /// attributes, [`Warning::MissingAttributeData`] is reported before
+    /// reading it.
+    /// This helps to find bugs of exporters, which are otherwise detected
+    /// only indirectly (or not at all) by node end offsets.
+    ///
+    /// The checks are done when attributes are read, so nodes whose
+    /// attributes are skipped are checked only if they declare no attributes
+    /// or no bytes of attributes.
+    /// At most one warning is reported for each node.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [`Warning::AttributesLengthExceeded`]:
+    /// `crate::pull_parser::error::Warning::AttributesLengthExceeded`
+    /// [`Warning::ExtraAttributeData`]:
+    /// `crate::pull_parser::error::Warning::ExtraAttributeData`
+    /// [`Warning::MissingAttributeData`]:
+    /// `crate::pull_parser::error::Warning::MissingAttributeData`
+    pub strict_attribute_count: bool,
+    /// Whether to report nodes skipped by [`Parser::skip_current_node`].
+    ///
+    /// If `true`, the next event after skipping a node is
+    /// [`Event::SkippedNode`], which has the name and the byte length of the
+    /// skipped node.
+    /// This helps auditing tools to account for the unprocessed regions.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [`Parser::skip_current_node`]: `super::Parser::skip_current_node`
+    /// [`Event::SkippedNode`]: `super::Event::SkippedNode`
+    pub report_skipped_nodes: bool,
+    /// Maximum number of warnings of the same kind passed to the warning
+    /// handler.
+    ///
+    /// Corrupt data can cause a huge number of identical warnings (such as
+    /// [`Warning::IncorrectBooleanRepresentation`]).
+    /// Warnings of the same enum variant beyond this limit are not passed to
+    /// the warning handler (i.e. they are treated as non-critical), and
+    /// counted instead.
+    /// The counts can be retrieved by [`Parser::suppressed_warnings`].
+    ///
+    /// `None` means unlimited.
+    ///
+    /// [`Warning::IncorrectBooleanRepresentation`]:
+    /// `crate::pull_parser::error::Warning::IncorrectBooleanRepresentation`
+    /// [`Parser::suppressed_warnings`]: `super::Parser::suppressed_warnings`
+    pub max_warnings_per_kind: Option<u64>,
+    /// Maximum distance to skip by reading instead of seeking.
+    ///
+    /// This is passed to [`ParserSource::set_skip_small_threshold`], and is
+    /// effective only for seekable sources such as [`SeekableSource`].
+    /// Reading short distances lets buffered readers consume the skipped
+    /// data from their buffer, instead of discarding the buffer on seek.
+    ///
+    /// `None` means the default of the source.
+    ///
+    /// [`ParserSource::set_skip_small_threshold`]:
+    /// `crate::pull_parser::ParserSource::set_skip_small_threshold`
+    /// [`SeekableSource`]: `crate::pull_parser::reader::SeekableSource`
+    pub skip_small_threshold: Option<u64>,
+}
@@ -1,7 +1,7 @@
 //! Parser event.
 
 use crate::{
-    low::v7400::FbxFooter,
+    low::v7400::{AttributeValue, FbxFooter},
     pull_parser::{
         v7400::{Attributes, Parser},
         ParserSource, Result,
@@ -15,6 +15,20 @@ pub enum Event<'a, R> {
     StartNode(StartNode<'a, R>),
     /// End of a node.
     EndNode,
+    /// A node skipped by [`Parser::skip_current_node`].
+    ///
+    /// This is emitted right after skipping a node, only when
+    /// [`ParserConfig::report_skipped_nodes`] is enabled.
+    /// The depth of the parser is not changed by this event.
+    ///
+    /// [`ParserConfig::report_skipped_nodes`]:
+    /// `crate::pull_parser::v7400::ParserConfig::report_skipped_nodes`
+    SkippedNode {
+        /// Node name.
+        name: String,
+        /// Byte length of the whole node, including its children.
+        bytelen: u64,
+    },
     /// End of an FBX document.
     ///
     /// This will contain `Ok(_)` if the the FBX footer is succssfully read,
@@ -22,6 +36,20 @@ pub enum Event<'a, R> {
     EndFbx(Result<Box<FbxFooter>>),
 }
 
+impl<'a, R: 'a + ParserSource> Event<'a, R> {
+    /// Converts the event into an owned event.
+    ///
+    /// Node attributes are loaded or skipped according to the given policy.
+    pub fn into_owned(self, policy: AttributeLoadPolicy) -> Result<OwnedEvent> {
+        match self {
+            Event::StartNode(start) => start.into_owned(policy).map(OwnedEvent::StartNode),
+            Event::EndNode => Ok(OwnedEvent::EndNode),
+            Event::SkippedNode { name, bytelen } => Ok(OwnedEvent::SkippedNode { name, bytelen }),
+            Event::EndFbx(footer) => Ok(OwnedEvent::EndFbx(footer)),
+        }
+    }
+}
+
 /// Node start event.
 #[derive(Debug)]
 pub struct StartNode<'a, R> {
@@ -44,6 +72,47 @@ impl<'a, R: 'a + ParserSource> StartNode<'a, R> {
         self.parser.current_node_name()
     }
 
+    /// Returns the start offset of the node.
+    #[inline]
+    #[must_use]
+    pub fn start_offset(&self) -> u64 {
+        self.parser.current_node_start_offset()
+    }
+
+    /// Returns the end offset of the node.
+    ///
+    /// "End offset" means the next byte of the last byte of the node,
+    /// including its children and the node end marker.
+    #[inline]
+    #[must_use]
+    pub fn end_offset(&self) -> u64 {
+        self.parser.current_node_end_offset()
+    }
+
+    /// Returns the byte length of the whole node, including its children.
+    ///
+    /// This can be used to decide whether to skip a large node before loading
+    /// its attributes.
+    #[inline]
+    #[must_use]
+    pub fn bytelen(&self) -> u64 {
+        self.end_offset().saturating_sub(self.start_offset())
+    }
+
+    /// Returns the number of the node attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes_count(&self) -> u64 {
+        self.parser.current_attributes_count()
+    }
+
+    /// Returns the byte length of the node attributes.
+    #[inline]
+    #[must_use]
+    pub fn attributes_bytelen(&self) -> u64 {
+        self.parser.current_attributes_bytelen()
+    }
+
     /// Returns node attributes reader.
     #[inline]
     #[must_use]
@@ -51,3 +120,115 @@ impl<'a, R: 'a + ParserSource> StartNode<'a, R> {
         Attributes::from_parser(self.parser)
     }
 }
+
+impl<'a, R: 'a + ParserSource> StartNode<'a, R> {
+    /// Converts the event into an owned event.
+    ///
+    /// Node attributes are loaded or skipped according to the given policy.
+    pub fn into_owned(self, policy: AttributeLoadPolicy) -> Result<OwnedStartNode> {
+        let name = self.name().to_owned();
+        let mut attrs = self.attributes();
+        let attributes_count = attrs.total_count();
+        let attributes = match policy {
+            AttributeLoadPolicy::Load => attrs.load_all_direct()?,
+            AttributeLoadPolicy::Skip => {
+                attrs.finish();
+                Vec::new()
+            }
+        };
+
+        Ok(OwnedStartNode {
+            name,
+            attributes_count,
+            attributes,
+        })
+    }
+}
+
+/// Policy to load node attributes on conversion into owned events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AttributeLoadPolicy {
+    /// Loads all node attributes.
+    Load,
+    /// Skips all node attributes.
+    Skip,
+}
+
+impl Default for AttributeLoadPolicy {
+    #[inline]
+    fn default() -> Self {
+        AttributeLoadPolicy::Load
+    }
+}
+
+/// Parser event which owns its data.
+///
+/// Unlike [`Event`], this does not borrow the parser, and can be sent to
+/// other threads.
+#[derive(Debug)]
+pub enum OwnedEvent {
+    /// Start of a node.
+    StartNode(OwnedStartNode),
+    /// End of a node.
+    EndNode,
+    /// A node skipped by [`Parser::skip_current_node`].
+    ///
+    /// See [`Event::SkippedNode`].
+    SkippedNode {
+        /// Node name.
+        name: String,
+        /// Byte length of the whole node, including its children.
+        bytelen: u64,
+    },
+    /// End of an FBX document.
+    ///
+    /// This will contain `Ok(_)` if the the FBX footer is succssfully read,
+    /// contain `Err(_)` if the parser failed to load the FBX footer.
+    EndFbx(Result<Box<FbxFooter>>),
+}
+
+/// Node start event which owns its data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedStartNode {
+    /// Node name.
+    name: String,
+    /// Number of node attributes.
+    attributes_count: u64,
+    /// Loaded node attributes.
+    attributes: Vec<AttributeValue>,
+}
+
+impl OwnedStartNode {
+    /// Returns the node name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of node attributes.
+    ///
+    /// This is available even if the attributes are skipped.
+    #[inline]
+    #[must_use]
+    pub fn attributes_count(&self) -> u64 {
+        self.attributes_count
+    }
+
+    /// Returns the loaded node attributes.
+    ///
+    /// This is empty if the attributes are skipped.
+    #[inline]
+    #[must_use]
+    pub fn attributes(&self) -> &[AttributeValue] {
+        &self.attributes
+    }
+
+    /// Returns the node name and the loaded node attributes.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (String, Vec<AttributeValue>) {
+        (self.name, self.attributes)
+    }
+}
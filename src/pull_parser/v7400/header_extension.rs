@@ -0,0 +1,211 @@
+//! FBX header extension.
+
+use crate::{
+    low::v7400::AttributeValue,
+    pull_parser::{
+        v7400::{attribute::loaders::DirectLoader, Event, Parser},
+        ParserSource, Result,
+    },
+};
+
+/// Name of the top-level header extension node.
+const HEADER_EXTENSION_NODE_NAME: &str = "FBXHeaderExtension";
+
+/// Provenance metadata stored in the `FBXHeaderExtension` node.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct HeaderExtension {
+    /// `FBXHeaderVersion` value.
+    pub header_version: Option<i32>,
+    /// `FBXVersion` value.
+    pub fbx_version: Option<i32>,
+    /// `Creator` value.
+    pub creator: Option<String>,
+    /// `CreationTimeStamp` value.
+    pub creation_timestamp: Option<CreationTimeStamp>,
+    /// `SceneInfo` value.
+    pub scene_info: Option<SceneInfo>,
+}
+
+/// Creation time stamp stored in `FBXHeaderExtension/CreationTimeStamp`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CreationTimeStamp {
+    /// Year.
+    pub year: i32,
+    /// Month (1-12).
+    pub month: i32,
+    /// Day (1-31).
+    pub day: i32,
+    /// Hour.
+    pub hour: i32,
+    /// Minute.
+    pub minute: i32,
+    /// Second.
+    pub second: i32,
+    /// Millisecond.
+    pub millisecond: i32,
+}
+
+/// Scene metadata stored in `FBXHeaderExtension/SceneInfo/MetaData`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SceneInfo {
+    /// `Title` value.
+    pub title: Option<String>,
+    /// `Subject` value.
+    pub subject: Option<String>,
+    /// `Author` value.
+    pub author: Option<String>,
+    /// `Keywords` value.
+    pub keywords: Option<String>,
+    /// `Revision` value.
+    pub revision: Option<String>,
+    /// `Comment` value.
+    pub comment: Option<String>,
+}
+
+/// Loads the `FBXHeaderExtension` node without reading the rest of the
+/// document.
+///
+/// Top-level nodes preceding `FBXHeaderExtension` are skipped.
+/// When this returns `Ok(Some(_))`, the parser is positioned just after the
+/// `FBXHeaderExtension` node and can be used to read the remaining top-level
+/// nodes.
+/// When this returns `Ok(None)`, the document has no header extension and
+/// the parser has already reached the end of the document (and the footer is
+/// discarded).
+///
+/// Unknown children and values of unexpected types are ignored.
+///
+/// # Panics
+///
+/// Panics if the parser is not at the top level (i.e. some node is open).
+pub fn load_header_extension<R: ParserSource>(
+    parser: &mut Parser<R>,
+) -> Result<Option<HeaderExtension>> {
+    assert_eq!(
+        parser.current_depth(),
+        0,
+        "The parser should be at the top level"
+    );
+
+    loop {
+        let is_header_extension = match parser.next_event()? {
+            Event::StartNode(start) => start.name() == HEADER_EXTENSION_NODE_NAME,
+            Event::EndNode => unreachable!("`EndNode` should not be emitted at the top level"),
+            Event::SkippedNode { .. } => continue,
+            Event::EndFbx(_) => return Ok(None),
+        };
+        if is_header_extension {
+            return load_header_extension_children(parser).map(Some);
+        }
+        parser.skip_current_node()?;
+    }
+}
+
+/// Loads the children of the `FBXHeaderExtension` node, and closes the node.
+fn load_header_extension_children<R: ParserSource>(
+    parser: &mut Parser<R>,
+) -> Result<HeaderExtension> {
+    let mut ext = HeaderExtension::default();
+    while let Some((name, value)) = next_child(parser)? {
+        match &*name {
+            "FBXHeaderVersion" => ext.header_version = value.and_then(|v| v.get_i32()),
+            "FBXVersion" => ext.fbx_version = value.and_then(|v| v.get_i32()),
+            "Creator" => ext.creator = value.and_then(into_string),
+            "CreationTimeStamp" => {
+                ext.creation_timestamp = Some(load_creation_timestamp(parser)?);
+                continue;
+            }
+            "SceneInfo" => {
+                ext.scene_info = Some(load_scene_info(parser)?);
+                continue;
+            }
+            _ => {}
+        }
+        parser.skip_current_node()?;
+    }
+
+    Ok(ext)
+}
+
+/// Loads the children of the `CreationTimeStamp` node, and closes the node.
+fn load_creation_timestamp<R: ParserSource>(parser: &mut Parser<R>) -> Result<CreationTimeStamp> {
+    let mut timestamp = CreationTimeStamp::default();
+    while let Some((name, value)) = next_child(parser)? {
+        let field = match &*name {
+            "Year" => Some(&mut timestamp.year),
+            "Month" => Some(&mut timestamp.month),
+            "Day" => Some(&mut timestamp.day),
+            "Hour" => Some(&mut timestamp.hour),
+            "Minute" => Some(&mut timestamp.minute),
+            "Second" => Some(&mut timestamp.second),
+            "Millisecond" => Some(&mut timestamp.millisecond),
+            _ => None,
+        };
+        if let (Some(field), Some(v)) = (field, value.and_then(|v| v.get_i32())) {
+            *field = v;
+        }
+        parser.skip_current_node()?;
+    }
+
+    Ok(timestamp)
+}
+
+/// Loads the `MetaData` child of the `SceneInfo` node, and closes the node.
+fn load_scene_info<R: ParserSource>(parser: &mut Parser<R>) -> Result<SceneInfo> {
+    let mut info = SceneInfo::default();
+    while let Some((name, _)) = next_child(parser)? {
+        if name != "MetaData" {
+            parser.skip_current_node()?;
+            continue;
+        }
+        while let Some((name, value)) = next_child(parser)? {
+            let field = match &*name {
+                "Title" => Some(&mut info.title),
+                "Subject" => Some(&mut info.subject),
+                "Author" => Some(&mut info.author),
+                "Keywords" => Some(&mut info.keywords),
+                "Revision" => Some(&mut info.revision),
+                "Comment" => Some(&mut info.comment),
+                _ => None,
+            };
+            if let Some(field) = field {
+                *field = value.and_then(into_string);
+            }
+            parser.skip_current_node()?;
+        }
+    }
+
+    Ok(info)
+}
+
+/// Reads the next child node, and returns its name and the first attribute.
+///
+/// The child node is left open.
+/// Returns `Ok(None)` if the current node is closed.
+fn next_child<R: ParserSource>(
+    parser: &mut Parser<R>,
+) -> Result<Option<(String, Option<AttributeValue>)>> {
+    loop {
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                let name = start.name().to_owned();
+                let value = start.attributes().load_next(DirectLoader)?;
+                return Ok(Some((name, value)));
+            }
+            Event::EndNode => return Ok(None),
+            Event::SkippedNode { .. } => {}
+            Event::EndFbx(_) => unreachable!("The current node should be closed before `EndFbx`"),
+        }
+    }
+}
+
+/// Converts the attribute value into a string, if possible.
+fn into_string(value: AttributeValue) -> Option<String> {
+    match value {
+        AttributeValue::String(v) => Some(v),
+        _ => None,
+    }
+}
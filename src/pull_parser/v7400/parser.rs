@@ -1,20 +1,27 @@
 //! Parser for FBX 7.4 or later.
 
-use std::{fmt, io};
+use std::{collections::VecDeque, fmt, io, mem, num::NonZeroUsize};
 
 use crate::{
     low::{
-        v7400::{FbxFooter, NodeHeader},
+        v7400::{FbxFooter, NodeHeader, NodeHeaderError},
         FbxHeader, FbxVersion,
     },
     pull_parser::{
-        error::{DataError, OperationError},
+        error::{DataError, Limit, OperationError},
         reader::{PlainSource, SeekableSource},
-        v7400::{Event, FromParser, StartNode},
-        Error, ParserSource, ParserVersion, Result, SyntacticPosition, Warning,
+        v7400::{
+            AttributeLoadPolicy, Event, EventStream, FromParser, OwnedEvent, ParserConfig,
+            StartNode,
+        },
+        Error, ParserSource, ParserVersion, Result, SeekableParserSource, SyntacticPosition,
+        Warning,
     },
 };
 
+/// Default size of the read buffer for compressed array attributes.
+const DEFAULT_DECOMPRESSION_BUF_LEN: usize = 8 * 1024;
+
 /// Warning handler type.
 type WarningHandler = Box<dyn FnMut(Warning, &SyntacticPosition) -> Result<()>>;
 
@@ -54,6 +61,14 @@ pub struct Parser<R> {
     reader: R,
     /// Warning handler.
     warning_handler: Option<WarningHandler>,
+    /// Buffer for compressed array attributes, reused across attributes.
+    compressed_buf: Vec<u8>,
+    /// Parser configuration.
+    config: ParserConfig,
+    /// Data after the FBX footer, if read.
+    trailing_data: Option<Vec<u8>>,
+    /// Number of warnings for each kind, if counted.
+    warning_counts: Vec<WarningCount>,
 }
 
 impl<R: ParserSource> Parser<R> {
@@ -74,6 +89,10 @@ impl<R: ParserSource> Parser<R> {
             state: State::new(fbx_version),
             reader,
             warning_handler: None,
+            compressed_buf: Vec::new(),
+            config: ParserConfig::default(),
+            trailing_data: None,
+            warning_counts: Vec::new(),
         })
     }
 
@@ -114,6 +133,40 @@ impl<R: ParserSource> Parser<R> {
         self.warning_handler = Some(Box::new(warning_handler));
     }
 
+    /// Sets the parser configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel::{low::FbxHeader, pull_parser::v7400::ParserConfig};
+    /// # let reader = std::io::empty();
+    /// # let header: FbxHeader = unimplemented!();
+    /// let mut parser = fbxcel::pull_parser::v7400::from_reader(header, reader)
+    ///     .expect("Failed to create parser");
+    /// let mut config = ParserConfig::default();
+    /// config.max_depth = Some(64);
+    /// config.max_node_count = Some(1_000_000);
+    /// parser.set_config(config);
+    /// ```
+    #[inline]
+    pub fn set_config(&mut self, config: ParserConfig) {
+        if config.skip_small_threshold != self.config.skip_small_threshold {
+            self.reader
+                .set_skip_small_threshold(config.skip_small_threshold);
+        }
+        if config.skip_buffer_size != self.config.skip_buffer_size {
+            self.reader.set_skip_buffer_size(config.skip_buffer_size);
+        }
+        self.config = config;
+    }
+
+    /// Returns the parser configuration.
+    #[inline]
+    #[must_use]
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
     /// Returns a mutable reference to the inner reader.
     #[inline]
     #[must_use]
@@ -153,6 +206,129 @@ impl<R: ParserSource> Parser<R> {
             .attributes_count
     }
 
+    /// Returns the end offset of the attributes of the current node.
+    #[inline]
+    #[must_use]
+    pub(crate) fn current_attributes_end_offset(&self) -> u64 {
+        self.state
+            .current_node()
+            .expect("Implicit top-level node has no attributes")
+            .attributes_end_offset
+    }
+
+    /// Returns the number and the byte length of the attributes of the
+    /// current node, if none of them are read yet.
+    #[cfg(feature = "writer")]
+    #[must_use]
+    pub(crate) fn current_unread_attributes(&self) -> Option<(u64, u64)> {
+        let node = self.state.current_node()?;
+        if node.rest_attributes_count != node.attributes_count {
+            return None;
+        }
+        Some((node.attributes_count, node.attributes_bytelen))
+    }
+
+    /// Marks an attribute of the current node as read.
+    #[inline]
+    pub(crate) fn mark_current_attribute_read(&mut self) {
+        if let Some(node) = self.state.started_nodes.last_mut() {
+            node.rest_attributes_count = node.rest_attributes_count.saturating_sub(1);
+        }
+    }
+
+    /// Sets the number of the rest attributes of the current node.
+    #[inline]
+    pub(crate) fn set_current_rest_attributes_count(&mut self, count: u64) {
+        if let Some(node) = self.state.started_nodes.last_mut() {
+            node.rest_attributes_count = count;
+        }
+    }
+
+    /// Marks the rest attributes of the current node as explicitly finished.
+    #[inline]
+    pub(crate) fn finish_current_attributes(&mut self) {
+        if let Some(node) = self.state.started_nodes.last_mut() {
+            node.rest_attributes_count = 0;
+        }
+    }
+
+    /// Returns the number of nodes whose attributes are implicitly skipped.
+    ///
+    /// Attributes are implicitly skipped when the next event is requested
+    /// while some attributes of the current node remain unread, i.e. when
+    /// [`StartNode`][`super::StartNode`] or
+    /// [`Attributes`][`super::Attributes`] is dropped without reading all
+    /// the attributes.
+    /// Attributes explicitly skipped by
+    /// [`Attributes::finish`][`super::Attributes::finish`] or
+    /// [`skip_current_node`][`Self::skip_current_node`] are not counted.
+    ///
+    /// See also [`ParserConfig::warn_unread_attributes`].
+    #[inline]
+    #[must_use]
+    pub fn nodes_with_unread_attributes_count(&self) -> u64 {
+        self.state.nodes_with_unread_attributes_count
+    }
+
+    /// Returns the start offset of the current node.
+    #[inline]
+    #[must_use]
+    pub(crate) fn current_node_start_offset(&self) -> u64 {
+        self.state
+            .current_node()
+            .expect("Implicit top-level node has no start offset")
+            .node_start_offset
+    }
+
+    /// Returns the end offset of the current node.
+    #[inline]
+    #[must_use]
+    pub(crate) fn current_node_end_offset(&self) -> u64 {
+        self.state
+            .current_node()
+            .expect("Implicit top-level node has no end offset")
+            .node_end_offset
+    }
+
+    /// Returns the byte length of the attributes of the current node.
+    #[inline]
+    #[must_use]
+    pub(crate) fn current_attributes_bytelen(&self) -> u64 {
+        self.state
+            .current_node()
+            .expect("Implicit top-level node has no attributes")
+            .attributes_bytelen
+    }
+
+    /// Takes the read buffer for compressed array attributes.
+    ///
+    /// The buffer has the size of
+    /// [`ParserConfig::decompression_buffer_size`].
+    /// Only the buffer is reused among array attributes, since the zlib
+    /// decoder of `libflate` cannot be reset.
+    ///
+    /// It should be returned by [`restore_compressed_buf`][`Self::restore_compressed_buf`]
+    /// after use.
+    #[must_use]
+    pub(crate) fn take_compressed_buf(&mut self) -> Vec<u8> {
+        let len = self
+            .config
+            .decompression_buffer_size
+            .map_or(DEFAULT_DECOMPRESSION_BUF_LEN, NonZeroUsize::get);
+        let mut buf = mem::take(&mut self.compressed_buf);
+        buf.resize(len, 0);
+        // Release the memory if the configured size has been reduced.
+        buf.shrink_to_fit();
+        buf
+    }
+
+    /// Returns the read buffer for compressed array attributes to the parser
+    /// for reuse.
+    #[inline]
+    pub(crate) fn restore_compressed_buf(&mut self, buf: Vec<u8>) {
+        self.compressed_buf = buf;
+    }
+
     /// Returns current node depth.
     ///
     /// Implicit root node is considered to be depth 0.
@@ -180,8 +356,73 @@ impl<R: ParserSource> Parser<R> {
         T::read_from_parser(self)
     }
 
+    /// Returns the warnings suppressed by
+    /// [`ParserConfig::max_warnings_per_kind`].
+    ///
+    /// This returns the first suppressed warning of each kind, its position,
+    /// and the number of the suppressed warnings of the kind.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel::{low::FbxHeader, pull_parser::v7400::{Event, ParserConfig}};
+    /// # let reader = std::io::empty();
+    /// # let header: FbxHeader = unimplemented!();
+    /// let mut parser = fbxcel::pull_parser::v7400::from_reader(header, reader)
+    ///     .expect("Failed to create parser");
+    /// let mut config = ParserConfig::default();
+    /// config.max_warnings_per_kind = Some(10);
+    /// parser.set_config(config);
+    /// parser.set_warning_handler(|warning, pos| {
+    ///     eprintln!("WARNING: {} (pos={:?})", warning, pos);
+    ///     Ok(())
+    /// });
+    /// while !matches!(parser.next_event()?, Event::EndFbx(_)) {}
+    /// for (warning, pos, count) in parser.suppressed_warnings() {
+    ///     eprintln!("{} more warnings like: {} (pos={:?})", count, warning, pos);
+    /// }
+    /// # Ok::<_, fbxcel::pull_parser::Error>(())
+    /// ```
+    pub fn suppressed_warnings(
+        &self,
+    ) -> impl Iterator<Item = (&Warning, &SyntacticPosition, u64)> + '_ {
+        self.warning_counts.iter().filter_map(|count| {
+            count
+                .first_suppressed
+                .as_ref()
+                .map(|(warning, pos)| (warning, pos, count.suppressed))
+        })
+    }
+
     /// Passes the given warning to the warning handler.
+    ///
+    /// The warning is suppressed if the number of warnings of the same kind
+    /// exceeds [`ParserConfig::max_warnings_per_kind`].
     pub(crate) fn warn(&mut self, warning: Warning, pos: SyntacticPosition) -> Result<()> {
+        if let Some(limit) = self.config.max_warnings_per_kind {
+            let kind = mem::discriminant(&warning);
+            let counts = &mut self.warning_counts;
+            let count = match counts.iter().position(|count| count.kind == kind) {
+                Some(i) => &mut counts[i],
+                None => {
+                    counts.push(WarningCount {
+                        kind,
+                        reported: 0,
+                        suppressed: 0,
+                        first_suppressed: None,
+                    });
+                    counts.last_mut().expect("Should never fail: just pushed")
+                }
+            };
+            if count.reported >= limit {
+                count.suppressed += 1;
+                if count.first_suppressed.is_none() {
+                    count.first_suppressed = Some((warning, pos));
+                }
+                return Ok(());
+            }
+            count.reported += 1;
+        }
         match self.warning_handler {
             Some(ref mut handler) => match handler(warning, &pos) {
                 Ok(()) => Ok(()),
@@ -191,6 +432,62 @@ impl<R: ParserSource> Parser<R> {
         }
     }
 
+    /// Returns next event as an owned event if successfully read.
+    ///
+    /// Node attributes are loaded or skipped according to the given policy.
+    /// The returned event does not borrow the parser, so it can be sent to
+    /// other threads (for example, through a channel).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel::low::FbxHeader;
+    /// use fbxcel::pull_parser::v7400::{AttributeLoadPolicy, OwnedEvent};
+    /// # let reader = std::io::empty();
+    /// # let header: FbxHeader = unimplemented!();
+    /// let mut parser = fbxcel::pull_parser::v7400::from_reader(header, reader)
+    ///     .expect("Failed to create parser");
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// loop {
+    ///     let event = parser.next_owned_event(AttributeLoadPolicy::Load)?;
+    ///     let is_end = matches!(event, OwnedEvent::EndFbx(_));
+    ///     sender.send(event).expect("Failed to send");
+    ///     if is_end {
+    ///         break;
+    ///     }
+    /// }
+    /// # let _ = receiver;
+    /// # Ok::<_, fbxcel::pull_parser::Error>(())
+    /// ```
+    pub fn next_owned_event(&mut self, policy: AttributeLoadPolicy) -> Result<OwnedEvent> {
+        self.next_event()?.into_owned(policy)
+    }
+
+    /// Converts the parser into an iterator of owned events.
+    ///
+    /// With `futures-core` feature, the returned value also implements
+    /// `futures_core::Stream`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel::low::FbxHeader;
+    /// use fbxcel::pull_parser::v7400::OwnedEvent;
+    /// # let reader = std::io::empty();
+    /// # let header: FbxHeader = unimplemented!();
+    /// let parser = fbxcel::pull_parser::v7400::from_reader(header, reader)
+    ///     .expect("Failed to create parser");
+    /// let nodes_count = parser
+    ///     .into_event_stream()
+    ///     .filter(|ev| matches!(ev, Ok(OwnedEvent::StartNode(_))))
+    ///     .count();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_event_stream(self) -> EventStream<R> {
+        EventStream::new(self)
+    }
+
     /// Returns next event if successfully read.
     ///
     /// You should not call `next_event()` if a parser functionality has been
@@ -203,6 +500,11 @@ impl<R: ParserSource> Parser<R> {
         // Precondition: Health should be `Health::Running`.
         self.ensure_continuable()?;
 
+        // Report the nodes skipped just before, if necessary.
+        if let Some((name, bytelen)) = self.state.skipped_nodes.pop_front() {
+            return Ok(Event::SkippedNode { name, bytelen });
+        }
+
         // Update health.
         let event_kind = match self.next_event_impl() {
             Ok(v) => v,
@@ -260,12 +562,56 @@ impl<R: ParserSource> Parser<R> {
             EventKind::StartNode => Event::StartNode(StartNode::new(self)),
             EventKind::EndNode => Event::EndNode,
             EventKind::EndFbx => {
-                let footer_res = FbxFooter::read_from_parser(self).map(Box::new);
+                let footer_res = FbxFooter::read_from_parser(self).and_then(|footer| {
+                    if self.config.read_trailing_data {
+                        self.read_trailing_data()?;
+                    }
+                    Ok(Box::new(footer))
+                });
                 Event::EndFbx(footer_res)
             }
         })
     }
 
+    /// Reads all the data after the FBX footer, and reports a warning if
+    /// exists.
+    fn read_trailing_data(&mut self) -> Result<()> {
+        let pos = self.position();
+        let mut data = Vec::new();
+        match self.config.max_trailing_data_len {
+            Some(max_len) => {
+                // Read one more byte to detect the excess.
+                let mut reader = io::Read::take(&mut self.reader, max_len.saturating_add(1));
+                io::Read::read_to_end(&mut reader, &mut data)?;
+                if data.len() as u64 > max_len {
+                    return Err(DataError::LimitExceeded(Limit::TrailingDataLength(max_len)).into());
+                }
+            }
+            None => {
+                self.reader.read_to_end(&mut data)?;
+            }
+        }
+        let len = data.len() as u64;
+        self.trailing_data = Some(data);
+        if len != 0 {
+            self.warn(Warning::TrailingData(len), pos)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the data after the FBX footer.
+    ///
+    /// This returns `Some(_)` only when
+    /// [`ParserConfig::read_trailing_data`] is enabled and the FBX footer is
+    /// successfully read.
+    /// The returned data is empty if there are no trailing data.
+    #[inline]
+    #[must_use]
+    pub fn trailing_data(&self) -> Option<&[u8]> {
+        self.trailing_data.as_deref()
+    }
+
     /// Reads the next node header and changes the parser state (except for
     /// parser health and the last event kind).
     fn next_event_impl(&mut self) -> Result<EventKind> {
@@ -339,6 +685,28 @@ impl<R: ParserSource> Parser<R> {
             };
         }
 
+        // Check if the node fits in the source, if the length is known.
+        // This detects truncated or broken data before reading attributes.
+        node_header
+            .validate(self.fbx_version(), self.reader.total_len())
+            .map_err(|e| match e {
+                NodeHeaderError::BeyondFileEnd(end_offset, file_len) => {
+                    DataError::NodeLengthMismatch(end_offset, Some(file_len))
+                }
+                NodeHeaderError::EndOffsetTooLarge(end_offset) => {
+                    DataError::NodeLengthMismatch(end_offset, None)
+                }
+                NodeHeaderError::TooManyAttributes(_) | NodeHeaderError::AttributesTooLong(_) => {
+                    DataError::NodeAttributeError
+                }
+                #[cfg(feature = "writer")]
+                NodeHeaderError::NameTooLong(_) => {
+                    unreachable!("Names of the read node headers should fit in `u8`")
+                }
+            })?;
+
+        self.check_node_limits(&node_header)?;
+
         if node_header.bytelen_name == 0 {
             let mut pos = self.position();
             // Need to modify position, because the currently reading node is
@@ -368,6 +736,8 @@ impl<R: ParserSource> Parser<R> {
             node_end_offset: node_header.end_offset,
             attributes_count: node_header.num_attributes,
             attributes_end_offset: current_offset + node_header.bytelen_attributes,
+            attributes_bytelen: node_header.bytelen_attributes,
+            rest_attributes_count: node_header.num_attributes,
             name,
             known_children_count: 0,
         };
@@ -377,18 +747,66 @@ impl<R: ParserSource> Parser<R> {
             Some(parent) => parent.known_children_count += 1,
             None => self.state.known_toplevel_nodes_count += 1,
         }
+        self.state.known_nodes_count += 1;
         self.state.started_nodes.push(starting);
+
+        if self.config.strict_attribute_count {
+            let warning = match (node_header.num_attributes, node_header.bytelen_attributes) {
+                (0, 0) => None,
+                (0, bytelen) => Some(Warning::ExtraAttributeData(bytelen)),
+                (count, 0) => Some(Warning::MissingAttributeData(count)),
+                _ => None,
+            };
+            if let Some(warning) = warning {
+                let pos = self.position();
+                self.warn(warning, pos)?;
+            }
+        }
+
         Ok(EventKind::StartNode)
     }
 
+    /// Checks if the node to be started exceeds the limits.
+    fn check_node_limits(&self, node_header: &NodeHeader) -> Result<()> {
+        if let Some(max_depth) = self.config.max_depth {
+            if self.current_depth() >= max_depth {
+                return Err(DataError::LimitExceeded(Limit::Depth(max_depth)).into());
+            }
+        }
+        if let Some(max_node_count) = self.config.max_node_count {
+            if self.state.known_nodes_count >= max_node_count {
+                return Err(DataError::LimitExceeded(Limit::NodeCount(max_node_count)).into());
+            }
+        }
+        if let Some(max_bytelen) = self.config.max_attribute_bytelen {
+            if node_header.bytelen_attributes > max_bytelen {
+                return Err(DataError::LimitExceeded(Limit::AttributeBytelen(max_bytelen)).into());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Skips unread attribute of the current node, if remains.
     ///
     /// If there are no unread attributes, this method simply do nothing.
+    ///
+    /// If some attributes are neither read nor explicitly finished, the
+    /// skip is counted, and [`Warning::UnreadAttributesSkipped`] is reported
+    /// if enabled by [`ParserConfig::warn_unread_attributes`].
     fn skip_unread_attributes(&mut self) -> Result<()> {
-        let attributes_end_offset = match self.state.current_node() {
-            Some(v) => v.attributes_end_offset,
+        let (attributes_end_offset, rest_count) = match self.state.current_node() {
+            Some(v) => (v.attributes_end_offset, v.rest_attributes_count),
             None => return Ok(()),
         };
+        if rest_count != 0 {
+            self.state.nodes_with_unread_attributes_count += 1;
+            if self.config.warn_unread_attributes {
+                let pos = self.position();
+                self.warn(Warning::UnreadAttributesSkipped(rest_count), pos)?;
+            }
+            self.finish_current_attributes();
+        }
         if attributes_end_offset > self.reader().position() {
             // Skip if attributes remains (partially or entirely) unread.
             self.reader().skip_to(attributes_end_offset)?;
@@ -409,6 +827,11 @@ impl<R: ParserSource> Parser<R> {
     /// node is read.
     /// The last [`EndNode`] (for the current node) is also discarded.
     ///
+    /// If [`ParserConfig::report_skipped_nodes`] is enabled, the next event
+    /// will be [`Event::SkippedNode`] for the skipped node.
+    /// If nodes are skipped successively, they are reported in the skipped
+    /// order.
+    ///
     /// This method seeks to the node end position without any additional
     /// parsing, since the parser already knows the node end position.
     /// Because of this, some errors can be overlooked, or detected at the
@@ -449,6 +872,7 @@ impl<R: ParserSource> Parser<R> {
     ///         match parser.next_event()? {
     ///             Event::StartNode(_) => skip_current_node(parser)?,
     ///             Event::EndNode => return Ok(()),
+    ///             Event::SkippedNode { .. } => {}
     ///             Event::EndFbx(_) => panic!("Attempt to skip implicit top-level node"),
     ///         }
     ///     }
@@ -457,13 +881,18 @@ impl<R: ParserSource> Parser<R> {
     ///
     /// [`EndNode`]: `Event::EndNode`
     pub fn skip_current_node(&mut self) -> Result<()> {
-        let end_pos = self
+        let node = self
             .state
             .started_nodes
             .pop()
-            .expect("Attempt to skip implicit top-level node")
-            .node_end_offset;
+            .expect("Attempt to skip implicit top-level node");
+        let end_pos = node.node_end_offset;
         self.state.last_event_kind = Some(EventKind::EndNode);
+        if self.config.report_skipped_nodes {
+            self.state
+                .skipped_nodes
+                .push_back((node.name, end_pos - node.node_start_offset));
+        }
         self.reader.skip_to(end_pos)?;
 
         Ok(())
@@ -545,11 +974,102 @@ impl<R: ParserSource> Parser<R> {
     }
 }
 
+impl<R: SeekableParserSource> Parser<R> {
+    /// Captures the current position and state of the parser.
+    ///
+    /// The parser can go back to the bookmarked position later by
+    /// [`resume`][`Self::resume`], without re-opening the file and
+    /// re-parsing the header.
+    /// This is useful for two-pass algorithms such as "count then load".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::pull_parser::{v7400::{Event, Parser}, Result, SeekableParserSource};
+    /// # fn f<R: SeekableParserSource>(parser: &mut Parser<R>) -> Result<()> {
+    /// let bookmark = parser.bookmark();
+    ///
+    /// // First pass: count the top-level nodes.
+    /// let mut count = 0;
+    /// loop {
+    ///     match parser.next_event()? {
+    ///         Event::StartNode(_) => {
+    ///             count += 1;
+    ///             parser.skip_current_node()?;
+    ///         }
+    ///         Event::EndNode => unreachable!("Top-level nodes are skipped"),
+    ///         Event::SkippedNode { .. } => {}
+    ///         Event::EndFbx(_) => break,
+    ///     }
+    /// }
+    ///
+    /// // Second pass: collect their names.
+    /// parser.resume(&bookmark)?;
+    /// let mut names = Vec::with_capacity(count);
+    /// loop {
+    ///     match parser.next_event()? {
+    ///         Event::StartNode(start) => {
+    ///             names.push(start.name().to_owned());
+    ///             parser.skip_current_node()?;
+    ///         }
+    ///         Event::EndNode => unreachable!("Top-level nodes are skipped"),
+    ///         Event::SkippedNode { .. } => {}
+    ///         Event::EndFbx(_) => break,
+    ///     }
+    /// }
+    /// assert_eq!(names.len(), count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn bookmark(&self) -> Bookmark {
+        Bookmark {
+            state: self.state.clone(),
+            byte_pos: self.reader.position(),
+        }
+    }
+
+    /// Goes back (or forward) to the bookmarked position and state.
+    ///
+    /// Configuration and warning handler are not affected.
+    /// The counts of warnings for
+    /// [`ParserConfig::max_warnings_per_kind`] are reset, so that the
+    /// warnings found again after resuming are reported in the same way as
+    /// the first time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bookmark is created for data of another FBX version.
+    /// Note that this cannot detect bookmarks created for other data of the
+    /// same FBX version, and such bookmarks make the parser state broken.
+    pub fn resume(&mut self, bookmark: &Bookmark) -> Result<()> {
+        assert_eq!(
+            bookmark.state.fbx_version, self.state.fbx_version,
+            "The bookmark should be created for the same data"
+        );
+        self.reader.seek_to(bookmark.byte_pos)?;
+        self.state = bookmark.state.clone();
+        self.warning_counts.clear();
+        if self.state.last_event_kind != Some(EventKind::EndFbx) {
+            // The footer has not been read yet at the bookmarked position.
+            self.trailing_data = None;
+        }
+
+        Ok(())
+    }
+}
+
 impl<R: fmt::Debug> fmt::Debug for Parser<R> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Parser")
             .field("state", &self.state)
             .field("reader", &self.reader)
+            .field("config", &self.config)
+            .field(
+                "trailing_data_len",
+                &self.trailing_data.as_ref().map(Vec::len),
+            )
+            .field("warning_counts", &self.warning_counts)
             .field(
                 "warning_handler",
                 &self.warning_handler.as_ref().map(|v| v as *const _),
@@ -558,6 +1078,44 @@ impl<R: fmt::Debug> fmt::Debug for Parser<R> {
     }
 }
 
+/// Bookmark of a parser position and state.
+///
+/// This is created by [`Parser::bookmark`], and used by [`Parser::resume`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bookmark {
+    /// Parser state.
+    state: State,
+    /// Byte position of the reader.
+    byte_pos: u64,
+}
+
+impl Bookmark {
+    /// Returns the byte position of the reader at the bookmark.
+    #[inline]
+    #[must_use]
+    pub fn byte_pos(&self) -> u64 {
+        self.byte_pos
+    }
+
+    /// Returns the node depth at the bookmark.
+    ///
+    /// Implicit root node is considered to be depth 0.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.state.started_nodes.len()
+    }
+
+    /// Returns an iterator of the names of the open nodes at the bookmark,
+    /// from the top-level node.
+    pub fn node_path(&self) -> impl Iterator<Item = &str> + '_ {
+        self.state
+            .started_nodes
+            .iter()
+            .map(|node| node.name.as_str())
+    }
+}
+
 /// Health of a parser.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Health {
@@ -569,6 +1127,19 @@ enum Health {
     Aborted(SyntacticPosition),
 }
 
+/// Number of warnings of a kind.
+#[derive(Debug)]
+struct WarningCount {
+    /// Kind of the warnings.
+    kind: mem::Discriminant<Warning>,
+    /// Number of warnings passed to the warning handler.
+    reported: u64,
+    /// Number of suppressed warnings.
+    suppressed: u64,
+    /// First suppressed warning and its position.
+    first_suppressed: Option<(Warning, SyntacticPosition)>,
+}
+
 /// Parser state.
 ///
 /// This type contains parser state especially which are independent of parser
@@ -589,6 +1160,13 @@ struct State {
     ///
     /// This is here because [`StartedNode`] is not used for implicit root node.
     known_toplevel_nodes_count: usize,
+    /// Number of known nodes (except for implicit root node).
+    known_nodes_count: u64,
+    /// Number of nodes whose attributes are implicitly skipped.
+    nodes_with_unread_attributes_count: u64,
+    /// Names and byte lengths of the nodes skipped but not yet reported, in
+    /// the skipped order.
+    skipped_nodes: VecDeque<(String, u64)>,
 }
 
 impl State {
@@ -602,6 +1180,9 @@ impl State {
             started_nodes: Vec::new(),
             last_event_kind: None,
             known_toplevel_nodes_count: 0,
+            known_nodes_count: 0,
+            nodes_with_unread_attributes_count: 0,
+            skipped_nodes: VecDeque::new(),
         }
     }
 
@@ -653,6 +1234,10 @@ struct StartedNode {
     ///
     /// "End offset" means a next byte of the last byte of the last attribute.
     attributes_end_offset: u64,
+    /// Byte length of the node attributes.
+    attributes_bytelen: u64,
+    /// Number of node attributes not yet read nor explicitly finished.
+    rest_attributes_count: u64,
     /// Node name.
     name: String,
     /// Number of known children.
@@ -0,0 +1,92 @@
+//! Owned event stream.
+
+use crate::pull_parser::{
+    v7400::{AttributeLoadPolicy, OwnedEvent, Parser},
+    ParserSource, Result,
+};
+
+/// Iterator of owned parser events.
+///
+/// This is created by [`Parser::into_event_stream`].
+///
+/// The iterator yields events until [`OwnedEvent::EndFbx`] or an error, and
+/// then returns `None`.
+///
+/// With `futures-core` feature, this also implements
+/// `futures_core::Stream`, so that the parser can be used with stream
+/// combinators.
+/// Note that the parser reads data synchronously, so every poll is
+/// immediately ready.
+#[derive(Debug)]
+pub struct EventStream<R> {
+    /// Parser.
+    parser: Parser<R>,
+    /// Attribute load policy.
+    policy: AttributeLoadPolicy,
+    /// Whether the stream is finished.
+    is_finished: bool,
+}
+
+impl<R: ParserSource> EventStream<R> {
+    /// Creates a new `EventStream`.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(parser: Parser<R>) -> Self {
+        Self {
+            parser,
+            policy: AttributeLoadPolicy::default(),
+            is_finished: false,
+        }
+    }
+
+    /// Sets the policy to load node attributes.
+    ///
+    /// This is [`AttributeLoadPolicy::Load`] by default.
+    #[inline]
+    pub fn set_attribute_load_policy(&mut self, policy: AttributeLoadPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns a reference to the parser.
+    #[inline]
+    #[must_use]
+    pub fn parser(&self) -> &Parser<R> {
+        &self.parser
+    }
+
+    /// Returns the parser.
+    #[inline]
+    #[must_use]
+    pub fn into_parser(self) -> Parser<R> {
+        self.parser
+    }
+}
+
+impl<R: ParserSource> Iterator for EventStream<R> {
+    type Item = Result<OwnedEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished {
+            return None;
+        }
+        let res = self.parser.next_owned_event(self.policy);
+        self.is_finished = matches!(res, Ok(OwnedEvent::EndFbx(_)) | Err(_));
+        Some(res)
+    }
+}
+
+impl<R: ParserSource> std::iter::FusedIterator for EventStream<R> {}
+
+#[cfg(feature = "futures-core")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "futures-core")))]
+impl<R: ParserSource + Unpin> futures_core::Stream for EventStream<R> {
+    type Item = Result<OwnedEvent>;
+
+    #[inline]
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::task::Poll::Ready(self.get_mut().next())
+    }
+}
@@ -19,17 +19,14 @@ impl ParserVersion {
     /// Returns the parser version corresponding to the given FBX version.
     #[must_use]
     pub fn from_fbx_version(fbx_version: FbxVersion) -> Option<Self> {
-        let raw = fbx_version.raw();
-        match raw {
-            7000..=7999 => {
-                if raw < 7400 {
-                    info!("<FBX-7.4 might be successfully read, but unsupported");
-                } else if raw > 7500 {
-                    info!(">FBX-7.5 might be successfully read, but unsupported");
-                }
-                Some(ParserVersion::V7400)
-            }
-            _ => None,
+        if !fbx_version.is_supported_by_parser() {
+            return None;
         }
+        if fbx_version < FbxVersion::V7_4 {
+            info!("<FBX-7.4 might be successfully read, but unsupported");
+        } else if fbx_version > FbxVersion::V7_5 {
+            info!(">FBX-7.5 might be successfully read, but unsupported");
+        }
+        Some(ParserVersion::V7400)
     }
 }
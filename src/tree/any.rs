@@ -1,13 +1,17 @@
 //! Types and functions for all supported versions.
 
-use std::io::{Read, Seek};
+use std::{
+    cell::RefCell,
+    io::{Read, Seek},
+    rc::Rc,
+};
 
 use log::warn;
 
 pub use self::error::{Error, Result};
 use crate::{
     low::{self, FbxVersion},
-    pull_parser::{self, any::AnyParser},
+    pull_parser::{self, any::AnyParser, ParserSource, SyntacticPosition},
     tree,
 };
 
@@ -30,37 +34,126 @@ impl AnyTree {
     /// This works for seekable readers (which implement [`std::io::Seek`]), but
     /// [`from_seekable_reader`][`Self::from_seekable_reader`] should be used for them, because it is more
     /// efficent.
+    ///
+    /// Parser warnings are logged and ignored.
+    /// To collect them, use [`from_reader_with_report`][`Self::from_reader_with_report`].
     pub fn from_reader(reader: impl Read) -> Result<Self> {
-        match pull_parser::any::from_reader(reader)? {
-            AnyParser::V7400(mut parser) => {
-                let fbx_version = parser.fbx_version();
-                parser.set_warning_handler(|w, pos| {
-                    warn!("WARNING: {} (pos={:?})", w, pos);
-                    Ok(())
-                });
-                let tree_loader = tree::v7400::Loader::new();
-                let (tree, footer) = tree_loader.load(&mut parser)?;
-                Ok(AnyTree::V7400(fbx_version, tree, footer))
-            }
-        }
+        Self::from_reader_with_warning_handler(reader, log_warning)
     }
 
     /// Loads a tree from the given seekable reader.
+    ///
+    /// Parser warnings are logged and ignored.
+    /// To collect them, use [`from_seekable_reader_with_report`][`Self::from_seekable_reader_with_report`].
     pub fn from_seekable_reader(reader: impl Read + Seek) -> Result<Self> {
+        Self::from_seekable_reader_with_warning_handler(reader, log_warning)
+    }
+
+    /// Loads a tree from the file.
+    ///
+    /// The file is read with a buffered reader as a seekable source.
+    /// Parser warnings are logged and ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fbxcel::tree::any::AnyTree;
+    ///
+    /// let tree = AnyTree::from_path("sample.fbx")?;
+    /// println!("FBX version: {}", tree.fbx_version());
+    /// # Ok::<_, fbxcel::tree::any::Error>(())
+    /// ```
+    #[cfg(feature = "fs")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "fs")))]
+    #[inline]
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        crate::fs::Fbx::options().load_tree(path)
+    }
+
+    /// Loads a tree from the given reader, using the given warning handler.
+    ///
+    /// See [`pull_parser::v7400::Parser::set_warning_handler`] for detail of
+    /// the warning handler.
+    pub fn from_reader_with_warning_handler<F>(
+        reader: impl Read,
+        warning_handler: F,
+    ) -> Result<Self>
+    where
+        F: 'static + FnMut(pull_parser::Warning, &SyntacticPosition) -> pull_parser::Result<()>,
+    {
+        match pull_parser::any::from_reader(reader)? {
+            AnyParser::V7400(parser) => Self::load_v7400(parser, warning_handler),
+        }
+    }
+
+    /// Loads a tree from the given seekable reader, using the given warning
+    /// handler.
+    ///
+    /// See [`pull_parser::v7400::Parser::set_warning_handler`] for detail of
+    /// the warning handler.
+    pub fn from_seekable_reader_with_warning_handler<F>(
+        reader: impl Read + Seek,
+        warning_handler: F,
+    ) -> Result<Self>
+    where
+        F: 'static + FnMut(pull_parser::Warning, &SyntacticPosition) -> pull_parser::Result<()>,
+    {
         match pull_parser::any::from_seekable_reader(reader)? {
-            AnyParser::V7400(mut parser) => {
-                let fbx_version = parser.fbx_version();
-                parser.set_warning_handler(|w, pos| {
-                    warn!("WARNING: {} (pos={:?})", w, pos);
-                    Ok(())
-                });
-                let tree_loader = tree::v7400::Loader::new();
-                let (tree, footer) = tree_loader.load(&mut parser)?;
-                Ok(AnyTree::V7400(fbx_version, tree, footer))
-            }
+            AnyParser::V7400(parser) => Self::load_v7400(parser, warning_handler),
+        }
+    }
+
+    /// Loads a tree from the given reader, and returns it with the load
+    /// report.
+    ///
+    /// Parser warnings are ignored and collected into the report, instead of
+    /// being logged.
+    pub fn from_reader_with_report(reader: impl Read) -> Result<(Self, LoadReport)> {
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let tree = Self::from_reader_with_warning_handler(reader, collect_warning(&warnings))?;
+        Ok((tree, LoadReport::new(warnings.take())))
+    }
+
+    /// Loads a tree from the given seekable reader, and returns it with the
+    /// load report.
+    ///
+    /// Parser warnings are ignored and collected into the report, instead of
+    /// being logged.
+    pub fn from_seekable_reader_with_report(
+        reader: impl Read + Seek,
+    ) -> Result<(Self, LoadReport)> {
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let tree =
+            Self::from_seekable_reader_with_warning_handler(reader, collect_warning(&warnings))?;
+        Ok((tree, LoadReport::new(warnings.take())))
+    }
+
+    /// Loads a tree using the given parser.
+    ///
+    /// Parser warnings are logged and ignored.
+    /// The warning handler of the parser is replaced.
+    pub fn from_parser<R: ParserSource>(parser: AnyParser<R>) -> Result<Self> {
+        match parser {
+            AnyParser::V7400(parser) => Self::load_v7400(parser, log_warning),
         }
     }
 
+    /// Loads a tree using the given v7400 parser and the warning handler.
+    fn load_v7400<R, F>(
+        mut parser: pull_parser::v7400::Parser<R>,
+        warning_handler: F,
+    ) -> Result<Self>
+    where
+        R: ParserSource,
+        F: 'static + FnMut(pull_parser::Warning, &SyntacticPosition) -> pull_parser::Result<()>,
+    {
+        let fbx_version = parser.fbx_version();
+        // The handler may be `!Send`, so set it to the parser directly.
+        parser.set_warning_handler(warning_handler);
+        let (tree, footer) = tree::v7400::Loader::new().load(&mut parser)?;
+        Ok(AnyTree::V7400(fbx_version, tree, footer))
+    }
+
     /// Returns the FBX version of the document the tree came from.
     #[inline]
     #[must_use]
@@ -69,4 +162,73 @@ impl AnyTree {
             Self::V7400(ver, _, _) => *ver,
         }
     }
+
+    /// Returns the FBX footer of the document the tree came from.
+    ///
+    /// Returns `Err(_)` if the tree is successfully loaded but the footer is
+    /// not.
+    #[inline]
+    pub fn footer(&self) -> std::result::Result<&low::v7400::FbxFooter, &pull_parser::Error> {
+        match self {
+            Self::V7400(_, _, footer) => footer.as_deref(),
+        }
+    }
+}
+
+/// Warnings collected by a warning handler.
+type CollectedWarnings = Rc<RefCell<Vec<(pull_parser::Warning, SyntacticPosition)>>>;
+
+/// Logs the warning and ignores it.
+fn log_warning(warning: pull_parser::Warning, pos: &SyntacticPosition) -> pull_parser::Result<()> {
+    warn!("WARNING: {} (pos={:?})", warning, pos);
+    Ok(())
+}
+
+/// Returns a warning handler which collects warnings to the given vector.
+fn collect_warning(
+    warnings: &CollectedWarnings,
+) -> impl 'static + FnMut(pull_parser::Warning, &SyntacticPosition) -> pull_parser::Result<()> {
+    let warnings = warnings.clone();
+    move |warning, pos| {
+        warnings.borrow_mut().push((warning, pos.clone()));
+        Ok(())
+    }
+}
+
+/// Report of tree loading.
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// Parser warnings and their positions.
+    warnings: Vec<(pull_parser::Warning, SyntacticPosition)>,
+}
+
+impl LoadReport {
+    /// Creates a new `LoadReport`.
+    #[inline]
+    #[must_use]
+    fn new(warnings: Vec<(pull_parser::Warning, SyntacticPosition)>) -> Self {
+        Self { warnings }
+    }
+
+    /// Returns the parser warnings and their positions, in the order they
+    /// are emitted.
+    #[inline]
+    #[must_use]
+    pub fn warnings(&self) -> &[(pull_parser::Warning, SyntacticPosition)] {
+        &self.warnings
+    }
+
+    /// Returns `true` if the parser emitted some warnings.
+    #[inline]
+    #[must_use]
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Returns the parser warnings and their positions.
+    #[inline]
+    #[must_use]
+    pub fn into_warnings(self) -> Vec<(pull_parser::Warning, SyntacticPosition)> {
+        self.warnings
+    }
 }
@@ -2,7 +2,7 @@
 
 use std::{error, fmt};
 
-use crate::{pull_parser, tree};
+use crate::{pull_parser, tree, ErrorCode};
 
 /// AnyTree load result.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -19,6 +19,20 @@ pub enum Error {
     Tree(Box<dyn error::Error + Send + Sync + 'static>),
 }
 
+impl Error {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::ParserCreation(e) => e.code(),
+            Error::Parser(e) => e.code(),
+            Error::Tree(e) => e
+                .downcast_ref::<tree::v7400::LoadError>()
+                .map_or(ErrorCode::Other, tree::v7400::LoadError::code),
+        }
+    }
+}
+
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
@@ -1,35 +1,46 @@
 //! FBX data tree for v7.4 or later.
 
-use std::fmt;
+use std::{fmt, io, sync::Arc};
 
 use indextree::Arena;
 use string_interner::{DefaultBackend, StringInterner};
 
-use crate::low::v7400::AttributeValue;
+use crate::low::v7400::{ArrayAttributeEncoding, AttributeValue};
 
 use self::node::{NodeData, NodeNameSym};
 pub use self::{
-    error::LoadError,
+    definitions::DefinitionsCountMismatch,
+    error::{AttributeError, LoadError},
     loader::Loader,
     node::{
         handle::{Children, ChildrenByName, NodeHandle},
         NodeId,
     },
+    search::{FindNodes, NameIndex},
+    visit::{VisitFlow, Visitor},
 };
 
 mod macros;
 
+mod cache;
+mod definitions;
 mod error;
 mod loader;
 mod node;
+mod search;
+mod visit;
 
 /// FBX data tree.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tree {
     /// Tree data.
-    arena: Arena<NodeData>,
+    ///
+    /// This is shared among snapshots, and copied on write.
+    arena: Arc<Arena<NodeData>>,
     /// Node name interner.
-    node_names: StringInterner<DefaultBackend<NodeNameSym>>,
+    ///
+    /// This is shared among snapshots, and copied on write.
+    node_names: Arc<StringInterner<DefaultBackend<NodeNameSym>>>,
     /// (Implicit) root node ID.
     root_id: NodeId,
 }
@@ -42,6 +53,207 @@ impl Tree {
         NodeHandle::new(self, self.root_id)
     }
 
+    /// Creates a snapshot of the tree.
+    ///
+    /// This does not copy the tree data.
+    /// The tree data is shared between the tree and the snapshot, and copied
+    /// when either of them is modified for the first time.
+    /// Even then, node attributes are still shared, and copied on write per
+    /// node.
+    /// This is useful to keep undo history of large documents, because the
+    /// attribute data (such as vertices) of unmodified nodes is not
+    /// duplicated.
+    ///
+    /// Note that the copy on the first modification is not partial: the
+    /// structure of all nodes (names, links, and pointers to the attributes)
+    /// and the node name table are copied as a whole, even if only one node
+    /// is modified.
+    /// The cost is proportional to the number of nodes, so taking a snapshot
+    /// before every small edit of a tree with many nodes is still expensive.
+    ///
+    /// Note that `Clone` also shares the data in the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{low::v7400::AttributeValue, tree_v7400};
+    /// let mut tree = tree_v7400! {
+    ///     Node0: [vec![0.0f64; 1024]] {}
+    ///     Node1: [42i32] {}
+    /// };
+    /// let snapshot = tree.snapshot();
+    ///
+    /// let node1 = tree.root().last_child().expect("Should exist").node_id();
+    /// *tree.get_attribute_mut(node1, 0).expect("Should exist") = AttributeValue::I32(0);
+    ///
+    /// let node1 = snapshot.root().last_child().expect("Should exist");
+    /// assert_eq!(node1.attributes(), [AttributeValue::I32(42)]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Returns an iterator of depth-first traversal events of all nodes
+    /// except for the implicit root node.
+    ///
+    /// Top-level nodes have depth 1, since the implicit root node is
+    /// considered to be depth 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let tree = tree_v7400! {
+    ///     Node0: {
+    ///         Node0_0: {}
+    ///     }
+    ///     Node1: {}
+    /// };
+    ///
+    /// let opened = tree
+    ///     .traverse()
+    ///     .filter(|ev| ev.is_open())
+    ///     .map(|ev| (ev.node().name(), ev.depth()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(opened, [("Node0", 1), ("Node0_0", 2), ("Node1", 1)]);
+    /// ```
+    #[must_use]
+    pub fn traverse(&self) -> Traverse<'_> {
+        let mut inner = self.root_id.traverse_depth_first();
+        // Skip the implicit root node.
+        inner.next_forward(self);
+        inner.next_backward(self);
+        Traverse {
+            tree: self,
+            inner,
+            depth: 1,
+        }
+    }
+
+    /// Visits the nodes in depth-first order with the given visitor.
+    ///
+    /// The visitor can skip the descendants of a node by returning
+    /// [`VisitFlow::Prune`], or stop the traversal by returning
+    /// [`VisitFlow::Stop`].
+    /// The implicit root node is not visited.
+    ///
+    /// Returns `VisitFlow::Stop` if the traversal is stopped by the visitor,
+    /// and `VisitFlow::Continue` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// use fbxcel::tree::v7400::{NodeHandle, VisitFlow};
+    ///
+    /// let tree = tree_v7400! {
+    ///     Objects: {
+    ///         Geometry: [1i64] {
+    ///             Vertices: [vec![0.0f64; 9]] {}
+    ///         }
+    ///         Model: [2i64] {}
+    ///         Model: [3i64] {}
+    ///     }
+    ///     Connections: {}
+    /// };
+    ///
+    /// // Find the first model without looking into geometries.
+    /// let mut visited = Vec::new();
+    /// let mut found = None;
+    /// let flow = tree.visit(&mut |node: NodeHandle<'_>, _depth| {
+    ///     visited.push(node.name().to_owned());
+    ///     match node.name() {
+    ///         "Geometry" => VisitFlow::Prune,
+    ///         "Model" => {
+    ///             found = Some(node.node_id());
+    ///             VisitFlow::Stop
+    ///         }
+    ///         _ => VisitFlow::Continue,
+    ///     }
+    /// });
+    /// assert_eq!(flow, VisitFlow::Stop);
+    /// assert_eq!(visited, ["Objects", "Geometry", "Model"]);
+    /// assert!(found.is_some());
+    /// ```
+    pub fn visit<'a, V>(&'a self, visitor: &mut V) -> VisitFlow
+    where
+        V: Visitor<'a> + ?Sized,
+    {
+        visit::visit(self, visitor)
+    }
+
+    /// Returns an iterator of nodes matching the predicate, in depth-first
+    /// order.
+    ///
+    /// The predicate receives the node name and the node attributes.
+    /// The implicit root node is not visited.
+    ///
+    /// To search nodes by name repeatedly, consider using
+    /// [`name_index`][`Self::name_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let tree = tree_v7400! {
+    ///     Objects: {
+    ///         Model: [1i64, "Cube\u{0}\u{1}Model", "Mesh"] {}
+    ///         Model: [2i64, "Light\u{0}\u{1}Model", "Light"] {}
+    ///     }
+    /// };
+    ///
+    /// let lights = tree
+    ///     .find_nodes(|name, attrs| {
+    ///         name == "Model" && attrs.get(2).and_then(|v| v.get_string()) == Some("Light")
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(lights.len(), 1);
+    /// assert_eq!(lights[0].attributes()[0].get_i64(), Some(2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn find_nodes<P>(&self, predicate: P) -> FindNodes<'_, P>
+    where
+        P: FnMut(&str, &[AttributeValue]) -> bool,
+    {
+        FindNodes::new(self.traverse(), predicate)
+    }
+
+    /// Creates an index of all nodes by name.
+    ///
+    /// Building the index visits all nodes once, and subsequent lookups by
+    /// name are fast.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let tree = tree_v7400! {
+    ///     Objects: {
+    ///         Model: [1i64] {}
+    ///         Geometry: [2i64] {}
+    ///         Model: [3i64] {}
+    ///     }
+    /// };
+    ///
+    /// let index = tree.name_index();
+    /// assert_eq!(index.node_ids("Model").len(), 2);
+    /// assert_eq!(index.nodes("Geometry").count(), 1);
+    /// assert_eq!(
+    ///     index
+    ///         .find_nodes("Model", |attrs| attrs[0].get_i64() == Some(3))
+    ///         .count(),
+    ///     1
+    /// );
+    /// assert!(index.node_ids("Connections").is_empty());
+    /// ```
+    #[must_use]
+    pub fn name_index(&self) -> NameIndex<'_> {
+        NameIndex::new(self)
+    }
+
     /// Creates a new `Tree`.
     #[inline]
     #[must_use]
@@ -51,12 +263,28 @@ impl Tree {
         root_id: NodeId,
     ) -> Self {
         Self {
-            arena,
-            node_names,
+            arena: Arc::new(arena),
+            node_names: Arc::new(node_names),
             root_id,
         }
     }
 
+    /// Returns the mutable reference to the tree data.
+    ///
+    /// The data is copied if it is shared with snapshots.
+    #[inline]
+    fn arena_mut(&mut self) -> &mut Arena<NodeData> {
+        Arc::make_mut(&mut self.arena)
+    }
+
+    /// Returns the mutable reference to the node name interner.
+    ///
+    /// The interner is copied if it is shared with snapshots.
+    #[inline]
+    fn node_names_mut(&mut self) -> &mut StringInterner<DefaultBackend<NodeNameSym>> {
+        Arc::make_mut(&mut self.node_names)
+    }
+
     /// Returns internally managed node data.
     ///
     /// # Panics
@@ -102,9 +330,11 @@ impl Tree {
     ///
     /// Panics if the given node ID is not used in the tree.
     pub fn append_new(&mut self, parent: NodeId, name: &str) -> NodeId {
-        let name_sym = self.node_names.get_or_intern(name);
-        let new_child = self.arena.new_node(NodeData::new(name_sym, Vec::new()));
-        parent.raw().append(new_child, &mut self.arena);
+        let name_sym = self.node_names_mut().get_or_intern(name);
+        let new_child = self
+            .arena_mut()
+            .new_node(NodeData::new(name_sym, Vec::new()));
+        parent.raw().append(new_child, self.arena_mut());
 
         NodeId::new(new_child)
     }
@@ -115,9 +345,11 @@ impl Tree {
     ///
     /// Panics if the given node ID is not used in the tree.
     pub fn prepend_new(&mut self, parent: NodeId, name: &str) -> NodeId {
-        let name_sym = self.node_names.get_or_intern(name);
-        let new_child = self.arena.new_node(NodeData::new(name_sym, Vec::new()));
-        parent.raw().prepend(new_child, &mut self.arena);
+        let name_sym = self.node_names_mut().get_or_intern(name);
+        let new_child = self
+            .arena_mut()
+            .new_node(NodeData::new(name_sym, Vec::new()));
+        parent.raw().prepend(new_child, self.arena_mut());
 
         NodeId::new(new_child)
     }
@@ -129,9 +361,11 @@ impl Tree {
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     pub fn insert_new_after(&mut self, sibling: NodeId, name: &str) -> NodeId {
         assert_ne!(sibling, self.root_id, "Root node should have no siblings");
-        let name_sym = self.node_names.get_or_intern(name);
-        let new_child = self.arena.new_node(NodeData::new(name_sym, Vec::new()));
-        sibling.raw().insert_after(new_child, &mut self.arena);
+        let name_sym = self.node_names_mut().get_or_intern(name);
+        let new_child = self
+            .arena_mut()
+            .new_node(NodeData::new(name_sym, Vec::new()));
+        sibling.raw().insert_after(new_child, self.arena_mut());
 
         NodeId::new(new_child)
     }
@@ -143,9 +377,11 @@ impl Tree {
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     pub fn insert_new_before(&mut self, sibling: NodeId, name: &str) -> NodeId {
         assert_ne!(sibling, self.root_id, "Root node should have no siblings");
-        let name_sym = self.node_names.get_or_intern(name);
-        let new_child = self.arena.new_node(NodeData::new(name_sym, Vec::new()));
-        sibling.raw().insert_before(new_child, &mut self.arena);
+        let name_sym = self.node_names_mut().get_or_intern(name);
+        let new_child = self
+            .arena_mut()
+            .new_node(NodeData::new(name_sym, Vec::new()));
+        sibling.raw().insert_before(new_child, self.arena_mut());
 
         NodeId::new(new_child)
     }
@@ -166,7 +402,10 @@ impl Tree {
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     fn append_attribute_impl(&mut self, node_id: NodeId, v: AttributeValue) {
         assert_ne!(node_id, self.root_id, "Root node should have no attributes");
-        let node = self.arena.get_mut(node_id.raw()).expect("Invalid node ID");
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
         node.get_mut().append_attribute(v)
     }
 
@@ -177,35 +416,389 @@ impl Tree {
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     #[must_use]
     pub fn get_attribute_mut(&mut self, node_id: NodeId, i: usize) -> Option<&mut AttributeValue> {
-        let node = self.arena.get_mut(node_id.raw()).expect("Invalid node ID");
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
         node.get_mut().get_attribute_mut(i)
     }
 
+    /// Replaces the node attribute at the given index with the given value,
+    /// and returns the old value.
+    ///
+    /// The new value should have the same type as the old one, and the array
+    /// encoding hint is kept.
+    /// To append a new attribute, use [`append_attribute`][`Self::append_attribute`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AttributeError::IndexOutOfRange`] if the node has no attribute
+    /// at the index, and [`AttributeError::TypeMismatch`] if the type of the
+    /// new value differs.
+    /// The node is not modified on errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given node ID is invalid (i.e. not used).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::{
+    ///     low::v7400::{AttributeType, AttributeValue},
+    ///     tree::v7400::AttributeError,
+    ///     tree_v7400,
+    /// };
+    ///
+    /// let mut tree = tree_v7400! {
+    ///     Node: [42i64, "name"] {}
+    /// };
+    /// let node = tree.root().first_child().expect("Should exist").node_id();
+    ///
+    /// assert_eq!(tree.set_attribute(node, 0, 43i64), Ok(AttributeValue::I64(42)));
+    /// assert_eq!(
+    ///     tree.set_attribute(node, 0, 44i32),
+    ///     Err(AttributeError::TypeMismatch(AttributeType::I64, AttributeType::I32))
+    /// );
+    /// assert_eq!(
+    ///     tree.set_attribute(node, 2, "extra"),
+    ///     Err(AttributeError::IndexOutOfRange(2, 2))
+    /// );
+    /// ```
+    pub fn set_attribute(
+        &mut self,
+        node_id: NodeId,
+        i: usize,
+        v: impl Into<AttributeValue>,
+    ) -> Result<AttributeValue, AttributeError> {
+        let v = v.into();
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
+        let data = node.get_mut();
+        let attrs = data.attributes();
+        let old_type = attrs
+            .get(i)
+            .ok_or(AttributeError::IndexOutOfRange(i, attrs.len()))?
+            .type_();
+        if old_type != v.type_() {
+            return Err(AttributeError::TypeMismatch(old_type, v.type_()));
+        }
+        let old = data
+            .get_attribute_mut(i)
+            .expect("Index should have been checked");
+
+        Ok(std::mem::replace(old, v))
+    }
+
+    /// Sets the array encoding hint of the node attribute at the given index.
+    ///
+    /// See [`NodeHandle::array_encoding`] for detail.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given node ID is invalid (i.e. not used or root node).
+    pub fn set_array_encoding(
+        &mut self,
+        node_id: NodeId,
+        i: usize,
+        encoding: Option<ArrayAttributeEncoding>,
+    ) {
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
+        node.get_mut().set_array_encoding(i, encoding)
+    }
+
     /// Takes all attributes as a `Vec`.
     ///
     /// After calling this, the node will have no attributes (until other values are set).
+    /// Array encoding hints are also cleared.
     ///
     /// # Panics
     ///
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     pub fn take_attributes_vec(&mut self, node_id: NodeId) -> Vec<AttributeValue> {
-        let node = self.arena.get_mut(node_id.raw()).expect("Invalid node ID");
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
         node.get_mut().replace_attributes(Default::default())
     }
 
     /// Sets the given `Vec` of attribute values as the node attributes.
     ///
     /// After calling this, the node will have only the given attributes.
+    /// Array encoding hints are cleared.
     ///
     /// # Panics
     ///
     /// Panics if the given node ID is invalid (i.e. not used or root node).
     pub fn set_attributes_vec(&mut self, node_id: NodeId, new: Vec<AttributeValue>) {
-        let node = self.arena.get_mut(node_id.raw()).expect("Invalid node ID");
+        let node = self
+            .arena_mut()
+            .get_mut(node_id.raw())
+            .expect("Invalid node ID");
         // Ignore the returned value.
         node.get_mut().replace_attributes(new);
     }
 
+    /// Removes the given node and its descendants from the tree.
+    ///
+    /// Node IDs of the removed nodes become invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given node ID is invalid (i.e. not used or root node).
+    pub fn remove_subtree(&mut self, node_id: NodeId) {
+        assert_ne!(node_id, self.root_id, "Root node cannot be removed");
+        assert!(self.contains_node(node_id), "Invalid node ID");
+        node_id.raw().remove_subtree(self.arena_mut());
+    }
+
+    /// Copies the given node and its descendants (possibly in another tree),
+    /// and appends the copy to the given parent node.
+    ///
+    /// Node names are re-interned and new node IDs are assigned to the copied
+    /// nodes.
+    /// Returns the node ID of the copied node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given parent node ID is not used in the tree, or the
+    /// source node is a root node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let src = tree_v7400! {
+    ///     Objects: {
+    ///         Model: [2i64, "Cube\u{0}\u{1}Model", "Mesh"] {}
+    ///     }
+    /// };
+    /// let mut merged = tree_v7400! {
+    ///     Objects: {
+    ///         Model: [1i64, "Sphere\u{0}\u{1}Model", "Mesh"] {}
+    ///     }
+    /// };
+    ///
+    /// let src_objects = src.root().first_child_by_name("Objects").unwrap();
+    /// let merged_objects = merged.root().first_child_by_name("Objects").unwrap().node_id();
+    /// for object in src_objects.children() {
+    ///     merged.append_subtree_copy(merged_objects, object);
+    /// }
+    ///
+    /// assert_eq!(merged_objects.to_handle(&merged).children().count(), 2);
+    /// ```
+    pub fn append_subtree_copy(&mut self, parent: NodeId, source: NodeHandle<'_>) -> NodeId {
+        assert!(
+            source.parent().is_some(),
+            "Root node cannot be copied as a subtree"
+        );
+        let source_tree = source.tree();
+        let mut traverse = source.node_id().traverse_depth_first();
+        // Stack of the destination parents.
+        let mut parents = vec![parent];
+        let mut copied_root = None;
+        while let Some(event) = traverse.next_forward(source_tree) {
+            match event {
+                DepthFirstTraversed::Open(source_id) => {
+                    let source_node = source_id.to_handle(source_tree);
+                    let dest_parent = *parents
+                        .last()
+                        .expect("Should never fail: the parent should be pushed on open");
+                    let new_id = self.append_new(dest_parent, source_node.name());
+                    self.set_attributes_vec(new_id, source_node.attributes().to_vec());
+                    let array_encodings = source_node.node().get().array_encodings().to_vec();
+                    self.arena_mut()
+                        .get_mut(new_id.raw())
+                        .expect("Should never fail: the node is just created")
+                        .get_mut()
+                        .set_array_encodings(array_encodings);
+                    copied_root.get_or_insert(new_id);
+                    parents.push(new_id);
+                }
+                DepthFirstTraversed::Close(_) => {
+                    parents.pop();
+                }
+            }
+        }
+
+        copied_root.expect("Should never fail: the source node itself should be copied")
+    }
+
+    /// Moves the given node and its descendants from another tree, and
+    /// appends them to the given parent node.
+    ///
+    /// This is same as [`append_subtree_copy`][`Self::append_subtree_copy`]
+    /// followed by [`remove_subtree`][`Self::remove_subtree`] on the source
+    /// tree.
+    /// Returns the node ID of the moved node in this tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given parent node ID is not used in the tree, or the
+    /// given source node ID is invalid (i.e. not used or root node) in the
+    /// source tree.
+    pub fn append_subtree_moved(
+        &mut self,
+        parent: NodeId,
+        source_tree: &mut Tree,
+        source: NodeId,
+    ) -> NodeId {
+        let new_id = self.append_subtree_copy(parent, source.to_handle(source_tree));
+        source_tree.remove_subtree(source);
+
+        new_id
+    }
+
+    /// Updates the counts in `Definitions` node to match the objects.
+    ///
+    /// Children of `Objects` node are counted by node name, and the
+    /// top-level `GlobalSettings` node is counted as an object.
+    /// The `Count` child of each `ObjectType` node and the total `Count` of
+    /// `Definitions` node are rewritten, and `ObjectType` nodes are appended
+    /// for object types without definitions.
+    /// `ObjectType` nodes without corresponding objects are kept with count
+    /// 0, since they might have property templates.
+    ///
+    /// If there is no `Definitions` node, it is created before `Objects`
+    /// node.
+    ///
+    /// Some importers misbehave on mismatched counts, so call this before
+    /// writing a modified or generated tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let mut tree = tree_v7400! {
+    ///     GlobalSettings: {}
+    ///     Definitions: {
+    ///         Version: [100i32] {}
+    ///         Count: [1i32] {}
+    ///         ObjectType: ["Model"] {
+    ///             Count: [1i32] {}
+    ///         }
+    ///     }
+    ///     Objects: {
+    ///         Model: [1i64] {}
+    ///         Model: [2i64] {}
+    ///         Geometry: [3i64] {}
+    ///     }
+    /// };
+    /// tree.update_definitions_counts();
+    ///
+    /// let definitions = tree.root().first_child_by_name("Definitions").unwrap();
+    /// let count = |node: fbxcel::tree::v7400::NodeHandle<'_>| {
+    ///     node.first_child_by_name("Count").unwrap().attributes()[0].get_i32()
+    /// };
+    /// assert_eq!(count(definitions), Some(4));
+    /// let counts = definitions
+    ///     .children_by_name("ObjectType")
+    ///     .map(|ty| (ty.attributes()[0].get_string().unwrap(), count(ty).unwrap()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(counts, [("Model", 2), ("GlobalSettings", 1), ("Geometry", 1)]);
+    /// ```
+    pub fn update_definitions_counts(&mut self) {
+        definitions::update_counts(self);
+    }
+
+    /// Returns the mismatches between the counts in `Definitions` node and
+    /// the objects in the tree.
+    ///
+    /// Objects are counted in the same way as
+    /// [`update_definitions_counts`][`Self::update_definitions_counts`].
+    /// An empty result means the counts are consistent.
+    /// This is useful to detect half-written or inconsistently modified
+    /// files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let mut tree = tree_v7400! {
+    ///     Definitions: {
+    ///         Count: [2i32] {}
+    ///         ObjectType: ["Model"] {
+    ///             Count: [2i32] {}
+    ///         }
+    ///     }
+    ///     Objects: {
+    ///         Model: [1i64] {}
+    ///     }
+    /// };
+    ///
+    /// let mismatches = tree.definitions_count_mismatches();
+    /// assert_eq!(mismatches.len(), 2);
+    /// assert_eq!(mismatches[0].object_type(), Some("Model"));
+    /// assert_eq!(mismatches[0].declared(), Some(2));
+    /// assert_eq!(mismatches[0].actual(), 1);
+    /// // The total count.
+    /// assert_eq!(mismatches[1].object_type(), None);
+    ///
+    /// tree.update_definitions_counts();
+    /// assert!(tree.definitions_count_mismatches().is_empty());
+    /// ```
+    #[must_use]
+    pub fn definitions_count_mismatches(&self) -> Vec<DefinitionsCountMismatch> {
+        definitions::count_mismatches(self)
+    }
+
+    /// Writes the tree to the given writer in the binary cache format.
+    ///
+    /// The cache format is a simple dump of the decoded tree, and loading it
+    /// by [`load_cache`][`Self::load_cache`] is much faster than parsing FBX
+    /// data, since no decompression or validation is required.
+    /// This is useful for asset caches of editors.
+    ///
+    /// The format is private to this crate, and might change in future
+    /// versions.
+    /// Caches written by other versions of this crate are rejected by
+    /// `load_cache`, so fall back to parsing the original FBX data in such
+    /// case.
+    ///
+    /// Array encoding hints are preserved.
+    ///
+    /// The writer is buffered internally, so it is not necessary to wrap it
+    /// with [`io::BufWriter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{tree::v7400::Tree, tree_v7400};
+    /// let tree = tree_v7400! {
+    ///     Node0: [1i32, "Hello"] {
+    ///         Node0_0: [vec![1.0f64, 2.0]] {}
+    ///     }
+    /// };
+    ///
+    /// let mut cache = Vec::new();
+    /// tree.save_cache(&mut cache)?;
+    /// let loaded = Tree::load_cache(&cache[..])?;
+    /// assert!(loaded.strict_eq(&tree));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn save_cache(&self, writer: impl io::Write) -> io::Result<()> {
+        cache::save(self, writer)
+    }
+
+    /// Reads a tree written by [`save_cache`][`Self::save_cache`] from the
+    /// given reader.
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] error if the data is not a
+    /// cache, or is written by another version of this crate.
+    ///
+    /// The reader is buffered internally, so it is not necessary to wrap it
+    /// with [`io::BufReader`].
+    /// Note that the reader might be read beyond the end of the cache.
+    pub fn load_cache(reader: impl io::Read) -> io::Result<Self> {
+        cache::load(reader)
+    }
+
     /// Compares trees strictly.
     ///
     /// Returns `true` if the two trees are same.
@@ -237,11 +830,7 @@ impl Default for Tree {
         let root_id =
             NodeId::new(arena.new_node(NodeData::new(node_names.get_or_intern(""), Vec::new())));
 
-        Self {
-            arena,
-            node_names,
-            root_id,
-        }
+        Self::new(arena, node_names, root_id)
     }
 }
 
@@ -528,3 +1117,303 @@ impl DepthFirstTraverseSubtree {
         }
     }
 }
+
+/// Iterator of depth-first traversal events with depth info.
+///
+/// This is created by [`Tree::traverse`].
+#[derive(Debug, Clone, Copy)]
+pub struct Traverse<'a> {
+    /// Tree.
+    tree: &'a Tree,
+    /// Traversal cursors.
+    inner: DepthFirstTraverseSubtree,
+    /// Depth of the node to be opened next.
+    depth: usize,
+}
+
+impl<'a> Iterator for Traverse<'a> {
+    type Item = TraverseEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.inner.next_forward(self.tree)?;
+        let depth = match event {
+            DepthFirstTraversed::Open(_) => {
+                self.depth += 1;
+                self.depth - 1
+            }
+            DepthFirstTraversed::Close(_) => {
+                self.depth -= 1;
+                self.depth
+            }
+        };
+        Some(TraverseEvent {
+            event,
+            node: event.node_id().to_handle(self.tree),
+            depth,
+        })
+    }
+}
+
+impl std::iter::FusedIterator for Traverse<'_> {}
+
+/// Event of depth-first traversal with the node handle and the depth.
+#[derive(Debug, Clone, Copy)]
+pub struct TraverseEvent<'a> {
+    /// Traversal event.
+    event: DepthFirstTraversed,
+    /// Node.
+    node: NodeHandle<'a>,
+    /// Depth of the node.
+    depth: usize,
+}
+
+impl<'a> TraverseEvent<'a> {
+    /// Returns the traversal event.
+    #[inline]
+    #[must_use]
+    pub fn event(&self) -> DepthFirstTraversed {
+        self.event
+    }
+
+    /// Returns the node handle.
+    #[inline]
+    #[must_use]
+    pub fn node(&self) -> NodeHandle<'a> {
+        self.node
+    }
+
+    /// Returns the depth of the node.
+    ///
+    /// The implicit root node is considered to be depth 0.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns true if the event is node open.
+    #[inline]
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.event.is_open()
+    }
+
+    /// Returns true if the event is node close.
+    #[inline]
+    #[must_use]
+    pub fn is_close(&self) -> bool {
+        self.event.is_close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree_v7400;
+
+    use super::*;
+
+    #[test]
+    fn set_attribute_keeps_encoding_and_snapshots() {
+        let mut tree = tree_v7400! {
+            Node0: [vec![1.0f64, 2.0]] {}
+        };
+        let node0 = tree.root().first_child().expect("Should exist").node_id();
+        tree.set_array_encoding(node0, 0, Some(ArrayAttributeEncoding::Zlib));
+        let snapshot = tree.snapshot();
+
+        assert_eq!(
+            tree.set_attribute(node0, 0, vec![3.0f64]),
+            Ok(AttributeValue::ArrF64(vec![1.0, 2.0]))
+        );
+        let node0 = node0.to_handle(&tree);
+        assert_eq!(node0.attributes(), [AttributeValue::ArrF64(vec![3.0])]);
+        assert_eq!(node0.array_encoding(0), Some(ArrayAttributeEncoding::Zlib));
+        let old = snapshot.root().first_child().expect("Should exist");
+        assert_eq!(old.attributes(), [AttributeValue::ArrF64(vec![1.0, 2.0])]);
+    }
+
+    #[test]
+    fn snapshot_shares_data_until_modified() {
+        let mut tree = tree_v7400! {
+            Node0: [0i32] {}
+        };
+        let snapshot = tree.snapshot();
+        assert!(Arc::ptr_eq(&tree.arena, &snapshot.arena));
+        assert!(Arc::ptr_eq(&tree.node_names, &snapshot.node_names));
+
+        let root = tree.root().node_id();
+        tree.append_new(root, "Node1");
+        assert!(!Arc::ptr_eq(&tree.arena, &snapshot.arena));
+        assert!(!Arc::ptr_eq(&tree.node_names, &snapshot.node_names));
+        assert_eq!(tree.root().children().count(), 2);
+        assert_eq!(snapshot.root().children().count(), 1);
+    }
+
+    #[test]
+    fn children_by_name_after_modification() {
+        let mut tree = tree_v7400! {
+            Node0: [0i32] {}
+            Node1: {}
+            Node0: [1i32] {}
+        };
+        let root = tree.root().node_id();
+        let first_attr = |tree: &Tree| {
+            tree.root()
+                .children_by_name("Node0")
+                .map(|node| node.attributes()[0].get_i32().expect("Should be `i32`"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(first_attr(&tree), [0, 1]);
+        assert_eq!(tree.root().children_by_name("Node0").count(), 2);
+        assert_eq!(tree.root().children_by_name("Node2").count(), 0);
+
+        let new = tree.prepend_new(root, "Node0");
+        tree.append_attribute(new, -1i32);
+        let node1 = tree
+            .root()
+            .first_child_by_name("Node1")
+            .expect("Should exist")
+            .node_id();
+        let new = tree.insert_new_before(node1, "Node0");
+        tree.append_attribute(new, 2i32);
+        assert_eq!(first_attr(&tree), [-1, 0, 2, 1]);
+
+        let removed = tree
+            .root()
+            .first_child_by_name("Node0")
+            .expect("Should exist")
+            .node_id();
+        tree.remove_subtree(removed);
+        assert_eq!(first_attr(&tree), [0, 2, 1]);
+
+        let index = tree.root().children_name_index();
+        assert_eq!(
+            index
+                .nodes("Node0")
+                .map(|node| node.node_id())
+                .collect::<Vec<_>>(),
+            tree.root()
+                .children_by_name("Node0")
+                .map(|node| node.node_id())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            index.first_node("Node1").map(|node| node.node_id()),
+            Some(node1)
+        );
+        assert!(index.node_ids("Node2").is_empty());
+    }
+
+    #[test]
+    fn visit_enter_and_leave() {
+        /// Visitor recording the events.
+        struct Recorder(Vec<String>);
+
+        impl<'a> Visitor<'a> for Recorder {
+            fn enter(&mut self, node: NodeHandle<'a>, depth: usize) -> VisitFlow {
+                self.0.push(format!("+{}@{}", node.name(), depth));
+                match node.name() {
+                    "Pruned" => VisitFlow::Prune,
+                    "Stop" => VisitFlow::Stop,
+                    _ => VisitFlow::Continue,
+                }
+            }
+
+            fn leave(&mut self, node: NodeHandle<'a>, depth: usize) -> VisitFlow {
+                self.0.push(format!("-{}@{}", node.name(), depth));
+                VisitFlow::Continue
+            }
+        }
+
+        let tree = tree_v7400! {
+            Node0: {
+                Pruned: {
+                    Hidden: {}
+                }
+                Node0_1: {
+                    Node0_1_0: {}
+                }
+            }
+            Node1: {}
+        };
+        let mut recorder = Recorder(Vec::new());
+        assert_eq!(tree.visit(&mut recorder), VisitFlow::Continue);
+        assert_eq!(
+            recorder.0,
+            [
+                "+Node0@1",
+                "+Pruned@2",
+                "-Pruned@2",
+                "+Node0_1@2",
+                "+Node0_1_0@3",
+                "-Node0_1_0@3",
+                "-Node0_1@2",
+                "-Node0@1",
+                "+Node1@1",
+                "-Node1@1",
+            ]
+        );
+
+        let tree = tree_v7400! {
+            Node0: {
+                Stop: {}
+            }
+            Node1: {}
+        };
+        let mut recorder = Recorder(Vec::new());
+        assert_eq!(tree.visit(&mut recorder), VisitFlow::Stop);
+        assert_eq!(recorder.0, ["+Node0@1", "+Stop@2"]);
+
+        assert_eq!(
+            Tree::default().visit(&mut |_: NodeHandle<'_>, _| VisitFlow::Stop),
+            VisitFlow::Continue
+        );
+    }
+
+    #[test]
+    fn update_definitions_counts_creates_definitions() {
+        let mut tree = tree_v7400! {
+            Objects: {
+                Model: [1i64] {}
+                Material: [2i64] {}
+                Model: [3i64] {}
+            }
+        };
+        let mismatches = tree.definitions_count_mismatches();
+        assert_eq!(
+            mismatches
+                .iter()
+                .map(|v| (v.object_type(), v.declared(), v.actual()))
+                .collect::<Vec<_>>(),
+            [
+                (Some("Model"), None, 2),
+                (Some("Material"), None, 1),
+                (None, None, 3)
+            ]
+        );
+
+        tree.update_definitions_counts();
+        assert!(tree.definitions_count_mismatches().is_empty());
+        // Idempotent.
+        tree.update_definitions_counts();
+
+        let expected = tree_v7400! {
+            Definitions: {
+                Version: [100i32] {}
+                Count: [3i32] {}
+                ObjectType: ["Model"] {
+                    Count: [2i32] {}
+                }
+                ObjectType: ["Material"] {
+                    Count: [1i32] {}
+                }
+            }
+            Objects: {
+                Model: [1i64] {}
+                Material: [2i64] {}
+                Model: [3i64] {}
+            }
+        };
+        assert!(tree.strict_eq(&expected), "tree={:?}", tree.debug_tree());
+    }
+}
@@ -0,0 +1,306 @@
+//! Binary cache of trees.
+//!
+//! The format is private to this crate, and might change between versions.
+//! All integers are little-endian.
+//!
+//! * Magic (`MAGIC`) and format version (`u32`).
+//! * Version of the crate which wrote the cache (`u64` length and UTF-8
+//!   bytes).
+//! * Number of the top-level nodes (`u64`).
+//! * Nodes (except for the implicit root) in depth-first pre-order.
+//!   Each node is:
+//!     + the node name (`u64` length and UTF-8 bytes),
+//!     + the number of attributes (`u64`),
+//!     + the attributes, each of which is the type code (same as FBX
+//!       binary), the array encoding hint (`u8`), and the value,
+//!     + the number of children (`u64`).
+//!
+//! Single values are written as is, arrays as the number of elements (`u64`)
+//! and the elements, and binaries and strings as the byte length (`u64`) and
+//! the bytes.
+
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    low::v7400::{ArrayAttributeEncoding, AttributeType, AttributeValue},
+    tree::v7400::{NodeHandle, NodeId, Tree},
+};
+
+/// Magic of the cache.
+const MAGIC: &[u8; 12] = b"fbxcel-tree\0";
+
+/// Format version of the cache.
+const FORMAT_VERSION: u32 = 1;
+
+/// Version of this crate.
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Writes the tree to the given writer in the cache format.
+///
+/// See [`Tree::save_cache`].
+pub(crate) fn save(tree: &Tree, writer: impl Write) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(MAGIC)?;
+    writer.write_u32::<LittleEndian>(FORMAT_VERSION)?;
+    write_bytes(&mut writer, CRATE_VERSION.as_bytes())?;
+    write_len(&mut writer, tree.root().children().count())?;
+    for ev in tree.traverse().filter(|ev| ev.is_open()) {
+        write_node(&mut writer, ev.node())?;
+    }
+
+    writer.flush()
+}
+
+/// Reads a tree from the given reader in the cache format.
+///
+/// See [`Tree::load_cache`].
+pub(crate) fn load(reader: impl Read) -> io::Result<Tree> {
+    let mut reader = BufReader::new(reader);
+    let mut magic = [0; MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != *MAGIC {
+        return Err(invalid_data("Tree cache magic is not detected"));
+    }
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version != FORMAT_VERSION {
+        return Err(invalid_data(format!(
+            "Unsupported tree cache format version: {}",
+            version
+        )));
+    }
+    let crate_version = read_bytes(&mut reader)?;
+    if crate_version != CRATE_VERSION.as_bytes() {
+        return Err(invalid_data(format!(
+            "Tree cache is written by another version of fbxcel: {}",
+            String::from_utf8_lossy(&crate_version)
+        )));
+    }
+
+    let mut tree = Tree::default();
+    // Parents and the number of their children yet to be read.
+    let mut open: Vec<(NodeId, u64)> = vec![(tree.root().node_id(), read_len(&mut reader)?)];
+    while let Some((parent, rest)) = open.last_mut() {
+        if *rest == 0 {
+            open.pop();
+            continue;
+        }
+        *rest -= 1;
+        let parent = *parent;
+        let node = read_node(&mut reader, &mut tree, parent)?;
+        let children_count = read_len(&mut reader)?;
+        open.push((node, children_count));
+    }
+
+    Ok(tree)
+}
+
+/// Writes a node except for its children.
+fn write_node(writer: &mut impl Write, node: NodeHandle<'_>) -> io::Result<()> {
+    write_bytes(writer, node.name().as_bytes())?;
+    let attrs = node.attributes();
+    write_len(writer, attrs.len())?;
+    for (i, attr) in attrs.iter().enumerate() {
+        writer.write_u8(attr.type_().type_code())?;
+        writer.write_u8(match node.array_encoding(i) {
+            None => 0,
+            Some(ArrayAttributeEncoding::Direct) => 1,
+            Some(ArrayAttributeEncoding::Zlib) => 2,
+        })?;
+        write_value(writer, attr)?;
+    }
+    write_len(writer, node.children().count())
+}
+
+/// Reads a node except for its children, and appends it to the parent.
+fn read_node(reader: &mut impl Read, tree: &mut Tree, parent: NodeId) -> io::Result<NodeId> {
+    let name = String::from_utf8(read_bytes(reader)?).map_err(invalid_data)?;
+    let node = tree.append_new(parent, &name);
+    let attrs_count = read_len(reader)?;
+    for i in 0..attrs_count {
+        let type_code = reader.read_u8()?;
+        let ty = AttributeType::from_type_code(type_code)
+            .ok_or_else(|| invalid_data(format!("Invalid attribute type code: {:?}", type_code)))?;
+        let encoding = match reader.read_u8()? {
+            0 => None,
+            1 => Some(ArrayAttributeEncoding::Direct),
+            2 => Some(ArrayAttributeEncoding::Zlib),
+            v => return Err(invalid_data(format!("Invalid array encoding: {}", v))),
+        };
+        tree.append_attribute(node, read_value(reader, ty)?);
+        if encoding.is_some() {
+            tree.set_array_encoding(node, i as usize, encoding);
+        }
+    }
+
+    Ok(node)
+}
+
+/// Writes an attribute value.
+fn write_value(writer: &mut impl Write, value: &AttributeValue) -> io::Result<()> {
+    /// Writes an array.
+    fn write_arr<T: Copy>(
+        writer: &mut impl Write,
+        values: &[T],
+        mut f: impl FnMut(&mut dyn Write, T) -> io::Result<()>,
+    ) -> io::Result<()> {
+        write_len(writer, values.len())?;
+        values.iter().try_for_each(|&v| f(writer, v))
+    }
+
+    match value {
+        AttributeValue::Bool(v) => writer.write_u8(u8::from(*v)),
+        AttributeValue::I16(v) => writer.write_i16::<LittleEndian>(*v),
+        AttributeValue::I32(v) => writer.write_i32::<LittleEndian>(*v),
+        AttributeValue::I64(v) => writer.write_i64::<LittleEndian>(*v),
+        AttributeValue::F32(v) => writer.write_f32::<LittleEndian>(*v),
+        AttributeValue::F64(v) => writer.write_f64::<LittleEndian>(*v),
+        AttributeValue::ArrBool(v) => write_arr(writer, v, |w, v| w.write_u8(u8::from(v))),
+        AttributeValue::ArrI32(v) => write_arr(writer, v, |w, v| w.write_i32::<LittleEndian>(v)),
+        AttributeValue::ArrI64(v) => write_arr(writer, v, |w, v| w.write_i64::<LittleEndian>(v)),
+        AttributeValue::ArrF32(v) => write_arr(writer, v, |w, v| w.write_f32::<LittleEndian>(v)),
+        AttributeValue::ArrF64(v) => write_arr(writer, v, |w, v| w.write_f64::<LittleEndian>(v)),
+        AttributeValue::Binary(v) => write_bytes(writer, v),
+        AttributeValue::String(v) => write_bytes(writer, v.as_bytes()),
+    }
+}
+
+/// Reads an attribute value of the given type.
+fn read_value(reader: &mut impl Read, ty: AttributeType) -> io::Result<AttributeValue> {
+    /// Reads an array.
+    fn read_arr<R: Read, T>(
+        reader: &mut R,
+        mut f: impl FnMut(&mut R) -> io::Result<T>,
+    ) -> io::Result<Vec<T>> {
+        let len = read_len(reader)?;
+        (0..len).map(|_| f(reader)).collect()
+    }
+
+    Ok(match ty {
+        AttributeType::Bool => AttributeValue::Bool(reader.read_u8()? != 0),
+        AttributeType::I16 => AttributeValue::I16(reader.read_i16::<LittleEndian>()?),
+        AttributeType::I32 => AttributeValue::I32(reader.read_i32::<LittleEndian>()?),
+        AttributeType::I64 => AttributeValue::I64(reader.read_i64::<LittleEndian>()?),
+        AttributeType::F32 => AttributeValue::F32(reader.read_f32::<LittleEndian>()?),
+        AttributeType::F64 => AttributeValue::F64(reader.read_f64::<LittleEndian>()?),
+        AttributeType::ArrBool => {
+            AttributeValue::ArrBool(read_arr(reader, |r| Ok(r.read_u8()? != 0))?)
+        }
+        AttributeType::ArrI32 => {
+            AttributeValue::ArrI32(read_arr(reader, |r| r.read_i32::<LittleEndian>())?)
+        }
+        AttributeType::ArrI64 => {
+            AttributeValue::ArrI64(read_arr(reader, |r| r.read_i64::<LittleEndian>())?)
+        }
+        AttributeType::ArrF32 => {
+            AttributeValue::ArrF32(read_arr(reader, |r| r.read_f32::<LittleEndian>())?)
+        }
+        AttributeType::ArrF64 => {
+            AttributeValue::ArrF64(read_arr(reader, |r| r.read_f64::<LittleEndian>())?)
+        }
+        AttributeType::Binary => AttributeValue::Binary(read_bytes(reader)?),
+        AttributeType::String => {
+            AttributeValue::String(String::from_utf8(read_bytes(reader)?).map_err(invalid_data)?)
+        }
+    })
+}
+
+/// Writes a length.
+fn write_len(writer: &mut (impl Write + ?Sized), len: usize) -> io::Result<()> {
+    writer.write_u64::<LittleEndian>(len as u64)
+}
+
+/// Reads a length.
+fn read_len(reader: &mut impl Read) -> io::Result<u64> {
+    reader.read_u64::<LittleEndian>()
+}
+
+/// Writes bytes with the length.
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    write_len(writer, bytes.len())?;
+    writer.write_all(bytes)
+}
+
+/// Reads bytes with the length.
+fn read_bytes(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let len = read_len(reader)?;
+    let mut bytes = Vec::new();
+    let read = reader.take(len).read_to_end(&mut bytes)?;
+    if read as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(bytes)
+}
+
+/// Creates an error for invalid cache data.
+fn invalid_data(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree_v7400;
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() -> io::Result<()> {
+        let mut tree = tree_v7400! {
+            Node0: [true, 1i16, 2i32, 3i64, 4.0f32, 5.0f64] {
+                Node0_0: [vec![true], vec![1i32], vec![2i64], vec![3.0f32], vec![4.0f64]] {}
+                Node0_1: {}
+            }
+            Node1: [b"binary", "string"] {}
+        };
+        let node0_0 = tree
+            .root()
+            .first_child()
+            .and_then(|node| node.first_child())
+            .expect("Should exist")
+            .node_id();
+        tree.set_array_encoding(node0_0, 1, Some(ArrayAttributeEncoding::Zlib));
+        tree.set_array_encoding(node0_0, 2, Some(ArrayAttributeEncoding::Direct));
+
+        let mut cache = Vec::new();
+        tree.save_cache(&mut cache)?;
+        let loaded = Tree::load_cache(&cache[..])?;
+        assert!(loaded.strict_eq(&tree));
+        let node0_0 = loaded
+            .root()
+            .first_child()
+            .and_then(|node| node.first_child())
+            .expect("Should exist");
+        let encodings = (0..5)
+            .map(|i| node0_0.array_encoding(i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            encodings,
+            [
+                None,
+                Some(ArrayAttributeEncoding::Zlib),
+                Some(ArrayAttributeEncoding::Direct),
+                None,
+                None
+            ]
+        );
+
+        // Truncated data.
+        let err = Tree::load_cache(&cache[..(cache.len() - 1)])
+            .expect_err("Truncated cache should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        // Other format version.
+        cache[MAGIC.len()] += 1;
+        let err = Tree::load_cache(&cache[..]).expect_err("Other version should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        cache[MAGIC.len()] -= 1;
+        // Other crate version.
+        let crate_version_pos = MAGIC.len() + 4 + 8;
+        cache[crate_version_pos] += 1;
+        let err = Tree::load_cache(&cache[..]).expect_err("Other crate version should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+//! Maintenance of `Definitions` node.
+
+use crate::{
+    low::v7400::AttributeValue,
+    tree::v7400::{NodeHandle, NodeId, Tree},
+};
+
+/// Version of `Definitions` node created by this module.
+const DEFINITIONS_VERSION: i32 = 100;
+
+/// Updates the counts in `Definitions` node to match the objects in the tree.
+///
+/// See [`Tree::update_definitions_counts`].
+pub(crate) fn update_counts(tree: &mut Tree) {
+    let counts = count_objects(tree);
+    let definitions = definitions_node(tree);
+
+    let existing = object_types(definitions.to_handle(tree));
+
+    let mut total = 0;
+    for (object_type, name) in &existing {
+        let count = name
+            .as_deref()
+            .and_then(|name| counts.iter().find(|(n, _)| n == name))
+            .map_or(0, |&(_, count)| count);
+        set_count(tree, *object_type, count);
+        total += count;
+    }
+    for (name, count) in &counts {
+        if existing
+            .iter()
+            .any(|(_, existing)| existing.as_deref() == Some(name.as_str()))
+        {
+            continue;
+        }
+        let object_type = tree.append_new(definitions, "ObjectType");
+        tree.append_attribute(object_type, name.as_str());
+        set_count(tree, object_type, *count);
+        total += count;
+    }
+    set_count(tree, definitions, total);
+}
+
+/// Mismatch between a count declared in `Definitions` node and the actual
+/// number of objects.
+///
+/// This is returned by [`Tree::definitions_count_mismatches`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DefinitionsCountMismatch {
+    /// Object type name, or `None` for the total count.
+    object_type: Option<String>,
+    /// Declared count.
+    declared: Option<i64>,
+    /// Actual count.
+    actual: i64,
+}
+
+impl DefinitionsCountMismatch {
+    /// Returns the object type name, or `None` for the total count.
+    #[inline]
+    #[must_use]
+    pub fn object_type(&self) -> Option<&str> {
+        self.object_type.as_deref()
+    }
+
+    /// Returns the declared count.
+    ///
+    /// Returns `None` if the count (or the definition of the object type) is
+    /// missing.
+    #[inline]
+    #[must_use]
+    pub fn declared(&self) -> Option<i64> {
+        self.declared
+    }
+
+    /// Returns the actual number of the objects.
+    #[inline]
+    #[must_use]
+    pub fn actual(&self) -> i64 {
+        self.actual
+    }
+}
+
+/// Returns the mismatches between the counts in `Definitions` node and the
+/// objects in the tree.
+///
+/// See [`Tree::definitions_count_mismatches`].
+pub(crate) fn count_mismatches(tree: &Tree) -> Vec<DefinitionsCountMismatch> {
+    let counts = count_objects(tree);
+    let definitions = tree.root().first_child_by_name("Definitions");
+    let existing = definitions.map_or_else(Vec::new, object_types);
+
+    let mut mismatches = Vec::new();
+    let mut check = |object_type: Option<&str>, declared: Option<i64>, actual: i64| {
+        if declared != Some(actual) {
+            mismatches.push(DefinitionsCountMismatch {
+                object_type: object_type.map(ToOwned::to_owned),
+                declared,
+                actual,
+            });
+        }
+    };
+    for (object_type, name) in &existing {
+        let actual = name
+            .as_deref()
+            .and_then(|name| counts.iter().find(|(n, _)| n == name))
+            .map_or(0, |&(_, count)| count);
+        check(
+            name.as_deref(),
+            declared_count(object_type.to_handle(tree)),
+            i64::from(actual),
+        );
+    }
+    for (name, count) in &counts {
+        if !existing
+            .iter()
+            .any(|(_, existing)| existing.as_deref() == Some(name.as_str()))
+        {
+            check(Some(name), None, i64::from(*count));
+        }
+    }
+    let total = counts.iter().map(|&(_, count)| i64::from(count)).sum();
+    if definitions.is_some() || total != 0 {
+        check(None, definitions.and_then(declared_count), total);
+    }
+
+    mismatches
+}
+
+/// Returns the `ObjectType` children and their type names.
+fn object_types(definitions: NodeHandle<'_>) -> Vec<(NodeId, Option<String>)> {
+    definitions
+        .children_by_name("ObjectType")
+        .map(|node| {
+            let name = node
+                .attributes()
+                .first()
+                .and_then(AttributeValue::get_string)
+                .map(ToOwned::to_owned);
+            (node.node_id(), name)
+        })
+        .collect()
+}
+
+/// Returns the value of the `Count` child of the given node.
+fn declared_count(node: NodeHandle<'_>) -> Option<i64> {
+    match node.first_child_by_name("Count")?.attributes().first()? {
+        AttributeValue::I32(v) => Some(i64::from(*v)),
+        AttributeValue::I64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Counts the objects by the node name, in order of the first appearance.
+///
+/// `GlobalSettings` top-level node is also counted.
+fn count_objects(tree: &Tree) -> Vec<(String, i32)> {
+    let root = tree.root();
+    let mut counts: Vec<(String, i32)> = Vec::new();
+    if root.first_child_by_name("GlobalSettings").is_some() {
+        counts.push(("GlobalSettings".to_owned(), 1));
+    }
+    let objects = root
+        .first_child_by_name("Objects")
+        .into_iter()
+        .flat_map(|objects| objects.children());
+    for object in objects {
+        match counts.iter_mut().find(|(name, _)| name == object.name()) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((object.name().to_owned(), 1)),
+        }
+    }
+
+    counts
+}
+
+/// Returns the `Definitions` node, creating it if it does not exist.
+fn definitions_node(tree: &mut Tree) -> NodeId {
+    let root = tree.root();
+    if let Some(definitions) = root.first_child_by_name("Definitions") {
+        return definitions.node_id();
+    }
+    let definitions = match root.first_child_by_name("Objects") {
+        Some(objects) => {
+            let objects = objects.node_id();
+            tree.insert_new_before(objects, "Definitions")
+        }
+        None => {
+            let root = root.node_id();
+            tree.append_new(root, "Definitions")
+        }
+    };
+    let version = tree.append_new(definitions, "Version");
+    tree.append_attribute(version, DEFINITIONS_VERSION);
+
+    definitions
+}
+
+/// Sets the `Count` child of the given node.
+///
+/// If the node has no `Count` child, it is inserted after `Version` child or
+/// as the first child.
+fn set_count(tree: &mut Tree, parent: NodeId, count: i32) {
+    let parent_handle = parent.to_handle(tree);
+    let count_node = match parent_handle.first_child_by_name("Count") {
+        Some(node) => node.node_id(),
+        None => match parent_handle.first_child_by_name("Version") {
+            Some(version) => {
+                let version = version.node_id();
+                tree.insert_new_after(version, "Count")
+            }
+            None => tree.prepend_new(parent, "Count"),
+        },
+    };
+    tree.set_attributes_vec(count_node, vec![AttributeValue::I32(count)]);
+}
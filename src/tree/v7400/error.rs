@@ -2,7 +2,7 @@
 
 use std::{error, fmt};
 
-use crate::pull_parser::Error as ParserError;
+use crate::{low::v7400::AttributeType, pull_parser::Error as ParserError, ErrorCode};
 
 /// FBX data tree load error.
 #[derive(Debug)]
@@ -16,6 +16,17 @@ pub enum LoadError {
     Parser(ParserError),
 }
 
+impl LoadError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LoadError::BadParser => ErrorCode::BadParser,
+            LoadError::Parser(e) => e.code(),
+        }
+    }
+}
+
 impl fmt::Display for LoadError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -41,3 +52,47 @@ impl From<ParserError> for LoadError {
         LoadError::Parser(e)
     }
 }
+
+/// Node attribute edit error.
+///
+/// This is returned by [`Tree::set_attribute`][`super::Tree::set_attribute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AttributeError {
+    /// Attribute index is out of range.
+    ///
+    /// This contains the index and the number of the attributes.
+    IndexOutOfRange(usize, usize),
+    /// New value has a different type from the old one.
+    ///
+    /// This contains the type of the old value and the new value.
+    TypeMismatch(AttributeType, AttributeType),
+}
+
+impl AttributeError {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AttributeError::IndexOutOfRange(..) => ErrorCode::TreeAttributeIndexOutOfRange,
+            AttributeError::TypeMismatch(..) => ErrorCode::AttributeTypeMismatch,
+        }
+    }
+}
+
+impl fmt::Display for AttributeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeError::IndexOutOfRange(index, len) => write!(
+                f,
+                "Attribute index out of range: index={}, len={}",
+                index, len
+            ),
+            AttributeError::TypeMismatch(old, new) => {
+                write!(f, "Attribute type mismatch: old={}, new={}", old, new)
+            }
+        }
+    }
+}
+
+impl error::Error for AttributeError {}
@@ -1,5 +1,10 @@
 //! FBX data tree loader.
 
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
 use indextree::Arena;
 use log::{debug, error, trace};
 use string_interner::{DefaultBackend, StringInterner};
@@ -8,13 +13,16 @@ use crate::{
     low::v7400::FbxFooter,
     pull_parser::{
         v7400::{attribute::loaders::DirectLoader, Event, Parser, StartNode},
-        Error as ParserError, ParserSource,
+        Error as ParserError, ParserSource, Result as ParserResult, SyntacticPosition, Warning,
     },
     tree::v7400::{LoadError, NodeData, NodeId, NodeNameSym, Tree},
 };
 
+/// Warning handler type.
+type WarningHandler = Arc<Mutex<dyn FnMut(Warning, &SyntacticPosition) -> ParserResult<()> + Send>>;
+
 /// FBX data tree loader.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Loader {
     /// Tree data.
     arena: Arena<NodeData>,
@@ -22,6 +30,8 @@ pub struct Loader {
     node_names: StringInterner<DefaultBackend<NodeNameSym>>,
     /// (Implicit) root node ID.
     root_id: NodeId,
+    /// Warning handler to be set to the parser.
+    warning_handler: Option<WarningHandler>,
 }
 
 impl Loader {
@@ -32,6 +42,35 @@ impl Loader {
         Self::default()
     }
 
+    /// Sets the warning handler.
+    ///
+    /// The handler is set to the parser on [`load`][`Self::load`], replacing
+    /// the handler already set to the parser.
+    /// If no handler is set to the loader, the parser's own handler is used.
+    ///
+    /// The handler should be `Send`, so that the loader stays `Send` and
+    /// `Sync`.
+    /// To use a `!Send` handler, set it to the parser directly.
+    ///
+    /// See [`Parser::set_warning_handler`] for detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree::v7400::Loader;
+    /// let mut loader = Loader::new();
+    /// loader.set_warning_handler(|warning, pos| {
+    ///     eprintln!("WARNING: {} (pos={:?})", warning, pos);
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn set_warning_handler<F>(&mut self, warning_handler: F)
+    where
+        F: 'static + Send + FnMut(Warning, &SyntacticPosition) -> ParserResult<()>,
+    {
+        self.warning_handler = Some(Arc::new(Mutex::new(warning_handler)));
+    }
+
     /// Loads a tree from the given parser, and returns the tree and FBX footer.
     ///
     /// The given parser should be brand-new, i.e. it should not have emited any
@@ -53,6 +92,13 @@ impl Loader {
             return Err(LoadError::BadParser);
         }
 
+        if let Some(handler) = self.warning_handler.take() {
+            parser.set_warning_handler(move |warning, pos| {
+                let mut handler = handler.lock().unwrap_or_else(PoisonError::into_inner);
+                (*handler)(warning, pos)
+            });
+        }
+
         let mut open_nodes = vec![self.root_id];
         let footer = loop {
             trace!("Loading next parser event: open_nodes={:?}", open_nodes);
@@ -77,6 +123,9 @@ impl Loader {
                         .pop()
                         .expect("Should never fail: Open nodes stack should not be empty here");
                 }
+                Event::SkippedNode { .. } => {
+                    trace!("Got `Event::SkippedNode {{ .. }}`");
+                }
                 Event::EndFbx(footer) => {
                     trace!("Got `Event::EndFbx(_)`");
                     open_nodes
@@ -111,12 +160,20 @@ impl Loader {
         // Create a new node.
         let current = {
             let name_sym = self.node_names.get_or_intern(start.name());
-            let attributes = start
-                .attributes()
-                .into_iter(std::iter::repeat(DirectLoader))
-                .collect::<Result<Vec<_>, _>>()?;
+            let mut attrs = start.attributes();
+            let mut attributes = Vec::with_capacity(attrs.total_count() as usize);
+            let mut array_encodings = Vec::new();
+            while let Some(value) = attrs.load_next(DirectLoader)? {
+                if let Some(encoding) = attrs.last_array_encoding() {
+                    array_encodings.resize(attributes.len(), None);
+                    array_encodings.push(Some(encoding));
+                }
+                attributes.push(value);
+            }
 
-            NodeId::new(self.arena.new_node(NodeData::new(name_sym, attributes)))
+            let mut data = NodeData::new(name_sym, attributes);
+            data.set_array_encodings(array_encodings);
+            NodeId::new(self.arena.new_node(data))
         };
 
         // Set the parent.
@@ -145,6 +202,35 @@ impl Default for Loader {
             arena,
             node_names,
             root_id,
+            warning_handler: None,
         }
     }
 }
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Loader")
+            .field("arena", &self.arena)
+            .field("node_names", &self.node_names)
+            .field("root_id", &self.root_id)
+            .field(
+                "warning_handler",
+                &self.warning_handler.as_ref().map(Arc::as_ptr),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loader_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let mut loader = Loader::new();
+        loader.set_warning_handler(|_, _| Ok(()));
+        assert_send_sync(&loader);
+    }
+}
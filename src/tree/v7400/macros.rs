@@ -29,6 +29,19 @@
 ///     Node2: (vec!["hello".into(), "world".into(), 42i32.into()]) {}
 ///     // Use brackets to specify attributes one by one.
 ///     Node3: ["hello", "world", 1.234f32, &b"BINARY"[..]] {}
+///     // Arrays are converted to array attributes (or binary for `u8`).
+///     Node4: [[1.0f64, 2.0, 3.0], b"BINARY"] {}
+/// };
+/// ```
+///
+/// Integer literals in brackets should have a type suffix (such as `42i64`),
+/// since the attribute type would be ambiguous without it.
+/// Integer literals without suffix are rejected at compile time.
+///
+/// ```compile_fail
+/// # use fbxcel::tree_v7400;
+/// let tree = tree_v7400! {
+///     Node: [42] {}
 /// };
 /// ```
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
@@ -59,9 +72,13 @@ macro_rules! tree_v7400 {
     ) => {{
         {
             let _node = $tree.append_new($parent, stringify!($name));
-            $(
+            $({
+                // Reject integer literals without type suffix, since they
+                // would silently be `i32`.
+                let _integer_literal_attribute_requires_type_suffix: [(); 0] =
+                    [(); $crate::macro_support::is_unsuffixed_int_literal(stringify!($attr)) as usize];
                 $tree.append_attribute(_node, $attr);
-            )*
+            })*
             tree_v7400! { @__node, $tree, _node, $($subtree)* }
         }
         tree_v7400! { @__node, $tree, $parent, $($rest)* }
@@ -210,4 +227,33 @@ mod tests {
 
         assert!(tree_manual.strict_eq(&tree_macro));
     }
+
+    #[test]
+    fn array_attributes() {
+        let tree_vec = tree_v7400! {
+            Node: [
+                vec![true, false],
+                vec![1i32, 2],
+                vec![3i64],
+                vec![4.0f32],
+                vec![5.0f64, 6.0],
+                vec![7u8, 8],
+                String::from("Hello")
+            ] {}
+        };
+        let hello = String::from("Hello");
+        let tree_array = tree_v7400! {
+            Node: [
+                [true, false],
+                &[1i32, 2],
+                vec![3i64].into_boxed_slice(),
+                [4.0f32],
+                &[5.0f64, 6.0],
+                b"\x07\x08",
+                &hello
+            ] {}
+        };
+
+        assert!(tree_vec.strict_eq(&tree_array));
+    }
 }
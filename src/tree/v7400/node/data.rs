@@ -1,17 +1,39 @@
 //! Node-local data.
 
-use crate::{low::v7400::AttributeValue, tree::v7400::node::NodeNameSym};
+use std::sync::Arc;
+
+use crate::{
+    low::v7400::{ArrayAttributeEncoding, AttributeValue},
+    tree::v7400::node::NodeNameSym,
+};
 
 /// Node-local data in FBX data tree.
 ///
 /// This does not manages relations among nodes (including parent-child
 /// relatinos).
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Attributes are shared among clones, and copied on write.
+///
+/// Array encoding hints are representation details, and they are not
+/// compared by `PartialEq`.
+#[derive(Debug, Clone)]
 pub(crate) struct NodeData {
     /// Node name.
     name_sym: NodeNameSym,
     /// Node attributes.
-    attributes: Vec<AttributeValue>,
+    attributes: Arc<Vec<AttributeValue>>,
+    /// Array encoding hints of the attributes.
+    ///
+    /// This may be shorter than the attributes, and missing elements are
+    /// treated as `None`.
+    array_encodings: Vec<Option<ArrayAttributeEncoding>>,
+}
+
+impl PartialEq for NodeData {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.name_sym == other.name_sym && self.attributes == other.attributes
+    }
 }
 
 impl NodeData {
@@ -32,19 +54,61 @@ impl NodeData {
     /// Appends the given value to the attributes.
     #[inline]
     pub(crate) fn append_attribute(&mut self, v: AttributeValue) {
-        self.attributes.push(v)
+        Arc::make_mut(&mut self.attributes).push(v)
     }
 
-    /// Appends the given value to the attributes.
+    /// Returns a mutable reference to the attribute at the given index.
     #[inline]
     pub(crate) fn get_attribute_mut(&mut self, i: usize) -> Option<&mut AttributeValue> {
-        self.attributes.get_mut(i)
+        if i >= self.attributes.len() {
+            return None;
+        }
+        Arc::make_mut(&mut self.attributes).get_mut(i)
+    }
+
+    /// Returns the array encoding hint of the attribute at the given index.
+    #[inline]
+    #[must_use]
+    pub(crate) fn array_encoding(&self, i: usize) -> Option<ArrayAttributeEncoding> {
+        self.array_encodings.get(i).copied().flatten()
+    }
+
+    /// Returns the array encoding hints of the attributes.
+    #[inline]
+    #[must_use]
+    pub(crate) fn array_encodings(&self) -> &[Option<ArrayAttributeEncoding>] {
+        &self.array_encodings
+    }
+
+    /// Sets the array encoding hint of the attribute at the given index.
+    pub(crate) fn set_array_encoding(
+        &mut self,
+        i: usize,
+        encoding: Option<ArrayAttributeEncoding>,
+    ) {
+        if i >= self.array_encodings.len() {
+            if encoding.is_none() {
+                return;
+            }
+            self.array_encodings.resize(i + 1, None);
+        }
+        self.array_encodings[i] = encoding;
+    }
+
+    /// Replaces all array encoding hints by the given one.
+    #[inline]
+    pub(crate) fn set_array_encodings(&mut self, new: Vec<Option<ArrayAttributeEncoding>>) {
+        self.array_encodings = new;
     }
 
     /// Replaces all attributes by the given one, and returns the old.
+    ///
+    /// Array encoding hints are cleared.
     #[inline]
     pub(crate) fn replace_attributes(&mut self, new: Vec<AttributeValue>) -> Vec<AttributeValue> {
-        std::mem::replace(&mut self.attributes, new)
+        self.array_encodings.clear();
+        let old = std::mem::replace(&mut self.attributes, Arc::new(new));
+        Arc::try_unwrap(old).unwrap_or_else(|shared| (*shared).clone())
     }
 
     /// Creates a new `NodeData`.
@@ -53,7 +117,42 @@ impl NodeData {
     pub(crate) fn new(name_sym: NodeNameSym, attributes: Vec<AttributeValue>) -> Self {
         Self {
             name_sym,
-            attributes,
+            attributes: Arc::new(attributes),
+            array_encodings: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use string_interner::Symbol;
+
+    use super::*;
+
+    #[test]
+    fn attributes_copied_on_write() {
+        let sym = NodeNameSym::try_from_usize(0).expect("Should never fail");
+        let original = NodeData::new(sym, vec![AttributeValue::I32(42)]);
+        let mut cloned = original.clone();
+        assert!(Arc::ptr_eq(&original.attributes, &cloned.attributes));
+
+        *cloned.get_attribute_mut(0).expect("Should exist") = AttributeValue::I32(0);
+        assert!(!Arc::ptr_eq(&original.attributes, &cloned.attributes));
+        assert_eq!(original.attributes(), [AttributeValue::I32(42)]);
+        assert_eq!(cloned.attributes(), [AttributeValue::I32(0)]);
+    }
+
+    #[test]
+    fn array_encodings_ignored_by_eq() {
+        let sym = NodeNameSym::try_from_usize(0).expect("Should never fail");
+        let original = NodeData::new(sym, vec![AttributeValue::ArrI32(vec![42])]);
+        let mut hinted = original.clone();
+        hinted.set_array_encoding(0, Some(ArrayAttributeEncoding::Zlib));
+        assert_eq!(hinted.array_encoding(0), Some(ArrayAttributeEncoding::Zlib));
+        assert_eq!(hinted.array_encoding(1), None);
+        assert_eq!(original, hinted);
+
+        hinted.replace_attributes(vec![AttributeValue::ArrI32(vec![0])]);
+        assert_eq!(hinted.array_encoding(0), None);
+    }
+}
@@ -3,8 +3,8 @@
 use std::fmt;
 
 use crate::{
-    low::v7400::AttributeValue,
-    tree::v7400::{NodeData, NodeId, NodeNameSym, Tree},
+    low::v7400::{ArrayAttributeEncoding, AttributeValue},
+    tree::v7400::{NameIndex, NodeData, NodeId, NodeNameSym, Tree},
 };
 
 /// Node handle.
@@ -80,6 +80,21 @@ impl<'a> NodeHandle<'a> {
         self.node().get().attributes()
     }
 
+    /// Returns the array encoding hint of the attribute at the given index.
+    ///
+    /// Trees loaded from FBX data remember how each array attribute was
+    /// encoded, and `Writer::write_tree` (with `writer` feature) uses the
+    /// hints to re-encode the arrays in the same way.
+    /// This returns `None` for non-array attributes, attributes added after
+    /// loading, and attributes without hints.
+    ///
+    /// [`Writer::write_tree`]: `crate::writer::v7400::binary::Writer::write_tree`
+    #[inline]
+    #[must_use]
+    pub fn array_encoding(&self, i: usize) -> Option<ArrayAttributeEncoding> {
+        self.node().get().array_encoding(i)
+    }
+
     /// Returns an iterator of children with the given name.
     #[inline]
     #[must_use]
@@ -91,6 +106,10 @@ impl<'a> NodeHandle<'a> {
     }
 
     /// Returns an iterator of children with the given name.
+    ///
+    /// This scans the children linearly.
+    /// To look up children of the node by name repeatedly, consider using
+    /// [`children_name_index`][`Self::children_name_index`].
     #[inline]
     #[must_use]
     pub fn children_by_name(&self, name: &str) -> ChildrenByName<'a> {
@@ -100,6 +119,34 @@ impl<'a> NodeHandle<'a> {
         }
     }
 
+    /// Creates an index of the children by name.
+    ///
+    /// Building the index visits all children once, and subsequent lookups
+    /// by name are fast.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::tree_v7400;
+    /// let tree = tree_v7400! {
+    ///     Objects: {
+    ///         Model: [1i64] {}
+    ///         Geometry: [2i64] {}
+    ///         Model: [3i64] {}
+    ///     }
+    /// };
+    /// let objects = tree.root().first_child_by_name("Objects").expect("Should exist");
+    /// let index = objects.children_name_index();
+    /// assert_eq!(index.nodes("Model").count(), 2);
+    /// assert!(index.first_node("Geometry").is_some());
+    /// assert!(index.first_node("Texture").is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn children_name_index(&self) -> NameIndex<'a> {
+        NameIndex::from_nodes(self.tree, self.children())
+    }
+
     /// Returns the first child with the given name.
     #[inline]
     #[must_use]
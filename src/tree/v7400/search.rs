@@ -0,0 +1,141 @@
+//! Node search.
+
+use std::collections::HashMap;
+
+use crate::{
+    low::v7400::AttributeValue,
+    tree::v7400::{node::NodeNameSym, NodeHandle, NodeId, Traverse, Tree},
+};
+
+/// Iterator of nodes matching the predicate, in depth-first order.
+///
+/// This is created by [`Tree::find_nodes`].
+#[derive(Debug, Clone)]
+pub struct FindNodes<'a, P> {
+    /// Traversal of the tree.
+    inner: Traverse<'a>,
+    /// Predicate.
+    predicate: P,
+}
+
+impl<'a, P> FindNodes<'a, P> {
+    /// Creates a new `FindNodes`.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(inner: Traverse<'a>, predicate: P) -> Self {
+        Self { inner, predicate }
+    }
+}
+
+impl<'a, P> Iterator for FindNodes<'a, P>
+where
+    P: FnMut(&str, &[AttributeValue]) -> bool,
+{
+    type Item = NodeHandle<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+        self.inner
+            .by_ref()
+            .filter(|ev| ev.is_open())
+            .map(|ev| ev.node())
+            .find(|node| predicate(node.name(), node.attributes()))
+    }
+}
+
+impl<P> std::iter::FusedIterator for FindNodes<'_, P> where P: FnMut(&str, &[AttributeValue]) -> bool
+{}
+
+/// Index of nodes by name.
+///
+/// This is useful to accelerate repeated queries on a loaded tree.
+/// The index borrows the tree, so it never gets stale.
+///
+/// This is created by [`Tree::name_index`] for all nodes in the tree, or by
+/// [`NodeHandle::children_name_index`] for children of a node.
+#[derive(Debug, Clone)]
+pub struct NameIndex<'a> {
+    /// Tree.
+    tree: &'a Tree,
+    /// Node IDs for each name, in the order of the indexed nodes.
+    nodes: HashMap<NodeNameSym, Vec<NodeId>>,
+}
+
+impl<'a> NameIndex<'a> {
+    /// Creates a new index of all nodes in the tree.
+    #[must_use]
+    pub(crate) fn new(tree: &'a Tree) -> Self {
+        Self::from_nodes(
+            tree,
+            tree.traverse()
+                .filter(|ev| ev.is_open())
+                .map(|ev| ev.node()),
+        )
+    }
+
+    /// Creates a new index of the given nodes.
+    #[must_use]
+    pub(crate) fn from_nodes(
+        tree: &'a Tree,
+        iter: impl IntoIterator<Item = NodeHandle<'a>>,
+    ) -> Self {
+        let mut nodes: HashMap<_, Vec<_>> = HashMap::new();
+        for node in iter {
+            nodes
+                .entry(node.name_sym())
+                .or_default()
+                .push(node.node_id());
+        }
+
+        Self { tree, nodes }
+    }
+
+    /// Returns the tree.
+    #[inline]
+    #[must_use]
+    pub fn tree(&self) -> &'a Tree {
+        self.tree
+    }
+
+    /// Returns the IDs of nodes with the given name, in the order of the
+    /// indexed nodes.
+    ///
+    /// The order is depth-first order for the index of the whole tree, and
+    /// the tree order for the index of children.
+    #[must_use]
+    pub fn node_ids(&self, name: &str) -> &[NodeId] {
+        self.tree
+            .node_name_sym(name)
+            .and_then(|sym| self.nodes.get(&sym))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator of nodes with the given name, in the order of the
+    /// indexed nodes.
+    pub fn nodes(&self, name: &str) -> impl Iterator<Item = NodeHandle<'a>> + '_ {
+        let tree = self.tree;
+        self.node_ids(name).iter().map(move |id| id.to_handle(tree))
+    }
+
+    /// Returns the first node with the given name.
+    #[must_use]
+    pub fn first_node(&self, name: &str) -> Option<NodeHandle<'a>> {
+        self.node_ids(name)
+            .first()
+            .map(|id| id.to_handle(self.tree))
+    }
+
+    /// Returns an iterator of nodes with the given name whose attributes
+    /// match the predicate, in the order of the indexed nodes.
+    pub fn find_nodes<P>(
+        &self,
+        name: &str,
+        mut predicate: P,
+    ) -> impl Iterator<Item = NodeHandle<'a>> + '_
+    where
+        P: FnMut(&[AttributeValue]) -> bool + 'a,
+    {
+        self.nodes(name)
+            .filter(move |node| predicate(node.attributes()))
+    }
+}
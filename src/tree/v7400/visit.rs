@@ -0,0 +1,91 @@
+//! Callback-based tree traversal.
+
+use crate::tree::v7400::{NodeHandle, Tree};
+
+/// Control flow of [`Tree::visit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VisitFlow {
+    /// Continues the traversal.
+    Continue,
+    /// Skips the descendants of the current node, and continues the
+    /// traversal from the next sibling.
+    ///
+    /// This is same as `Continue` when returned by [`Visitor::leave`].
+    Prune,
+    /// Stops the traversal immediately.
+    Stop,
+}
+
+/// Visitor of tree nodes.
+///
+/// This is implemented for closures which receive the node and its depth,
+/// as `enter` callbacks.
+pub trait Visitor<'a> {
+    /// Called when the traversal enters the node.
+    ///
+    /// Top-level nodes have depth 1.
+    fn enter(&mut self, node: NodeHandle<'a>, depth: usize) -> VisitFlow;
+
+    /// Called when the traversal leaves the node.
+    ///
+    /// This is also called for pruned nodes, after `enter`.
+    #[inline]
+    fn leave(&mut self, _node: NodeHandle<'a>, _depth: usize) -> VisitFlow {
+        VisitFlow::Continue
+    }
+}
+
+impl<'a, F> Visitor<'a> for F
+where
+    F: FnMut(NodeHandle<'a>, usize) -> VisitFlow,
+{
+    #[inline]
+    fn enter(&mut self, node: NodeHandle<'a>, depth: usize) -> VisitFlow {
+        self(node, depth)
+    }
+}
+
+/// Visits the nodes of the tree in depth-first order.
+///
+/// Returns `VisitFlow::Stop` if the visitor stopped the traversal, and
+/// `VisitFlow::Continue` otherwise.
+pub(crate) fn visit<'a, V>(tree: &'a Tree, visitor: &mut V) -> VisitFlow
+where
+    V: Visitor<'a> + ?Sized,
+{
+    let mut next = tree.root().first_child();
+    let mut depth = 1;
+    while let Some(mut node) = next {
+        match visitor.enter(node, depth) {
+            VisitFlow::Stop => return VisitFlow::Stop,
+            VisitFlow::Continue => {
+                if let Some(child) = node.first_child() {
+                    next = Some(child);
+                    depth += 1;
+                    continue;
+                }
+            }
+            VisitFlow::Prune => {}
+        }
+        // Leave the node and the ancestors without next siblings.
+        loop {
+            if visitor.leave(node, depth) == VisitFlow::Stop {
+                return VisitFlow::Stop;
+            }
+            next = node.next_sibling();
+            if next.is_some() {
+                break;
+            }
+            depth -= 1;
+            if depth == 0 {
+                return VisitFlow::Continue;
+            }
+            node = node
+                .parent()
+                .expect("Non-root nodes should have the parent");
+        }
+    }
+
+    VisitFlow::Continue
+}
@@ -69,6 +69,8 @@
 //!
 //! If `close_node` call is too few and there remains open nodes on finalizing
 //! writer, `finalize()` and `finalize_and_flush()` will return error.
+//! For statically structured data, [`TypedWriter`] can check the balance of
+//! nodes at compile time.
 //!
 //! ```
 //! use fbxcel::{
@@ -91,18 +93,35 @@
 //! ```
 
 use std::{
-    convert::TryFrom,
+    fmt,
     io::{self, Read, Seek, SeekFrom, Write},
+    sync::{Arc, Mutex},
 };
 
 use log::{debug, trace};
 
-use crate::low::{v7400::NodeHeader, FbxVersion, MAGIC};
+use crate::{
+    low::{
+        v7400::{ArrayAttributeHeader, AttributeType, NodeHeader, NodeHeaderError},
+        FbxVersion, MAGIC,
+    },
+    pull_parser::{
+        v7400::{Event, Parser},
+        ParserSource,
+    },
+};
 
+#[cfg(feature = "ndarray")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "ndarray")))]
+pub use self::attributes::ElementOrder;
 pub use self::{
     attributes::AttributesWriter,
-    error::{CompressionError, Error, Result},
-    footer::{FbxFooter, FbxFooterPaddingLength},
+    error::{CompressionError, CopyError, Error, Result},
+    footer::{FbxFooter, FbxFooterPaddingLength, FooterPaddingRewrite},
+    name_policy::NodeNamePolicy,
+    properties::PropertiesWriter,
+    typed::{Depth, NodeWriter, TypedWriter, TypedWriterError},
+    warning::Warning,
 };
 
 mod macros;
@@ -110,11 +129,22 @@ mod macros;
 mod attributes;
 mod error;
 mod footer;
+mod name_policy;
+mod properties;
+mod typed;
+mod verify;
+mod warning;
+
+/// Size of the write buffer for compressed array attributes.
+const COMPRESSION_BUF_LEN: usize = 8 * 1024;
+
+/// Warning handler type.
+type WarningHandler = Arc<Mutex<dyn FnMut(Warning) -> Result<()> + Send>>;
 
 /// Binary writer.
 ///
 /// See [module documentation][`self`] for usage.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Writer<W: Write> {
     /// Writer destination.
     sink: W,
@@ -122,13 +152,28 @@ pub struct Writer<W: Write> {
     fbx_version: FbxVersion,
     /// Node header positions not yet closed.
     open_nodes: Vec<OpenNode>,
+    /// Byte sizes of the top-level nodes.
+    toplevel_node_sizes: Vec<ToplevelNodeSize>,
+    /// Whether to check the order of the top-level nodes.
+    check_toplevel_order: bool,
+    /// Highest rank of the well-known top-level nodes written so far.
+    max_toplevel_rank: Option<usize>,
+    /// Write buffer for compressed array attributes, reused across
+    /// attributes.
+    compression_buf: Vec<u8>,
+    /// Warning handler.
+    warning_handler: Option<WarningHandler>,
+    /// Table of contents, if recording is enabled.
+    toc: Option<Vec<TocEntry>>,
+    /// Default node name policy.
+    name_policy: NodeNamePolicy,
 }
 
 impl<W: Write + Seek> Writer<W> {
     /// Creates a new `Writer` and writes FBX file header.
     pub fn new(mut sink: W, fbx_version: FbxVersion) -> Result<Self> {
         // Check if the given version is supported.
-        if fbx_version.major() != 7 {
+        if !fbx_version.is_supported_by_writer() {
             return Err(Error::UnsupportedFbxVersion(fbx_version));
         }
 
@@ -141,9 +186,118 @@ impl<W: Write + Seek> Writer<W> {
             sink,
             fbx_version,
             open_nodes: Vec::new(),
+            toplevel_node_sizes: Vec::new(),
+            check_toplevel_order: false,
+            max_toplevel_rank: None,
+            compression_buf: Vec::new(),
+            warning_handler: None,
+            toc: None,
+            name_policy: NodeNamePolicy::default(),
         })
     }
 
+    /// Sets the warning handler.
+    ///
+    /// The warning handler will be called when the writer finds a non-fatal
+    /// condition which may produce files rejected by some importers.
+    /// If the handler returns `Ok(())`, the writer continues writing.
+    /// If the handler returns `Err(_)`, the writer stops and the operation
+    /// returns the error.
+    ///
+    /// Warnings are ignored if no handler is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+    /// # let sink = std::io::Cursor::new(Vec::new());
+    /// let mut writer = Writer::new(sink, FbxVersion::V7_4)?;
+    /// writer.set_warning_handler(|warning| {
+    ///     // Print warning.
+    ///     eprintln!("WARNING: {}", warning);
+    ///     // To ignore the warning and continue writing, return `Ok(())`.
+    ///     // To treat the given warning as a critical error, return
+    ///     // `Err(warning.into())`.
+    ///     Ok(())
+    /// });
+    /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+    /// ```
+    pub fn set_warning_handler<F>(&mut self, warning_handler: F)
+    where
+        F: 'static + FnMut(Warning) -> Result<()> + Send,
+    {
+        self.warning_handler = Some(Arc::new(Mutex::new(warning_handler)));
+    }
+
+    /// Reports the warning to the warning handler.
+    fn warn(&mut self, warning: Warning) -> Result<()> {
+        debug!("Writer warning: {}", warning);
+        match &self.warning_handler {
+            Some(handler) => {
+                let mut handler = handler
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                (*handler)(warning)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Enables or disables the check of the top-level node order.
+    ///
+    /// Some importers require the top-level nodes to be in a particular
+    /// order (for example, `FBXHeaderExtension` first and `Takes` last).
+    /// If enabled, [`new_node`][`Self::new_node`] returns
+    /// [`Error::ToplevelNodeOrder`] when a well-known top-level node is
+    /// written after another well-known node which should follow it.
+    /// If disabled, [`Warning::ToplevelNodeOrder`] is reported instead.
+    /// Unknown top-level nodes can be placed anywhere.
+    ///
+    /// The well-known order is: `FBXHeaderExtension`, `FileId`,
+    /// `CreationTime`, `Creator`, `GlobalSettings`, `Documents`,
+    /// `References`, `Definitions`, `Objects`, `Connections`, and `Takes`.
+    ///
+    /// This is disabled by default.
+    #[inline]
+    pub fn set_toplevel_order_check(&mut self, enabled: bool) {
+        self.check_toplevel_order = enabled;
+    }
+
+    /// Enables or disables recording of the table of contents.
+    ///
+    /// If enabled, the writer records the name and the position of every
+    /// node written after this call, and
+    /// [`finalize_with_toc`][`Self::finalize_with_toc`] returns them.
+    /// This is useful for debugging and for later in-place patching of
+    /// single nodes.
+    ///
+    /// Disabling discards the entries recorded so far, including the entries
+    /// of the nodes still open.
+    ///
+    /// This is disabled by default.
+    pub fn set_toc_recording(&mut self, enabled: bool) {
+        if !enabled {
+            self.toc = None;
+            for node in &mut self.open_nodes {
+                node.toc_index = None;
+            }
+        } else if self.toc.is_none() {
+            self.toc = Some(Vec::new());
+        }
+    }
+
+    /// Sets the default policy for node names containing NUL or non-ASCII
+    /// characters.
+    ///
+    /// This can be overridden for each node by
+    /// [`new_node_with_name_policy`][`Self::new_node_with_name_policy`].
+    ///
+    /// [`NodeNamePolicy::Allow`] is used by default.
+    #[inline]
+    pub fn set_node_name_policy(&mut self, policy: NodeNamePolicy) {
+        self.name_policy = policy;
+    }
+
     /// Returns a mutable reference to the sink.
     #[inline]
     #[must_use]
@@ -151,6 +305,48 @@ impl<W: Write + Seek> Writer<W> {
         &mut self.sink
     }
 
+    /// Takes the write buffer for compressed data.
+    ///
+    /// The buffer has the size of [`COMPRESSION_BUF_LEN`].
+    /// Only the buffer is reused among array attributes, since the zlib
+    /// encoder of `libflate` cannot be reset.
+    ///
+    /// It should be returned by [`restore_compression_buf`][`Self::restore_compression_buf`]
+    /// after use.
+    #[inline]
+    #[must_use]
+    fn take_compression_buf(&mut self) -> Vec<u8> {
+        let mut buf = std::mem::take(&mut self.compression_buf);
+        buf.resize(COMPRESSION_BUF_LEN, 0);
+        buf
+    }
+
+    /// Returns the write buffer for compressed data to the writer for reuse.
+    #[inline]
+    fn restore_compression_buf(&mut self, buf: Vec<u8>) {
+        self.compression_buf = buf;
+    }
+
+    /// Returns the number of open nodes.
+    ///
+    /// Returns `0` if there are no open nodes.
+    #[inline]
+    #[must_use]
+    pub fn current_depth(&self) -> usize {
+        self.open_nodes.len()
+    }
+
+    /// Returns whether the attributes of the current node are finalized.
+    ///
+    /// Returns `true` if there are no open nodes.
+    #[inline]
+    #[must_use]
+    pub(crate) fn is_current_attrs_finalized(&self) -> bool {
+        self.open_nodes
+            .last()
+            .map_or(true, |node| node.is_attrs_finalized)
+    }
+
     /// Returns a mutable reference to the node header of the current node.
     #[inline]
     #[must_use]
@@ -165,24 +361,47 @@ impl<W: Write + Seek> Writer<W> {
         self.current_node().map(|v| &mut v.header)
     }
 
+    /// Checks if the given offset in an open node can be represented in node
+    /// headers.
+    ///
+    /// Node headers of FBX 7.4 and earlier have 32-bit end offsets, so data
+    /// crossing 4 GiB can never be closed successfully.
+    /// This detects such data on the write that crosses the limit, rather
+    /// than on closing the node.
+    fn check_offset(&self, offset: u64) -> Result<()> {
+        if !self.fbx_version.has_64bit_node_header() && offset > u64::from(u32::MAX) {
+            return Err(Error::FileTooLarge(offset));
+        }
+        Ok(())
+    }
+
+    /// Checks if the current offset of the sink can be represented in node
+    /// headers.
+    ///
+    /// See [`check_offset`][`Self::check_offset`].
+    /// This does not query the sink position for FBX versions with 64-bit
+    /// node headers.
+    fn check_current_offset(&mut self) -> Result<()> {
+        if self.fbx_version.has_64bit_node_header() {
+            return Ok(());
+        }
+        let offset = self.sink.stream_position()?;
+        self.check_offset(offset)
+    }
+
     /// Writes the given node header.
     fn write_node_header(&mut self, header: &NodeHeader) -> Result<()> {
-        if self.fbx_version.raw() < 7500 {
-            self.sink.write_all(
-                &u32::try_from(header.end_offset)
-                    .map_err(|_| Error::FileTooLarge(header.end_offset))?
-                    .to_le_bytes(),
-            )?;
-            self.sink.write_all(
-                &u32::try_from(header.num_attributes)
-                    .map_err(|_| Error::TooManyAttributes(header.num_attributes as usize))?
-                    .to_le_bytes(),
-            )?;
-            self.sink.write_all(
-                &u32::try_from(header.bytelen_attributes)
-                    .map_err(|_| Error::AttributeTooLong(header.bytelen_attributes as usize))?
-                    .to_le_bytes(),
-            )?;
+        header
+            .validate(self.fbx_version, None)
+            .map_err(node_header_error)?;
+        if !self.fbx_version.has_64bit_node_header() {
+            // Fields are validated to fit in `u32`.
+            self.sink
+                .write_all(&(header.end_offset as u32).to_le_bytes())?;
+            self.sink
+                .write_all(&(header.num_attributes as u32).to_le_bytes())?;
+            self.sink
+                .write_all(&(header.bytelen_attributes as u32).to_le_bytes())?;
         } else {
             self.sink.write_all(&header.end_offset.to_le_bytes())?;
             self.sink.write_all(&header.num_attributes.to_le_bytes())?;
@@ -227,7 +446,40 @@ impl<W: Write + Seek> Writer<W> {
     }
 
     /// Creates a new node and returns node attributes writer.
+    ///
+    /// The node name is checked by the default node name policy set by
+    /// [`set_node_name_policy`][`Self::set_node_name_policy`].
+    #[inline]
     pub fn new_node(&mut self, name: &str) -> Result<AttributesWriter<'_, W>> {
+        self.new_node_with_name_policy(name, self.name_policy)
+    }
+
+    /// Creates a new node with the given node name policy, and returns node
+    /// attributes writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::{low::FbxVersion, writer::v7400::binary::{Error, NodeNamePolicy, Writer}};
+    /// # let sink = std::io::Cursor::new(Vec::new());
+    /// let mut writer = Writer::new(sink, FbxVersion::V7_4)?;
+    /// writer.set_node_name_policy(NodeNamePolicy::Reject);
+    /// assert!(matches!(
+    ///     writer.new_node("Bad\u{0}Name"),
+    ///     Err(Error::InvalidNodeName(_))
+    /// ));
+    /// // Object names use NUL as a separator legitimately.
+    /// writer.new_node_with_name_policy("Name\u{0}\u{1}Class", NodeNamePolicy::Allow)?;
+    /// writer.close_node()?;
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn new_node_with_name_policy(
+        &mut self,
+        name: &str,
+        policy: NodeNamePolicy,
+    ) -> Result<AttributesWriter<'_, W>> {
+        let name = policy.apply(name)?;
+        let name = &*name;
         trace!(
             "New node: name={:?}, depth={:?}",
             name,
@@ -237,21 +489,21 @@ impl<W: Write + Seek> Writer<W> {
 
         if let Some(current_node) = self.current_node() {
             current_node.has_child = true;
+        } else {
+            self.check_toplevel_node_order(name)?;
+        }
+
+        if name.is_empty() {
+            self.warn(Warning::EmptyNodeName)?;
+        } else if !name.bytes().all(|b| b.is_ascii_graphic()) {
+            self.warn(Warning::SuspiciousNodeName(name.to_owned()))?;
         }
 
         // Check if the node name is short enough.
-        let bytelen_name =
-            u8::try_from(name.len()).map_err(|_| Error::NodeNameTooLong(name.len()))?;
+        let header = NodeHeader::with_name_len(name.len()).map_err(node_header_error)?;
 
         let header_pos = self.sink.stream_position()?;
 
-        let header = NodeHeader {
-            end_offset: 0,
-            num_attributes: 0,
-            bytelen_attributes: 0,
-            bytelen_name,
-        };
-
         // Write dummy header (placeholder).
         self.write_node_header(&header)?;
 
@@ -259,6 +511,26 @@ impl<W: Write + Seek> Writer<W> {
         self.sink.write_all(name.as_ref())?;
 
         let body_pos = self.sink.stream_position()?;
+        self.check_offset(body_pos)?;
+
+        let depth = self.open_nodes.len() + 1;
+        let toc_index = self.toc.as_mut().map(|toc| {
+            toc.push(TocEntry {
+                name: name.to_owned(),
+                depth,
+                header_offset: header_pos,
+                end_offset: 0,
+            });
+            toc.len() - 1
+        });
+
+        if self.open_nodes.is_empty() {
+            self.toplevel_node_sizes.push(ToplevelNodeSize {
+                name: name.to_owned(),
+                offset: header_pos,
+                bytelen: 0,
+            });
+        }
 
         self.open_nodes.push(OpenNode {
             header_pos,
@@ -266,11 +538,37 @@ impl<W: Write + Seek> Writer<W> {
             header,
             has_child: false,
             is_attrs_finalized: false,
+            toc_index,
         });
 
         Ok(AttributesWriter::new(self))
     }
 
+    /// Checks if the top-level node with the given name can be written next.
+    ///
+    /// If the order check is enabled, returns an error for misordered nodes.
+    /// Otherwise, reports a warning.
+    fn check_toplevel_node_order(&mut self, name: &str) -> Result<()> {
+        let rank = match toplevel_node_rank(name) {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        match self.max_toplevel_rank {
+            Some(max_rank) if max_rank > rank => {
+                let prev = TOPLEVEL_NODE_ORDER[max_rank].to_owned();
+                if self.check_toplevel_order {
+                    Err(Error::ToplevelNodeOrder(name.to_owned(), prev))
+                } else {
+                    self.warn(Warning::ToplevelNodeOrder(name.to_owned(), prev))
+                }
+            }
+            _ => {
+                self.max_toplevel_rank = Some(rank);
+                Ok(())
+            }
+        }
+    }
+
     /// Closes an open node.
     pub fn close_node(&mut self) -> Result<()> {
         trace!("Close node: depth={:?}", self.open_nodes.len());
@@ -298,76 +596,164 @@ impl<W: Write + Seek> Writer<W> {
         self.write_node_header(&current_node.header)?;
         self.sink.seek(SeekFrom::Start(node_end_pos))?;
 
+        if let (Some(toc), Some(index)) = (self.toc.as_mut(), current_node.toc_index) {
+            toc[index].end_offset = node_end_pos;
+        }
+
+        if self.open_nodes.is_empty() {
+            let size = self
+                .toplevel_node_sizes
+                .last_mut()
+                .expect("Should never fail: top-level node should have been recorded on open");
+            size.bytelen = node_end_pos - size.offset;
+        }
+
         Ok(())
     }
 
+    /// Returns the byte sizes of the top-level nodes written so far.
+    ///
+    /// Only closed nodes are contained, in the order they are written.
+    /// This can be used to know which part of the file dominates output size.
+    #[inline]
+    #[must_use]
+    pub fn toplevel_node_sizes(&self) -> &[ToplevelNodeSize] {
+        if self.open_nodes.is_empty() {
+            &self.toplevel_node_sizes
+        } else {
+            // The last top-level node is not yet closed.
+            &self.toplevel_node_sizes[..(self.toplevel_node_sizes.len() - 1)]
+        }
+    }
+
     /// Writes the given tree.
+    ///
+    /// Array attributes are encoded as their encoding hints (see
+    /// [`NodeHandle::array_encoding`][`crate::tree::v7400::NodeHandle::array_encoding`]),
+    /// so arrays loaded from FBX data are written in the same way as the
+    /// source.
+    /// Arrays without hints are written without compression.
     #[cfg(feature = "tree")]
     #[cfg_attr(feature = "docsrs", doc(cfg(feature = "tree")))]
     pub fn write_tree(&mut self, tree: &crate::tree::v7400::Tree) -> Result<()> {
-        use crate::low::v7400::AttributeValue;
-
-        let mut current = match tree.root().first_child() {
-            Some(v) => v,
-            None => return Ok(()),
-        };
-
-        'all: loop {
-            let mut attrs_writer = self.new_node(current.name())?;
-            for attr in current.attributes() {
-                match attr {
-                    AttributeValue::Bool(v) => attrs_writer.append_bool(*v)?,
-                    AttributeValue::I16(v) => attrs_writer.append_i16(*v)?,
-                    AttributeValue::I32(v) => attrs_writer.append_i32(*v)?,
-                    AttributeValue::I64(v) => attrs_writer.append_i64(*v)?,
-                    AttributeValue::F32(v) => attrs_writer.append_f32(*v)?,
-                    AttributeValue::F64(v) => attrs_writer.append_f64(*v)?,
-                    AttributeValue::ArrBool(v) => {
-                        attrs_writer.append_arr_bool_from_iter(None, v.iter().cloned())?
-                    }
-                    AttributeValue::ArrI32(v) => {
-                        attrs_writer.append_arr_i32_from_iter(None, v.iter().cloned())?
-                    }
-                    AttributeValue::ArrI64(v) => {
-                        attrs_writer.append_arr_i64_from_iter(None, v.iter().cloned())?
-                    }
-                    AttributeValue::ArrF32(v) => {
-                        attrs_writer.append_arr_f32_from_iter(None, v.iter().cloned())?
-                    }
-                    AttributeValue::ArrF64(v) => {
-                        attrs_writer.append_arr_f64_from_iter(None, v.iter().cloned())?
-                    }
-                    AttributeValue::Binary(v) => attrs_writer.append_binary_direct(v)?,
-                    AttributeValue::String(v) => attrs_writer.append_string_direct(v)?,
+        for event in tree.traverse() {
+            if event.is_open() {
+                let node = event.node();
+                let mut attrs = self.new_node(node.name())?;
+                for (i, value) in node.attributes().iter().enumerate() {
+                    attrs.append_value_with_encoding(value, node.array_encoding(i))?;
                 }
+            } else {
+                self.close_node()?;
             }
+        }
 
-            let mut visit_child = true;
-            current = 'next: loop {
-                if visit_child {
-                    if let Some(child) = current.first_child() {
-                        break 'next child;
-                    }
-                    // No children.
-                    visit_child = false;
-                }
-                self.close_node()?;
-                if let Some(sib) = current.next_sibling() {
-                    break 'next sib;
-                }
-                let parent = current
-                    .parent()
-                    .expect("Should never fail: `current` must not be the root note");
-                if parent.node_id() == tree.root().node_id() {
-                    break 'all;
-                }
-                current = parent;
+        Ok(())
+    }
+
+    /// Copies the current node of the parser and its descendants.
+    ///
+    /// This should be called right after the parser emitted
+    /// [`Event::StartNode`] for the node to copy, and the parser is advanced
+    /// until the corresponding [`Event::EndNode`].
+    ///
+    /// Node attributes are copied byte-for-byte without decoding, since the
+    /// attribute encoding (including compressed arrays) is common among the
+    /// FBX versions supported by the writer.
+    /// Each attribute is written by [`AttributesWriter::append_raw`].
+    /// Node headers are written for the FBX version of the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Copy`] if some attributes of the current node are
+    /// already read, the node header declares inconsistent number and byte
+    /// length of the attributes, or an attribute has an unknown type code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parser has no open nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::pull_parser::v7400::{Event, Parser};
+    /// # use fbxcel::{pull_parser::ParserSource, writer::v7400::binary::{Result, Writer}};
+    /// # fn f<R: ParserSource, W: std::io::Write + std::io::Seek>(
+    /// #     parser: &mut Parser<R>,
+    /// #     writer: &mut Writer<W>,
+    /// # ) -> Result<()> {
+    /// // Copy top-level nodes except for `Takes`.
+    /// loop {
+    ///     match parser.next_event()? {
+    ///         Event::StartNode(start) if start.name() == "Takes" => {}
+    ///         Event::StartNode(_) => {
+    ///             writer.copy_node_from_parser(parser)?;
+    ///             continue;
+    ///         }
+    ///         Event::EndNode => unreachable!("Top-level nodes are skipped or copied"),
+    ///         Event::SkippedNode { .. } => continue,
+    ///         Event::EndFbx(_) => break,
+    ///     }
+    ///     parser.skip_current_node()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_node_from_parser<R: ParserSource>(&mut self, parser: &mut Parser<R>) -> Result<()> {
+        let mut payload = Vec::new();
+        self.copy_node_start_from_parser(parser, &mut payload)?;
+        let mut depth = 1_usize;
+        while depth > 0 {
+            let is_start = match parser.next_event()? {
+                Event::StartNode(_) => true,
+                Event::EndNode => false,
+                Event::SkippedNode { .. } => continue,
+                Event::EndFbx(_) => return Err(CopyError::UnexpectedFbxEnd.into()),
             };
+            if is_start {
+                self.copy_node_start_from_parser(parser, &mut payload)?;
+                depth += 1;
+            } else {
+                self.close_node()?;
+                depth -= 1;
+            }
         }
 
         Ok(())
     }
 
+    /// Creates a new node with the name and the raw attributes of the current
+    /// node of the parser.
+    ///
+    /// `payload` is a buffer for the attribute payloads.
+    fn copy_node_start_from_parser<R: ParserSource>(
+        &mut self,
+        parser: &mut Parser<R>,
+        payload: &mut Vec<u8>,
+    ) -> Result<()> {
+        let name = parser.current_node_name();
+        let (count, bytelen) = parser
+            .current_unread_attributes()
+            .ok_or(CopyError::AttributesAlreadyRead)?;
+        if (count == 0) != (bytelen == 0) {
+            return Err(CopyError::InconsistentAttributes(count, bytelen).into());
+        }
+        let mut attrs = self.new_node(name)?;
+
+        let mut reader = parser.reader().take(bytelen);
+        for _ in 0..count {
+            let ty = read_raw_attribute(&mut reader, payload)?
+                .ok_or(CopyError::InconsistentAttributes(count, bytelen))?;
+            attrs.append_raw(ty, payload)?;
+        }
+        if reader.limit() != 0 {
+            return Err(CopyError::InconsistentAttributes(count, bytelen).into());
+        }
+        parser.finish_current_attributes();
+
+        Ok(())
+    }
+
     /// Finalizes the FBX binary and returns the inner sink.
     ///
     /// You may want to use [`finalize_and_flush()`][`Self::finalize_and_flush()`].
@@ -385,6 +771,72 @@ impl<W: Write + Seek> Writer<W> {
         Ok(self.sink)
     }
 
+    /// Finalizes the FBX binary, flushes the sink, and verifies the written
+    /// data.
+    ///
+    /// The written data is re-read from the beginning of the sink with the
+    /// pull parser, and its structure (node end offsets, node attributes, and
+    /// the footer) is checked.
+    /// Node attributes are decoded but not stored.
+    ///
+    /// Parser warnings are treated as verification errors, except for
+    /// warnings about footer fields (which can be specified by users).
+    ///
+    /// On success, returns the inner sink positioned at the end of the FBX
+    /// data.
+    pub fn finalize_with_verification(mut self, footer: &FbxFooter<'_>) -> Result<W>
+    where
+        W: Read,
+    {
+        self.finalize_impl(footer)?;
+        self.sink.flush()?;
+
+        let end_pos = self.sink.stream_position()?;
+        self.sink.seek(SeekFrom::Start(0))?;
+        verify::verify(&mut self.sink, self.fbx_version, end_pos).map_err(Error::Verification)?;
+        self.sink.seek(SeekFrom::Start(end_pos))?;
+
+        Ok(self.sink)
+    }
+
+    /// Finalizes the FBX binary, and returns the inner sink after flushing,
+    /// with the table of contents.
+    ///
+    /// The entries are in the order the nodes are written (i.e. depth-first
+    /// order).
+    /// If the recording is not enabled by
+    /// [`set_toc_recording`][`Self::set_toc_recording`], the table of
+    /// contents is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+    /// # let sink = std::io::Cursor::new(Vec::new());
+    /// let mut writer = Writer::new(sink, FbxVersion::V7_4)?;
+    /// writer.set_toc_recording(true);
+    /// writer.new_node("Parent")?;
+    /// writer.new_node("Child")?.append_i32(42)?;
+    /// writer.close_node()?;
+    /// writer.close_node()?;
+    ///
+    /// let (_sink, toc) = writer.finalize_with_toc(&Default::default())?;
+    /// let names = toc
+    ///     .iter()
+    ///     .map(|entry| (entry.name(), entry.depth()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, [("Parent", 1), ("Child", 2)]);
+    /// assert!(toc[0].header_offset() < toc[1].header_offset());
+    /// assert!(toc[1].end_offset() <= toc[0].end_offset());
+    /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+    /// ```
+    pub fn finalize_with_toc(mut self, footer: &FbxFooter<'_>) -> Result<(W, Vec<TocEntry>)> {
+        self.finalize_impl(footer)?;
+        self.sink.flush()?;
+
+        Ok((self.sink, self.toc.unwrap_or_default()))
+    }
+
     /// Internal implementation of `finalize()` and `finalize_and_flush()`.
     fn finalize_impl(&mut self, footer: &FbxFooter<'_>) -> Result<()> {
         if !self.open_nodes.is_empty() {
@@ -397,12 +849,16 @@ impl<W: Write + Seek> Writer<W> {
         // Write FBX footer.
         self.sink.write_all(footer.unknown1())?;
         {
+            let current = self.sink.stream_position()?;
+            let expected = current.wrapping_neg() & 0x0f;
             let len = match footer.padding_len {
-                FbxFooterPaddingLength::Default => {
-                    let current = self.sink.stream_position()?;
-                    current.wrapping_neg() & 0x0f
+                FbxFooterPaddingLength::Default => expected,
+                FbxFooterPaddingLength::Forced(len) => {
+                    if u64::from(len) != expected {
+                        self.warn(Warning::InvalidFooterPaddingLength(expected as u8, len))?;
+                    }
+                    u64::from(len)
                 }
-                FbxFooterPaddingLength::Forced(len) => u64::from(len),
             };
             debug!(
                 "Footer padding: spec={:?}, len={:?}",
@@ -419,6 +875,57 @@ impl<W: Write + Seek> Writer<W> {
     }
 }
 
+/// Well-known top-level node names, in the order importers expect.
+const TOPLEVEL_NODE_ORDER: &[&str] = &[
+    "FBXHeaderExtension",
+    "FileId",
+    "CreationTime",
+    "Creator",
+    "GlobalSettings",
+    "Documents",
+    "References",
+    "Definitions",
+    "Objects",
+    "Connections",
+    "Takes",
+];
+
+/// Returns the rank of the given top-level node name in the well-known order.
+fn toplevel_node_rank(name: &str) -> Option<usize> {
+    TOPLEVEL_NODE_ORDER.iter().position(|&v| v == name)
+}
+
+/// Converts the node header validation error into the writer error.
+fn node_header_error(e: NodeHeaderError) -> Error {
+    match e {
+        NodeHeaderError::NameTooLong(len) => Error::NodeNameTooLong(len),
+        NodeHeaderError::EndOffsetTooLarge(v) | NodeHeaderError::BeyondFileEnd(v, _) => {
+            Error::FileTooLarge(v)
+        }
+        NodeHeaderError::TooManyAttributes(v) => Error::TooManyAttributes(v as usize),
+        NodeHeaderError::AttributesTooLong(v) => Error::AttributeTooLong(v as usize),
+    }
+}
+
+impl<W: Write + fmt::Debug> fmt::Debug for Writer<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Writer")
+            .field("sink", &self.sink)
+            .field("fbx_version", &self.fbx_version)
+            .field("open_nodes", &self.open_nodes)
+            .field("toplevel_node_sizes", &self.toplevel_node_sizes)
+            .field("check_toplevel_order", &self.check_toplevel_order)
+            .field("max_toplevel_rank", &self.max_toplevel_rank)
+            .field("compression_buf_len", &self.compression_buf.len())
+            .field(
+                "warning_handler",
+                &self.warning_handler.as_ref().map(Arc::as_ptr),
+            )
+            .field("toc", &self.toc)
+            .finish()
+    }
+}
+
 /// Open node state.
 #[derive(Debug, Clone, Copy)]
 struct OpenNode {
@@ -432,4 +939,155 @@ struct OpenNode {
     has_child: bool,
     /// Whether the attributes are finalized.
     is_attrs_finalized: bool,
+    /// Index of the table of contents entry, if recorded.
+    toc_index: Option<usize>,
+}
+
+/// Byte size of a top-level node written by the writer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ToplevelNodeSize {
+    /// Node name.
+    name: String,
+    /// Offset of the node header.
+    offset: u64,
+    /// Byte length of the node, including the header and the children.
+    bytelen: u64,
+}
+
+impl ToplevelNodeSize {
+    /// Returns the node name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the offset of the node header.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the byte length of the node, including the node header,
+    /// attributes, children, and the node end marker.
+    #[inline]
+    #[must_use]
+    pub fn bytelen(&self) -> u64 {
+        self.bytelen
+    }
+}
+
+/// Table of contents entry of a node written by the writer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TocEntry {
+    /// Node name.
+    name: String,
+    /// Depth of the node.
+    depth: usize,
+    /// Offset of the node header.
+    header_offset: u64,
+    /// End offset of the node.
+    end_offset: u64,
+}
+
+impl TocEntry {
+    /// Returns the node name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the depth of the node.
+    ///
+    /// Top-level nodes have depth 1.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the offset of the node header.
+    #[inline]
+    #[must_use]
+    pub fn header_offset(&self) -> u64 {
+        self.header_offset
+    }
+
+    /// Returns the end offset of the node.
+    ///
+    /// "End offset" means the next byte of the last byte of the node,
+    /// including its children and the node end marker.
+    #[inline]
+    #[must_use]
+    pub fn end_offset(&self) -> u64 {
+        self.end_offset
+    }
+}
+
+/// Reads the type code and the payload of an attribute into `payload`.
+///
+/// Returns `Ok(None)` if the attribute does not fit in the rest of the
+/// reader.
+fn read_raw_attribute<R: io::Read>(
+    reader: &mut io::Take<R>,
+    payload: &mut Vec<u8>,
+) -> Result<Option<AttributeType>> {
+    /// Appends `len` bytes read from the reader to the payload.
+    ///
+    /// Returns `Ok(false)` if the rest of the reader is shorter than `len`.
+    fn read_bytes<R: io::Read>(
+        reader: &mut io::Take<R>,
+        payload: &mut Vec<u8>,
+        len: u64,
+    ) -> io::Result<bool> {
+        if len > reader.limit() {
+            return Ok(false);
+        }
+        let start = payload.len();
+        payload.resize(start + len as usize, 0);
+        reader.read_exact(&mut payload[start..])?;
+        Ok(true)
+    }
+
+    let mut type_code = [0; 1];
+    if reader.limit() == 0 {
+        return Ok(None);
+    }
+    reader.read_exact(&mut type_code)?;
+    let ty = AttributeType::from_type_code(type_code[0])
+        .ok_or(CopyError::InvalidAttributeTypeCode(type_code[0]))?;
+
+    // Length of the fixed-size part, and the position of the byte length of
+    // the variable-size part in it.
+    let (header_len, bytelen_pos) = match ty {
+        AttributeType::Bool => (1, None),
+        AttributeType::I16 => (2, None),
+        AttributeType::I32 | AttributeType::F32 => (4, None),
+        AttributeType::I64 | AttributeType::F64 => (8, None),
+        AttributeType::Binary | AttributeType::String => (4, Some(0)),
+        AttributeType::ArrBool
+        | AttributeType::ArrI32
+        | AttributeType::ArrI64
+        | AttributeType::ArrF32
+        | AttributeType::ArrF64 => (ArrayAttributeHeader::BYTELEN as u64, Some(8)),
+    };
+    payload.clear();
+    if !read_bytes(reader, payload, header_len)? {
+        return Ok(None);
+    }
+    if let Some(pos) = bytelen_pos {
+        let bytelen = u32::from_le_bytes([
+            payload[pos],
+            payload[pos + 1],
+            payload[pos + 2],
+            payload[pos + 3],
+        ]);
+        if !read_bytes(reader, payload, u64::from(bytelen))? {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(ty))
 }
@@ -6,11 +6,20 @@ use std::{
 };
 
 use crate::{
-    low::v7400::{ArrayAttributeEncoding, ArrayAttributeHeader, AttributeType},
-    writer::v7400::binary::{Error, Result, Writer},
+    low::v7400::{
+        ArrayAttributeEncoding, ArrayAttributeHeader, AttributeType, AttributeValue,
+        SpecialAttributeHeader,
+    },
+    writer::v7400::binary::{Error, Result, Warning, Writer},
 };
 
+#[cfg(feature = "ndarray")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "ndarray")))]
+pub use self::ndarray_array::ElementOrder;
+
 mod array;
+#[cfg(feature = "ndarray")]
+mod ndarray_array;
 
 /// A dummy type for impossible error.
 pub(crate) enum Never {}
@@ -73,6 +82,24 @@ impl IntoBytes for f64 {
 /// Node attributes writer.
 ///
 /// See [module documentation](index.html) for usage.
+///
+/// # Attributes and children
+///
+/// FBX binary requires the node attributes to precede the child nodes.
+/// Attributes of a node are finalized when its first child is created (or
+/// when it is closed), and `AttributesWriter` borrows the writer exclusively,
+/// so attributes cannot be appended to a node after its children are
+/// started.
+///
+/// ```compile_fail
+/// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+/// let mut parent_attrs = writer.new_node("Parent")?;
+/// writer.new_node("Child")?;
+/// // Error: `writer` is already borrowed by `parent_attrs`.
+/// parent_attrs.append_i32(42)?;
+/// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+/// ```
 pub struct AttributesWriter<'a, W: Write> {
     /// Inner writer.
     writer: &'a mut Writer<W>,
@@ -89,8 +116,8 @@ macro_rules! impl_single_attr_append {
             pub fn $method(&mut self, v: $ty) -> Result<()> {
                 self.update_node_header()?;
                 self.write_type_code(AttributeType::$variant)?;
-                v.call_with_le_bytes(|bytes| self.writer.sink().write_all(bytes))
-                    .map_err(Into::into)
+                v.call_with_le_bytes(|bytes| self.writer.sink().write_all(bytes))?;
+                self.writer.check_current_offset()
             }
         )*
     }
@@ -155,6 +182,25 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
         self.writer.sink()
     }
 
+    /// Reports the warning to the warning handler of the writer.
+    #[inline]
+    pub(crate) fn warn(&mut self, warning: Warning) -> Result<()> {
+        self.writer.warn(warning)
+    }
+
+    /// Takes the buffer for compressed data from the writer.
+    #[inline]
+    #[must_use]
+    pub(crate) fn take_compression_buf(&mut self) -> Vec<u8> {
+        self.writer.take_compression_buf()
+    }
+
+    /// Returns the buffer for compressed data to the writer for reuse.
+    #[inline]
+    pub(crate) fn restore_compression_buf(&mut self, buf: Vec<u8>) {
+        self.writer.restore_compression_buf(buf)
+    }
+
     /// Writes the given attribute type as type code.
     fn write_type_code(&mut self, ty: AttributeType) -> Result<()> {
         self.writer
@@ -165,6 +211,10 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
 
     /// Updates the node header.
     fn update_node_header(&mut self) -> Result<()> {
+        assert!(
+            !self.writer.is_current_attrs_finalized(),
+            "Attributes should not be appended after they are finalized"
+        );
         let node_header = self
             .writer
             .current_node_header()
@@ -198,7 +248,7 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
     /// Writes the given array attribute header.
     #[inline]
     fn write_array_header(&mut self, header: &ArrayAttributeHeader) -> Result<()> {
-        array::write_array_header(self.writer.sink(), header).map_err(Into::into)
+        header.write_to(self.writer.sink()).map_err(Into::into)
     }
 
     /// Writes some headers for an array attibute, and returns header position.
@@ -229,6 +279,7 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
     fn finalize_array(&mut self, header_pos: u64, header: &ArrayAttributeHeader) -> Result<()> {
         // Write real array header.
         let end_pos = self.writer.sink().stream_position()?;
+        self.writer.check_offset(end_pos)?;
         self.writer.sink().seek(SeekFrom::Start(header_pos))?;
         self.write_array_header(header)?;
         self.writer.sink().seek(SeekFrom::Start(end_pos))?;
@@ -278,7 +329,7 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
 
         // Write special attribute header (dummy).
         let header_pos = self.writer.sink().stream_position()?;
-        self.writer.sink().write_all(&0u32.to_le_bytes())?;
+        SpecialAttributeHeader { bytelen: 0 }.write_to(self.writer.sink())?;
 
         Ok(header_pos)
     }
@@ -292,13 +343,63 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
 
         // Write real special attribute header.
         let end_pos = self.writer.sink().stream_position()?;
+        self.writer.check_offset(end_pos)?;
         self.writer.sink().seek(SeekFrom::Start(header_pos))?;
-        self.writer.sink().write_all(&bytelen.to_le_bytes())?;
+        SpecialAttributeHeader { bytelen }.write_to(self.writer.sink())?;
         self.writer.sink().seek(SeekFrom::Start(end_pos))?;
 
         Ok(())
     }
 
+    /// Writes a pre-encoded attribute as is.
+    ///
+    /// The payload is the attribute data following the type code, in the
+    /// FBX binary layout:
+    ///
+    /// * Single values: little-endian bytes of the value (`b'Y'` or `b'T'`
+    ///   for booleans).
+    /// * Arrays: 12-byte array header (number of elements, encoding, and byte
+    ///   length of the elements, as little-endian `u32`s) followed by the
+    ///   (possibly compressed) elements.
+    /// * Binary and string: 4-byte length (little-endian `u32`) followed by
+    ///   the bytes.
+    ///
+    /// This is useful to copy attributes without decoding and re-encoding
+    /// them, and compressed arrays are written byte-for-byte.
+    ///
+    /// Only the lengths in the payload are validated, and
+    /// [`Error::InvalidRawAttribute`] is returned if they are inconsistent.
+    /// The contents (such as compressed data and UTF-8 strings) are not
+    /// checked, so the caller is responsible to provide valid payloads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+    /// use fbxcel::low::v7400::AttributeType;
+    ///
+    /// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    /// let mut attrs = writer.new_node("Node")?;
+    /// attrs.append_raw(AttributeType::I32, &42i32.to_le_bytes())?;
+    /// attrs.append_raw(AttributeType::String, b"\x05\x00\x00\x00Hello")?;
+    /// // Inconsistent length.
+    /// assert!(attrs.append_raw(AttributeType::Binary, b"\x05\x00\x00\x00abc").is_err());
+    /// # writer.close_node()?;
+    /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+    /// ```
+    pub fn append_raw(&mut self, ty: AttributeType, payload: &[u8]) -> Result<()> {
+        if !is_valid_raw_payload(ty, payload) {
+            return Err(Error::InvalidRawAttribute(ty, payload.len()));
+        }
+        self.update_node_header()?;
+        self.write_type_code(ty)?;
+        self.writer.sink().write_all(payload)?;
+        let end_pos = self.writer.sink().stream_position()?;
+        self.writer.check_offset(end_pos)?;
+
+        Ok(())
+    }
+
     /// Writes a binary attribute.
     pub fn append_binary_direct(&mut self, binary: &[u8]) -> Result<()> {
         let header_pos = self.initialize_special(AttributeType::Binary)?;
@@ -426,4 +527,137 @@ impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
 
         Ok(())
     }
+
+    /// Writes the given attribute value.
+    ///
+    /// Array values are written with the default encoding.
+    #[inline]
+    pub fn append_value(&mut self, value: &AttributeValue) -> Result<()> {
+        self.append_value_with_encoding(value, None)
+    }
+
+    /// Writes the given attribute value with the given array encoding.
+    ///
+    /// `encoding` is used only for array values, and ignored for others.
+    pub fn append_value_with_encoding(
+        &mut self,
+        value: &AttributeValue,
+        encoding: impl Into<Option<ArrayAttributeEncoding>>,
+    ) -> Result<()> {
+        let encoding = encoding.into();
+        match value {
+            AttributeValue::Bool(v) => self.append_bool(*v),
+            AttributeValue::I16(v) => self.append_i16(*v),
+            AttributeValue::I32(v) => self.append_i32(*v),
+            AttributeValue::I64(v) => self.append_i64(*v),
+            AttributeValue::F32(v) => self.append_f32(*v),
+            AttributeValue::F64(v) => self.append_f64(*v),
+            AttributeValue::ArrBool(v) => {
+                self.append_arr_bool_from_iter(encoding, v.iter().cloned())
+            }
+            AttributeValue::ArrI32(v) => self.append_arr_i32_from_iter(encoding, v.iter().cloned()),
+            AttributeValue::ArrI64(v) => self.append_arr_i64_from_iter(encoding, v.iter().cloned()),
+            AttributeValue::ArrF32(v) => self.append_arr_f32_from_iter(encoding, v.iter().cloned()),
+            AttributeValue::ArrF64(v) => self.append_arr_f64_from_iter(encoding, v.iter().cloned()),
+            AttributeValue::Binary(v) => self.append_binary_direct(v),
+            AttributeValue::String(v) => self.append_string_direct(v),
+        }
+    }
+
+    /// Writes the given attribute values.
+    ///
+    /// Array values are written with the default encoding.
+    pub fn append_values<'v>(
+        &mut self,
+        values: impl IntoIterator<Item = &'v AttributeValue>,
+    ) -> Result<()> {
+        values
+            .into_iter()
+            .try_for_each(|value| self.append_value(value))
+    }
+
+    /// Writes the given 4x4 matrix as an `f64` array attribute.
+    ///
+    /// The matrix should be in column-major order, as FBX stores matrices
+    /// (for example, `Matrix` nodes in bind poses): elements `12..15` are
+    /// the translation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+    /// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    /// // Translation by (1, 2, 3).
+    /// let matrix = [
+    ///     1.0, 0.0, 0.0, 0.0,
+    ///     0.0, 1.0, 0.0, 0.0,
+    ///     0.0, 0.0, 1.0, 0.0,
+    ///     1.0, 2.0, 3.0, 1.0,
+    /// ];
+    /// writer.new_node("Matrix")?.append_matrix4x4(&matrix)?;
+    /// # writer.close_node()?;
+    /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+    /// ```
+    #[inline]
+    pub fn append_matrix4x4(&mut self, matrix: &[f64; 16]) -> Result<()> {
+        self.append_arr_f64_from_iter(None, matrix.iter().copied())
+    }
+
+    /// Writes the given 3D vector as an `f64` array attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+    /// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    /// writer.new_node("Position")?.append_vec3(&[1.0, 2.0, 3.0])?;
+    /// # writer.close_node()?;
+    /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+    /// ```
+    #[inline]
+    pub fn append_vec3(&mut self, v: &[f64; 3]) -> Result<()> {
+        self.append_arr_f64_from_iter(None, v.iter().copied())
+    }
+}
+
+/// Checks whether the lengths in the given raw attribute payload are
+/// consistent.
+fn is_valid_raw_payload(ty: AttributeType, payload: &[u8]) -> bool {
+    /// Reads a little-endian `u32` at the given position.
+    fn read_u32(payload: &[u8], pos: usize) -> Option<u32> {
+        let bytes = payload.get(pos..(pos + 4))?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    let elem_size = match ty {
+        AttributeType::Bool => return payload.len() == 1,
+        AttributeType::I16 => return payload.len() == 2,
+        AttributeType::I32 | AttributeType::F32 => return payload.len() == 4,
+        AttributeType::I64 | AttributeType::F64 => return payload.len() == 8,
+        AttributeType::Binary | AttributeType::String => {
+            return read_u32(payload, 0).map_or(false, |len| len as usize == payload.len() - 4);
+        }
+        AttributeType::ArrBool => 1,
+        AttributeType::ArrI32 | AttributeType::ArrF32 => 4,
+        AttributeType::ArrI64 | AttributeType::ArrF64 => 8,
+    };
+    let header_len = ArrayAttributeHeader::BYTELEN;
+    let (count, encoding, bytelen) = match (
+        read_u32(payload, 0),
+        read_u32(payload, 4),
+        read_u32(payload, 8),
+    ) {
+        (Some(count), Some(encoding), Some(bytelen)) => (count, encoding, bytelen),
+        _ => return false,
+    };
+    if bytelen as usize != payload.len() - header_len {
+        return false;
+    }
+    match encoding {
+        // Direct.
+        0 => u64::from(count) * elem_size == u64::from(bytelen),
+        // Zlib.
+        1 => true,
+        _ => false,
+    }
 }
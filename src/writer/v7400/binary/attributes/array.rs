@@ -8,7 +8,7 @@ use std::{
 use crate::{
     low::v7400::{ArrayAttributeEncoding, ArrayAttributeHeader, AttributeType},
     writer::v7400::binary::{
-        attributes::IntoBytes, AttributesWriter, CompressionError, Error, Result,
+        attributes::IntoBytes, AttributesWriter, CompressionError, Error, Result, Warning,
     },
 };
 
@@ -54,16 +54,85 @@ where
     Ok(elements_count)
 }
 
-/// Writes the given array attribute header.
-pub(crate) fn write_array_header(
-    mut writer: impl Write,
-    header: &ArrayAttributeHeader,
-) -> io::Result<()> {
-    writer.write_all(&header.elements_count.to_le_bytes())?;
-    writer.write_all(&header.encoding.to_u32().to_le_bytes())?;
-    writer.write_all(&header.bytelen.to_le_bytes())?;
+/// Compresses array elements and writes them into the given writer.
+///
+/// The compressed data is written to the writer through `write_buf`.
+/// Note that only the buffer can be reused, since `libflate` does not support
+/// resetting the encoder.
+fn write_compressed_elements_result_iter<T, E>(
+    writer: impl Write,
+    write_buf: &mut [u8],
+    iter: impl IntoIterator<Item = std::result::Result<T, E>>,
+) -> Result<u32>
+where
+    T: IntoBytes,
+    E: Into<Error>,
+{
+    let mut sink = libflate::zlib::Encoder::new(PooledBufWriter::new(writer, write_buf))?;
+    let count = write_elements_result_iter(&mut sink, iter)?;
+    sink.finish()
+        .into_result()
+        .map_err(CompressionError::Zlib)?
+        .flush_buf()?;
 
-    Ok(())
+    Ok(count)
+}
+
+/// Buffered writer with a borrowed buffer.
+///
+/// Unlike [`std::io::BufWriter`], this does not own the buffer, so that the
+/// buffer can be reused among writers.
+/// Buffered data is not written on drop, so [`flush_buf`][`Self::flush_buf`]
+/// should be called explicitly.
+#[derive(Debug)]
+struct PooledBufWriter<'b, W> {
+    /// Inner writer.
+    inner: W,
+    /// Buffer.
+    buf: &'b mut [u8],
+    /// Length of the buffered data.
+    len: usize,
+}
+
+impl<'b, W: Write> PooledBufWriter<'b, W> {
+    /// Creates a new `PooledBufWriter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is empty.
+    #[inline]
+    #[must_use]
+    fn new(inner: W, buf: &'b mut [u8]) -> Self {
+        assert!(!buf.is_empty(), "Write buffer should not be empty");
+        Self { inner, buf, len: 0 }
+    }
+
+    /// Writes the buffered data to the inner writer.
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let len = std::mem::replace(&mut self.len, 0);
+        self.inner.write_all(&self.buf[..len])
+    }
+}
+
+impl<W: Write> Write for PooledBufWriter<'_, W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.len == self.buf.len() {
+            self.flush_buf()?;
+        }
+        // Bypass the buffer for large writes.
+        if self.len == 0 && data.len() >= self.buf.len() {
+            return self.inner.write(data);
+        }
+        let len = std::cmp::min(self.buf.len() - self.len, data.len());
+        self.buf[self.len..(self.len + len)].copy_from_slice(&data[..len]);
+        self.len += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
 }
 
 /// Writes the given array attribute.
@@ -82,16 +151,18 @@ pub(crate) fn write_array_attr_result_iter<W: Write + Seek, T: IntoBytes, E: Int
     let elements_count = match encoding {
         ArrayAttributeEncoding::Direct => write_elements_result_iter(writer.sink(), iter)?,
         ArrayAttributeEncoding::Zlib => {
-            let mut sink = libflate::zlib::Encoder::new(writer.sink())?;
-            let count = write_elements_result_iter(&mut sink, iter)?;
-            sink.finish()
-                .into_result()
-                .map_err(CompressionError::Zlib)?;
-            count
+            // Return the buffer to the writer even on error.
+            let mut buf = writer.take_compression_buf();
+            let res = write_compressed_elements_result_iter(writer.sink(), &mut buf, iter);
+            writer.restore_compression_buf(buf);
+            res?
         }
     };
     let end_pos = writer.sink().stream_position()?;
     let bytelen = end_pos - start_pos;
+    if elements_count == 0 {
+        writer.warn(Warning::EmptyArrayAttribute)?;
+    }
 
     // Calculate header fields.
     let bytelen = u32::try_from(bytelen).map_err(|_| Error::AttributeTooLong(bytelen as usize))?;
@@ -0,0 +1,96 @@
+//! Array attributes from `ndarray` arrays.
+
+use std::io::{Seek, Write};
+
+use ndarray::{ArrayBase, Data, Dimension};
+
+use crate::{
+    low::v7400::ArrayAttributeEncoding,
+    writer::v7400::binary::{AttributesWriter, Result},
+};
+
+/// Order of elements to write multi-dimensional arrays in.
+///
+/// FBX itself has no notion of dimensions, and the order depends on the
+/// node.
+/// For example, matrices (such as `Matrix` nodes in bind poses) are stored
+/// in column-major order, while vertex arrays are stored as rows of
+/// coordinates (i.e. row-major order for `[vertex, xyz]` shaped arrays).
+///
+/// The order is about the logical indices, and independent of the memory
+/// layout of the source array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ElementOrder {
+    /// Row-major order (C order), i.e. the last index changes fastest.
+    RowMajor,
+    /// Column-major order (Fortran order), i.e. the first index changes
+    /// fastest.
+    ColumnMajor,
+}
+
+/// Implement `append_*_from_array` methods.
+macro_rules! impl_arr_from_array {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident: $ty_elem:ty => $from_iter:ident;
+    )*) => {$(
+        $(#[$meta])*
+        pub fn $name<S, D>(
+            &mut self,
+            encoding: impl Into<Option<ArrayAttributeEncoding>>,
+            array: &ArrayBase<S, D>,
+            order: ElementOrder,
+        ) -> Result<()>
+        where
+            S: Data<Elem = $ty_elem>,
+            D: Dimension,
+        {
+            match order {
+                ElementOrder::RowMajor => self.$from_iter(encoding, array.iter().copied()),
+                // Iterating the transposed view in logical order visits the
+                // elements in column-major order of the original array.
+                ElementOrder::ColumnMajor => self.$from_iter(encoding, array.t().iter().copied()),
+            }
+        }
+    )*}
+}
+
+impl<'a, W: Write + Seek> AttributesWriter<'a, W> {
+    impl_arr_from_array! {
+        /// Writes the given array as an `i32` array attribute in the given
+        /// element order.
+        append_arr_i32_from_array: i32 => append_arr_i32_from_iter;
+        /// Writes the given array as an `i64` array attribute in the given
+        /// element order.
+        append_arr_i64_from_array: i64 => append_arr_i64_from_iter;
+        /// Writes the given array as an `f32` array attribute in the given
+        /// element order.
+        append_arr_f32_from_array: f32 => append_arr_f32_from_iter;
+        /// Writes the given array as an `f64` array attribute in the given
+        /// element order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+        /// use fbxcel::writer::v7400::binary::ElementOrder;
+        ///
+        /// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+        /// // Translation by (1, 2, 3), in the usual mathematical notation.
+        /// let matrix = ndarray::arr2(&[
+        ///     [1.0, 0.0, 0.0, 1.0],
+        ///     [0.0, 1.0, 0.0, 2.0],
+        ///     [0.0, 0.0, 1.0, 3.0],
+        ///     [0.0, 0.0, 0.0, 1.0],
+        /// ]);
+        /// // FBX stores matrices in column-major order.
+        /// writer
+        ///     .new_node("Matrix")?
+        ///     .append_arr_f64_from_array(None, &matrix, ElementOrder::ColumnMajor)?;
+        /// # writer.close_node()?;
+        /// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+        /// ```
+        append_arr_f64_from_array: f64 => append_arr_f64_from_iter;
+    }
+}
@@ -2,7 +2,12 @@
 
 use std::{error, fmt, io};
 
-use crate::low::FbxVersion;
+use crate::{
+    low::{v7400::AttributeType, FbxVersion},
+    pull_parser,
+    writer::v7400::binary::Warning,
+    ErrorCode,
+};
 
 /// Write result.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -14,32 +19,86 @@ pub enum Error {
     AttributeTooLong(usize),
     /// Compression error.
     Compression(CompressionError),
+    /// Node cannot be copied from a parser.
+    Copy(CopyError),
     /// File is too large.
+    ///
+    /// FBX 7.4 and earlier cannot have nodes beyond 4 GiB.
+    /// The writer reports this as soon as an attribute or a node crosses the
+    /// limit, so consider using FBX 7.5 or later for such large data.
     FileTooLarge(u64),
+    /// Node name is rejected by the node name policy.
+    InvalidNodeName(String),
+    /// Raw attribute payload is inconsistent with the attribute type.
+    ///
+    /// This contains the attribute type and the byte length of the payload.
+    InvalidRawAttribute(AttributeType, usize),
     /// I/O error.
     Io(io::Error),
     /// There are no nodes to close.
     NoNodesToClose,
     /// Node name is too long.
     NodeNameTooLong(usize),
+    /// Parser error on copying nodes from a parser.
+    Parser(pull_parser::Error),
     /// Too many array attribute elements.
     TooManyArrayAttributeElements(usize),
     /// Too many attributes.
     TooManyAttributes(usize),
+    /// Top-level node is written out of the well-known order.
+    ///
+    /// This contains the node name, and the name of an already written node
+    /// which should follow it.
+    ToplevelNodeOrder(String, String),
     /// There remains unclosed nodes.
     UnclosedNode(usize),
     /// Unsupported FBX version.
     UnsupportedFbxVersion(FbxVersion),
     /// User-defined error.
     UserDefined(Box<dyn std::error::Error + 'static>),
+    /// Verification of the written data failed.
+    Verification(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// Critical warning.
+    Warning(Warning),
+}
+
+impl Error {
+    /// Returns the machine-readable error code.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::AttributeTooLong(_) => ErrorCode::AttributeTooLong,
+            Error::Compression(_) => ErrorCode::CompressionFailed,
+            Error::Copy(_) => ErrorCode::CopyFailed,
+            Error::FileTooLarge(_) => ErrorCode::FileTooLarge,
+            Error::InvalidNodeName(_) => ErrorCode::InvalidNodeName,
+            Error::InvalidRawAttribute(..) => ErrorCode::InvalidRawAttribute,
+            Error::Io(_) => ErrorCode::Io,
+            Error::NoNodesToClose => ErrorCode::NoNodesToClose,
+            Error::NodeNameTooLong(_) => ErrorCode::NodeNameTooLong,
+            Error::Parser(e) => e.code(),
+            Error::TooManyArrayAttributeElements(_) => ErrorCode::TooManyArrayAttributeElements,
+            Error::TooManyAttributes(_) => ErrorCode::TooManyAttributes,
+            Error::ToplevelNodeOrder(..) => ErrorCode::ToplevelNodeOrder,
+            Error::UnclosedNode(_) => ErrorCode::UnclosedNode,
+            Error::UnsupportedFbxVersion(_) => ErrorCode::WriterUnsupportedFbxVersion,
+            Error::UserDefined(_) => ErrorCode::UserDefined,
+            Error::Verification(_) => ErrorCode::VerificationFailed,
+            Error::Warning(_) => ErrorCode::CriticalWarning,
+        }
+    }
 }
 
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Error::Compression(e) => Some(e),
+            Error::Copy(e) => Some(e),
             Error::Io(e) => Some(e),
+            Error::Parser(e) => Some(e),
             Error::UserDefined(e) => Some(&**e),
+            Error::Verification(e) => Some(&**e),
+            Error::Warning(e) => Some(e),
             _ => None,
         }
     }
@@ -50,19 +109,38 @@ impl fmt::Display for Error {
         match self {
             Error::AttributeTooLong(v) => write!(f, "Node attribute is too long: {} bytes", v),
             Error::Compression(e) => write!(f, "Compression error: {}", e),
-            Error::FileTooLarge(v) => write!(f, "File is too large: {} bytes", v),
+            Error::Copy(e) => write!(f, "Failed to copy a node: {}", e),
+            Error::FileTooLarge(v) => write!(
+                f,
+                "File is too large: {} bytes (FBX 7.5 or later is required)",
+                v
+            ),
+            Error::InvalidNodeName(v) => write!(f, "Invalid node name: {:?}", v),
+            Error::InvalidRawAttribute(ty, len) => write!(
+                f,
+                "Invalid raw attribute payload: type={}, len={} bytes",
+                ty, len
+            ),
             Error::Io(e) => write!(f, "I/O error: {}", e),
             Error::NoNodesToClose => write!(f, "There are no nodes to close"),
             Error::NodeNameTooLong(v) => write!(f, "Node name is too long: {} bytes", v),
+            Error::Parser(e) => write!(f, "Parser error: {}", e),
             Error::TooManyArrayAttributeElements(v) => write!(
                 f,
                 "Too many array elements for a single node attribute: count={}",
                 v
             ),
             Error::TooManyAttributes(v) => write!(f, "Too many attributes: count={}", v),
+            Error::ToplevelNodeOrder(name, prev) => write!(
+                f,
+                "Top-level node `{}` should be written before `{}`",
+                name, prev
+            ),
             Error::UnclosedNode(v) => write!(f, "There remains unclosed nodes: depth={}", v),
             Error::UnsupportedFbxVersion(v) => write!(f, "Unsupported FBX version: {:?}", v),
             Error::UserDefined(e) => write!(f, "User-defined error: {}", e),
+            Error::Verification(e) => write!(f, "Verification failed: {}", e),
+            Error::Warning(e) => write!(f, "Critical warning: {}", e),
         }
     }
 }
@@ -74,6 +152,20 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<pull_parser::Error> for Error {
+    #[inline]
+    fn from(e: pull_parser::Error) -> Self {
+        Error::Parser(e)
+    }
+}
+
+impl From<Warning> for Error {
+    #[inline]
+    fn from(e: Warning) -> Self {
+        Error::Warning(e)
+    }
+}
+
 impl From<CompressionError> for Error {
     #[inline]
     fn from(e: CompressionError) -> Self {
@@ -81,6 +173,13 @@ impl From<CompressionError> for Error {
     }
 }
 
+impl From<CopyError> for Error {
+    #[inline]
+    fn from(e: CopyError) -> Self {
+        Error::Copy(e)
+    }
+}
+
 /// Compression error.
 #[derive(Debug)]
 pub enum CompressionError {
@@ -105,3 +204,40 @@ impl fmt::Display for CompressionError {
         }
     }
 }
+
+/// Error on copying nodes from a parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CopyError {
+    /// Some attributes of the node are already read.
+    AttributesAlreadyRead,
+    /// The number and the byte length of the attributes are inconsistent.
+    ///
+    /// This contains the number and the byte length.
+    InconsistentAttributes(u64, u64),
+    /// Unknown attribute type code.
+    InvalidAttributeTypeCode(u8),
+    /// FBX data ended inside the node.
+    UnexpectedFbxEnd,
+}
+
+impl error::Error for CopyError {}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyError::AttributesAlreadyRead => {
+                write!(f, "Attributes of the node are already read")
+            }
+            CopyError::InconsistentAttributes(count, bytelen) => write!(
+                f,
+                "Number and byte length of the attributes are inconsistent: count={}, bytelen={}",
+                count, bytelen
+            ),
+            CopyError::InvalidAttributeTypeCode(code) => {
+                write!(f, "Invalid attribute type code: {:?}", code)
+            }
+            CopyError::UnexpectedFbxEnd => write!(f, "FBX data ended inside the node"),
+        }
+    }
+}
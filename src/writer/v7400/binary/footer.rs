@@ -1,5 +1,20 @@
 //! FBX footer.
 
+use crate::low::{v7400::FbxFooter as ParsedFbxFooter, FbxVersion};
+
+/// Default value of the first unknown field.
+const DEFAULT_UNKNOWN1: [u8; 16] = [
+    0xf0, 0xb1, 0xa2, 0x03, 0xd4, 0xc5, 0xd6, 0x67, 0xb8, 0x79, 0xfa, 0x8b, 0x1c, 0xfd, 0x2e, 0x7f,
+];
+
+/// Default value of the second unknown field.
+const DEFAULT_UNKNOWN2: [u8; 4] = [0; 4];
+
+/// Default value of the third unknown field.
+const DEFAULT_UNKNOWN3: [u8; 16] = [
+    0xf8, 0x5a, 0x8c, 0x6a, 0xde, 0xf5, 0xd9, 0x7e, 0xec, 0xe9, 0x0c, 0xe3, 0x75, 0x8f, 0x29, 0x0b,
+];
+
 /// FBX footer padding length.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FbxFooterPaddingLength {
@@ -16,6 +31,22 @@ impl Default for FbxFooterPaddingLength {
     }
 }
 
+/// Padding length handling on writing a footer read from existing data.
+///
+/// See [`FbxFooter::from_parsed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FooterPaddingRewrite {
+    /// Writes the same padding length as the source, even if it is wrong.
+    ///
+    /// This keeps the footer byte-faithful.
+    /// If the length is wrong for the new data, the writer reports
+    /// [`Warning::InvalidFooterPaddingLength`][`super::Warning::InvalidFooterPaddingLength`].
+    Preserve,
+    /// Writes the correct padding length.
+    Correct,
+}
+
 /// FBX 7.4 footer.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FbxFooter<'a> {
@@ -33,6 +64,8 @@ pub struct FbxFooter<'a> {
     /// `padding_len >= 0 && padding <= 15` should hold.
     ///
     /// Note that third party exporter will not use correct padding length.
+    /// If the forced length is not correct, the writer reports
+    /// [`Warning::InvalidFooterPaddingLength`][`super::Warning::InvalidFooterPaddingLength`].
     pub padding_len: FbxFooterPaddingLength,
     /// Unknown 4-bytes data.
     ///
@@ -46,36 +79,122 @@ pub struct FbxFooter<'a> {
 }
 
 impl<'a> FbxFooter<'a> {
+    /// Creates a footer with the canonical field values for the given FBX
+    /// version.
+    ///
+    /// Unlike [`Default::default`], the unknown fields are explicitly set,
+    /// so the resulting value shows what will be written.
+    /// All FBX 7.x versions share the same values; for other versions, this
+    /// returns the default footer.
+    ///
+    /// Fields can be overridden by `with_*` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fbxcel::{low::FbxVersion, writer::v7400::binary::FbxFooter};
+    ///
+    /// let footer = FbxFooter::for_version(FbxVersion::V7_4);
+    /// assert!(footer.unknown1.is_some());
+    /// assert!(footer.unknown3.is_some());
+    ///
+    /// let unknown1 = [0xf0; 16];
+    /// let footer = FbxFooter::for_version(FbxVersion::V7_5).with_unknown1(&unknown1);
+    /// assert_eq!(footer.unknown1, Some(&unknown1));
+    /// assert_eq!(footer.unknown2, Some([0; 4]));
+    /// ```
+    #[must_use]
+    pub fn for_version(fbx_version: FbxVersion) -> Self {
+        match fbx_version.major() {
+            7 => Self {
+                unknown1: Some(&DEFAULT_UNKNOWN1),
+                unknown2: Some(DEFAULT_UNKNOWN2),
+                unknown3: Some(&DEFAULT_UNKNOWN3),
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Creates a footer with the same field values as the given footer read
+    /// by the parser.
+    ///
+    /// The padding length is handled according to `padding`.
+    /// The parser reports wrong padding length as
+    /// [`pull_parser::Warning::InvalidFooterPaddingLength`][`crate::pull_parser::Warning::InvalidFooterPaddingLength`],
+    /// so the option can be chosen based on the warnings.
+    #[must_use]
+    pub fn from_parsed(footer: &'a ParsedFbxFooter, padding: FooterPaddingRewrite) -> Self {
+        let padding_len = match padding {
+            FooterPaddingRewrite::Preserve => FbxFooterPaddingLength::Forced(footer.padding_len),
+            FooterPaddingRewrite::Correct => FbxFooterPaddingLength::Default,
+        };
+        Self {
+            unknown1: Some(&footer.unknown1),
+            padding_len,
+            unknown2: Some(footer.unknown2),
+            unknown3: Some(&footer.unknown3),
+        }
+    }
+
+    /// Sets the first unknown field.
+    #[inline]
+    #[must_use]
+    pub fn with_unknown1(self, unknown1: &'a [u8; 16]) -> Self {
+        Self {
+            unknown1: Some(unknown1),
+            ..self
+        }
+    }
+
+    /// Sets the padding length.
+    #[inline]
+    #[must_use]
+    pub fn with_padding_len(self, padding_len: FbxFooterPaddingLength) -> Self {
+        Self {
+            padding_len,
+            ..self
+        }
+    }
+
+    /// Sets the second unknown field.
+    #[inline]
+    #[must_use]
+    pub fn with_unknown2(self, unknown2: [u8; 4]) -> Self {
+        Self {
+            unknown2: Some(unknown2),
+            ..self
+        }
+    }
+
+    /// Sets the third unknown field.
+    #[inline]
+    #[must_use]
+    pub fn with_unknown3(self, unknown3: &'a [u8; 16]) -> Self {
+        Self {
+            unknown3: Some(unknown3),
+            ..self
+        }
+    }
+
     /// Returns the first unknown field or default.
     #[inline]
     #[must_use]
     pub(crate) fn unknown1(&self) -> &'a [u8; 16] {
-        /// Default value.
-        const DEFAULT: [u8; 16] = [
-            0xf0, 0xb1, 0xa2, 0x03, 0xd4, 0xc5, 0xd6, 0x67, 0xb8, 0x79, 0xfa, 0x8b, 0x1c, 0xfd,
-            0x2e, 0x7f,
-        ];
-        self.unknown1.unwrap_or(&DEFAULT)
+        self.unknown1.unwrap_or(&DEFAULT_UNKNOWN1)
     }
 
     /// Returns the second unknown field or default.
     #[inline]
     #[must_use]
     pub(crate) fn unknown2(&self) -> [u8; 4] {
-        /// Default value.
-        const DEFAULT: [u8; 4] = [0; 4];
-        self.unknown2.unwrap_or(DEFAULT)
+        self.unknown2.unwrap_or(DEFAULT_UNKNOWN2)
     }
 
     /// Returns the third unknown field or default.
     #[inline]
     #[must_use]
     pub(crate) fn unknown3(&self) -> &'a [u8; 16] {
-        /// Default value.
-        const DEFAULT: [u8; 16] = [
-            0xf8, 0x5a, 0x8c, 0x6a, 0xde, 0xf5, 0xd9, 0x7e, 0xec, 0xe9, 0x0c, 0xe3, 0x75, 0x8f,
-            0x29, 0x0b,
-        ];
-        self.unknown3.unwrap_or(&DEFAULT)
+        self.unknown3.unwrap_or(&DEFAULT_UNKNOWN3)
     }
 }
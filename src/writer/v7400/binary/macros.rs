@@ -35,6 +35,23 @@
 /// let _buf = writer.finalize_and_flush(&Default::default())?;
 /// # Ok::<_, Box<dyn std::error::Error>>(())
 /// ```
+///
+/// Integer literals in brackets should have a type suffix (such as `42i64`),
+/// since the attribute type would be ambiguous without it.
+/// Integer literals without suffix are rejected at compile time.
+///
+/// ```compile_fail
+/// # use fbxcel::write_v7400_binary;
+/// use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+/// write_v7400_binary!(
+///     writer=writer,
+///     tree={
+///         Node: [42] {}
+///     },
+/// )?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
 #[macro_export]
 macro_rules! write_v7400_binary {
@@ -75,6 +92,10 @@ macro_rules! write_v7400_binary {
     ) => {{
         let mut _attrs = $writer.new_node(stringify!($name))?;
         $({
+            // Reject integer literals without type suffix, since they would
+            // silently be `i32`.
+            let _integer_literal_attribute_requires_type_suffix: [(); 0] =
+                [(); $crate::macro_support::is_unsuffixed_int_literal(stringify!($attr)) as usize];
             let attr = $attr;
             write_v7400_binary!(@__attr, _attrs, attr.into())?;
         })*
@@ -98,22 +119,157 @@ macro_rules! write_v7400_binary {
     }};
 
     (@__attr, $attrs:ident, $attr:expr) => {{
-        use $crate::low::v7400::AttributeValue::*;
-        match $attr {
-            Bool(v) => $attrs.append_bool(v),
-            I16(v) => $attrs.append_i16(v),
-            I32(v) => $attrs.append_i32(v),
-            I64(v) => $attrs.append_i64(v),
-            F32(v) => $attrs.append_f32(v),
-            F64(v) => $attrs.append_f64(v),
-            ArrBool(v) => $attrs.append_arr_bool_from_iter(None, v),
-            ArrI32(v) => $attrs.append_arr_i32_from_iter(None, v),
-            ArrI64(v) => $attrs.append_arr_i64_from_iter(None, v),
-            ArrF32(v) => $attrs.append_arr_f32_from_iter(None, v),
-            ArrF64(v) => $attrs.append_arr_f64_from_iter(None, v),
-            Binary(v) => $attrs.append_binary_direct(&v),
-            String(v) => $attrs.append_string_direct(&v),
+        let attr: $crate::low::v7400::AttributeValue = $attr;
+        $attrs.append_value(&attr)
+    }};
+}
+
+/// Writes a `Properties70` node with the given properties, and returns the
+/// `fbxcel::writer::v7400::binary::Result<()>`.
+///
+/// Each property is written by the method of
+/// [`PropertiesWriter`][`crate::writer::v7400::binary::PropertiesWriter`]
+/// with the same name, so the leading attributes of `P` nodes are always
+/// correctly shaped.
+/// `color(r, g, b)` is a shorthand for `color_rgb([r, g, b])`.
+///
+/// Enabled by `writer` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::write_v7400_properties70;
+/// use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+///
+/// writer.new_node("Material")?;
+/// write_v7400_properties70!(
+///     writer=writer,
+///     properties={
+///         "DiffuseColor": color(1.0, 0.0, 0.0),
+///         "Opacity": double(0.5),
+///         "Visibility": bool(true),
+///         "ShadingModel": kstring("Phong"),
+///     },
+/// )?;
+/// writer.close_node()?;
+/// let _buf = writer.finalize_and_flush(&Default::default())?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+#[macro_export]
+macro_rules! write_v7400_properties70 {
+    (
+        writer=$writer:expr,
+        properties={$($props:tt)*},
+    ) => {{
+        let mut f = || -> $crate::writer::v7400::binary::Result<()> {
+            let mut _props = $crate::writer::v7400::binary::PropertiesWriter::new(&mut $writer)?;
+            write_v7400_properties70! { @__prop, _props, $($props)* }
+            _props.finish()
+        };
+        f()
+    }};
+
+    (@__prop, $props:ident,) => {};
+    (@__prop, $props:ident, , $($rest:tt)*) => {
+        write_v7400_properties70! { @__prop, $props, $($rest)* }
+    };
+
+    (@__prop, $props:ident,
+        $name:literal: color($r:expr, $g:expr, $b:expr $(,)?)
+        $($rest:tt)*
+    ) => {{
+        $props.color_rgb($name, [$r, $g, $b])?;
+        write_v7400_properties70! { @__prop, $props, $($rest)* }
+    }};
+    (@__prop, $props:ident,
+        $name:literal: $kind:ident($($arg:expr),* $(,)?)
+        $($rest:tt)*
+    ) => {{
+        $props.$kind($name, $($arg),*)?;
+        write_v7400_properties70! { @__prop, $props, $($rest)* }
+    }};
+}
+
+/// Writes a `Connections` node with the given connections, and returns the
+/// `fbxcel::writer::v7400::binary::Result<()>`.
+///
+/// Each connection is written as a `C` node.
+/// `OO parent <- child` connects objects, and
+/// `OP parent <- child @ "Property"` connects the child object to the
+/// property of the parent object.
+/// Object IDs should be single tokens (such as literals, variables, or
+/// expressions in parens), and are written as `i64`.
+///
+/// Enabled by `writer` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel::write_v7400_connections;
+/// use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+///
+/// let (model, curve_node) = (1, 2);
+/// write_v7400_connections!(
+///     writer=writer,
+///     connections={
+///         OO 0 <- model,
+///         OP model <- curve_node @ "Lcl Translation",
+///     },
+/// )?;
+/// let _buf = writer.finalize_and_flush(&Default::default())?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "writer")))]
+#[macro_export]
+macro_rules! write_v7400_connections {
+    (
+        writer=$writer:expr,
+        connections={$($conns:tt)*},
+    ) => {{
+        let mut f = || -> $crate::writer::v7400::binary::Result<()> {
+            let _writer = &mut $writer;
+            _writer.new_node("Connections")?;
+            write_v7400_connections! { @__conn, _writer, $($conns)* }
+            _writer.close_node()
+        };
+        f()
+    }};
+
+    (@__conn, $writer:ident,) => {};
+    (@__conn, $writer:ident, , $($rest:tt)*) => {
+        write_v7400_connections! { @__conn, $writer, $($rest)* }
+    };
+
+    (@__conn, $writer:ident,
+        OO $parent:tt <- $child:tt
+        $($rest:tt)*
+    ) => {{
+        write_v7400_connections!(@__c, $writer, "OO", $parent, $child)?;
+        write_v7400_connections! { @__conn, $writer, $($rest)* }
+    }};
+    (@__conn, $writer:ident,
+        OP $parent:tt <- $child:tt @ $prop:tt
+        $($rest:tt)*
+    ) => {{
+        write_v7400_connections!(@__c, $writer, "OP", $parent, $child, $prop)?;
+        write_v7400_connections! { @__conn, $writer, $($rest)* }
+    }};
+
+    (@__c, $writer:ident, $kind:expr, $parent:expr, $child:expr $(, $prop:expr)?) => {{
+        // IDs may be expressions in parens.
+        #[allow(unused_parens)]
+        let (parent, child): (i64, i64) = ($parent, $child);
+        {
+            let mut attrs = $writer.new_node("C")?;
+            attrs.append_string_direct($kind)?;
+            attrs.append_i64(child)?;
+            attrs.append_i64(parent)?;
+            $(attrs.append_string_direct($prop)?;)?
         }
+        $writer.close_node()
     }};
 }
 
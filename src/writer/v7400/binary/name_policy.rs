@@ -0,0 +1,94 @@
+//! Node name policy.
+
+use std::borrow::Cow;
+
+use crate::writer::v7400::binary::{Error, Result};
+
+/// Policy for node names containing NUL or non-ASCII characters.
+///
+/// Node names with embedded NUL (such as `"Name\u{0}\u{1}Class"` style) are
+/// legitimate in some contexts and corruption in others, and non-ASCII names
+/// may be rejected by some importers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NodeNamePolicy {
+    /// Writes the name as is.
+    ///
+    /// This is the default.
+    Allow,
+    /// Escapes NUL, non-ASCII, and backslash bytes as `\xNN` (where `NN` is
+    /// the lowercase hexadecimal byte value).
+    Escape,
+    /// Returns [`Error::InvalidNodeName`] if the name contains NUL or
+    /// non-ASCII characters.
+    Reject,
+}
+
+impl Default for NodeNamePolicy {
+    #[inline]
+    fn default() -> Self {
+        NodeNamePolicy::Allow
+    }
+}
+
+impl NodeNamePolicy {
+    /// Applies the policy to the given node name.
+    pub(crate) fn apply(self, name: &str) -> Result<Cow<'_, str>> {
+        /// Returns true if the byte should be rejected or escaped.
+        fn is_special(b: u8) -> bool {
+            b == 0 || !b.is_ascii()
+        }
+
+        match self {
+            NodeNamePolicy::Allow => Ok(Cow::Borrowed(name)),
+            NodeNamePolicy::Reject => {
+                if name.bytes().any(is_special) {
+                    Err(Error::InvalidNodeName(name.to_owned()))
+                } else {
+                    Ok(Cow::Borrowed(name))
+                }
+            }
+            NodeNamePolicy::Escape => {
+                if !name.bytes().any(|b| is_special(b) || b == b'\\') {
+                    return Ok(Cow::Borrowed(name));
+                }
+                let mut escaped = String::with_capacity(name.len());
+                for b in name.bytes() {
+                    if is_special(b) || b == b'\\' {
+                        escaped.push_str(&format!("\\x{:02x}", b));
+                    } else {
+                        escaped.push(char::from(b));
+                    }
+                }
+                Ok(Cow::Owned(escaped))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let name = "Name\u{0}\u{1}Class";
+        assert_eq!(NodeNamePolicy::Allow.apply(name).unwrap(), name);
+        assert!(matches!(
+            NodeNamePolicy::Reject.apply(name),
+            Err(Error::InvalidNodeName(_))
+        ));
+        assert_eq!(
+            NodeNamePolicy::Reject.apply("Name\u{1}").unwrap(),
+            "Name\u{1}"
+        );
+        assert_eq!(
+            NodeNamePolicy::Escape.apply(name).unwrap(),
+            "Name\\x00\u{1}Class"
+        );
+        assert_eq!(
+            NodeNamePolicy::Escape.apply("\u{e9}\\").unwrap(),
+            "\\xc3\\xa9\\x5c"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Writer for `Properties70` nodes.
+
+use std::io::{Seek, Write};
+
+use crate::writer::v7400::binary::{AttributesWriter, Result, Writer};
+
+/// Name of the properties node.
+const PROPERTIES70: &str = "Properties70";
+
+/// Name of the property nodes.
+const PROPERTY: &str = "P";
+
+/// Writer for `Properties70` node.
+///
+/// Each property is written as a `P` node, with four leading string
+/// attributes (name, type name, label, and flags) followed by the value
+/// attributes.
+/// Some applications silently drop properties with wrong leading attributes,
+/// so prefer the typed methods such as [`double`][`Self::double`] to
+/// [`property`][`Self::property`].
+///
+/// The `Properties70` node is opened by [`new`][`Self::new`], and should be
+/// closed explicitly by [`finish`][`Self::finish`].
+///
+/// # Examples
+///
+/// ```
+/// use fbxcel::writer::v7400::binary::PropertiesWriter;
+/// # use fbxcel::{low::FbxVersion, writer::v7400::binary::Writer};
+/// # let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+/// # writer.new_node("Model")?;
+/// let mut props = PropertiesWriter::new(&mut writer)?;
+/// props.double("Intensity", 100.0)?;
+/// props.color_rgb("Color", [1.0, 0.5, 0.0])?;
+/// props.bool("CastShadows", true)?;
+/// props.enum_("LightType", 1)?;
+/// props.kstring("Comment", "Written by fbxcel")?;
+/// props.finish()?;
+/// # writer.close_node()?;
+/// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct PropertiesWriter<'a, W: Write> {
+    /// Inner writer.
+    writer: &'a mut Writer<W>,
+}
+
+impl<'a, W: Write + Seek> PropertiesWriter<'a, W> {
+    /// Opens a new `Properties70` node.
+    pub fn new(writer: &'a mut Writer<W>) -> Result<Self> {
+        writer.new_node(PROPERTIES70)?;
+
+        Ok(Self { writer })
+    }
+
+    /// Writes a property with the given leading attributes, and writes the
+    /// value attributes using the given function.
+    pub fn property<F>(
+        &mut self,
+        name: &str,
+        type_name: &str,
+        label: &str,
+        flags: &str,
+        f: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut AttributesWriter<'_, W>) -> Result<()>,
+    {
+        {
+            let mut attrs = self.writer.new_node(PROPERTY)?;
+            attrs.append_string_direct(name)?;
+            attrs.append_string_direct(type_name)?;
+            attrs.append_string_direct(label)?;
+            attrs.append_string_direct(flags)?;
+            f(&mut attrs)?;
+        }
+        self.writer.close_node()
+    }
+
+    /// Writes a `double` property.
+    pub fn double(&mut self, name: &str, value: f64) -> Result<()> {
+        self.property(name, "double", "Number", "", |attrs| {
+            attrs.append_f64(value)
+        })
+    }
+
+    /// Writes a `ColorRGB` property.
+    pub fn color_rgb(&mut self, name: &str, rgb: [f64; 3]) -> Result<()> {
+        self.property(name, "ColorRGB", "Color", "", |attrs| {
+            rgb.iter().try_for_each(|&v| attrs.append_f64(v))
+        })
+    }
+
+    /// Writes a `bool` property.
+    ///
+    /// The value is written as an `i32` attribute (`0` or `1`), as FBX
+    /// exporters do.
+    pub fn bool(&mut self, name: &str, value: bool) -> Result<()> {
+        self.property(name, "bool", "", "", |attrs| {
+            attrs.append_i32(i32::from(value))
+        })
+    }
+
+    /// Writes an `enum` property.
+    pub fn enum_(&mut self, name: &str, value: i32) -> Result<()> {
+        self.property(name, "enum", "", "", |attrs| attrs.append_i32(value))
+    }
+
+    /// Writes a `KString` property.
+    pub fn kstring(&mut self, name: &str, value: &str) -> Result<()> {
+        self.property(name, "KString", "", "", |attrs| {
+            attrs.append_string_direct(value)
+        })
+    }
+
+    /// Closes the `Properties70` node.
+    pub fn finish(self) -> Result<()> {
+        self.writer.close_node()
+    }
+}
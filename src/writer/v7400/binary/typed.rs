@@ -0,0 +1,352 @@
+//! Writer with compile-time node depth tracking.
+
+use std::{
+    error, fmt,
+    io::{Seek, Write},
+    marker::PhantomData,
+};
+
+use crate::{
+    low::FbxVersion,
+    writer::v7400::binary::{AttributesWriter, Error, FbxFooter, Result, Writer},
+};
+
+use self::private::Sealed;
+
+/// Private items to prevent users from implementing [`Depth`] or
+/// reconstructing writers at arbitrary depth.
+mod private {
+    use crate::writer::v7400::binary::Writer;
+
+    /// Conversion from and into the underlying writer.
+    pub trait Sealed<W: std::io::Write>: Sized {
+        /// Wraps the given writer.
+        fn from_writer(writer: Writer<W>) -> Self;
+        /// Unwraps the writer.
+        fn into_writer(self) -> Writer<W>;
+    }
+}
+
+/// Type-level node depth of typed writers.
+///
+/// This is implemented for [`TypedWriter`] (depth 0) and [`NodeWriter`]
+/// (depth of the parent plus one), and cannot be implemented outside of this
+/// crate.
+pub trait Depth<W: Write>: private::Sealed<W> {
+    /// Node depth.
+    const DEPTH: usize;
+}
+
+/// Error of a typed writer operation, with the underlying writer.
+///
+/// The node depth is unknown after a failure (for example, the node may be
+/// already opened when writing its attributes failed), so the underlying
+/// untyped [`Writer`] is returned instead of the typed one.
+///
+/// This can be converted into [`Error`], so `?` can be used in functions
+/// returning [`Result`].
+pub struct TypedWriterError<W: Write> {
+    /// Error.
+    error: Error,
+    /// Underlying writer.
+    ///
+    /// This is boxed to keep the `Result` small.
+    writer: Box<Writer<W>>,
+}
+
+impl<W: Write> TypedWriterError<W> {
+    /// Creates a new `TypedWriterError`.
+    #[inline]
+    #[must_use]
+    fn new(error: Error, writer: Writer<W>) -> Self {
+        Self {
+            error,
+            writer: Box::new(writer),
+        }
+    }
+
+    /// Returns a reference to the error.
+    #[inline]
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn writer(&self) -> &Writer<W> {
+        &self.writer
+    }
+
+    /// Returns the error and the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (Error, Writer<W>) {
+        (self.error, *self.writer)
+    }
+}
+
+impl<W: Write> fmt::Debug for TypedWriterError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedWriterError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> fmt::Display for TypedWriterError<W> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<W: Write> error::Error for TypedWriterError<W> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<W: Write> From<TypedWriterError<W>> for Error {
+    #[inline]
+    fn from(e: TypedWriterError<W>) -> Self {
+        e.error
+    }
+}
+
+/// Typed writer at depth 0.
+///
+/// `TypedWriter` is a facade of [`Writer`] for statically structured
+/// exporters.
+/// Opening a node consumes the writer and returns a [`NodeWriter`] whose type
+/// remembers the parent, and closing the node gives the parent back.
+/// Finalizing is only available on `TypedWriter`, i.e. at depth 0, so
+/// unbalanced `close_node` calls are caught at compile time.
+///
+/// The depth is encoded as nesting of the types (such as
+/// `NodeWriter<W, NodeWriter<W, TypedWriter<W>>>` for depth 2), since
+/// arithmetic on const generic parameters is not available on stable Rust.
+///
+/// On failure, the underlying writer is returned with the error as
+/// [`TypedWriterError`].
+///
+/// # Examples
+///
+/// ```
+/// use fbxcel::{low::FbxVersion, writer::v7400::binary::{FbxFooter, TypedWriter}};
+/// # let sink = std::io::Cursor::new(Vec::new());
+/// let writer = TypedWriter::new(sink, FbxVersion::V7_4)?;
+/// let writer = writer
+///     .begin_node("GlobalSettings")?
+///     .begin_node_with("Version", |attrs| attrs.append_i32(1000))?
+///     .end_node()?
+///     .end_node()?;
+/// let _sink = writer.finalize_and_flush(&FbxFooter::default())?;
+/// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+/// ```
+///
+/// Finalizing with open nodes is rejected by the compiler:
+///
+/// ```compile_fail
+/// use fbxcel::{low::FbxVersion, writer::v7400::binary::{FbxFooter, TypedWriter}};
+/// # let sink = std::io::Cursor::new(Vec::new());
+/// let writer = TypedWriter::new(sink, FbxVersion::V7_4)?;
+/// let writer = writer.begin_node("GlobalSettings")?;
+/// // `NodeWriter` has no `finalize_and_flush()`.
+/// let _sink = writer.finalize_and_flush(&FbxFooter::default())?;
+/// # Ok::<_, fbxcel::writer::v7400::binary::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TypedWriter<W: Write> {
+    /// Underlying writer.
+    writer: Writer<W>,
+}
+
+impl<W: Write + Seek> TypedWriter<W> {
+    /// Creates a new `TypedWriter` and writes FBX file header.
+    #[inline]
+    pub fn new(sink: W, fbx_version: FbxVersion) -> Result<Self> {
+        Writer::new(sink, fbx_version).map(Self::from_writer)
+    }
+
+    /// Creates a new `TypedWriter` from the given configured writer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer has open nodes.
+    #[inline]
+    #[must_use]
+    pub fn with_writer(writer: Writer<W>) -> Self {
+        assert_eq!(
+            writer.current_depth(),
+            0,
+            "The writer should have no open nodes"
+        );
+        Self::from_writer(writer)
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn writer(&self) -> &Writer<W> {
+        &self.writer
+    }
+
+    /// Opens a new node without attributes.
+    #[inline]
+    pub fn begin_node(
+        self,
+        name: &str,
+    ) -> std::result::Result<NodeWriter<W, Self>, TypedWriterError<W>> {
+        begin_node(self, name, |_| Ok(()))
+    }
+
+    /// Opens a new node, and writes its attributes using the given function.
+    #[inline]
+    pub fn begin_node_with<F>(
+        self,
+        name: &str,
+        f: F,
+    ) -> std::result::Result<NodeWriter<W, Self>, TypedWriterError<W>>
+    where
+        F: FnOnce(&mut AttributesWriter<'_, W>) -> Result<()>,
+    {
+        begin_node(self, name, f)
+    }
+
+    /// Finalizes the FBX binary and returns the inner sink.
+    ///
+    /// See [`Writer::finalize`].
+    #[inline]
+    pub fn finalize(self, footer: &FbxFooter<'_>) -> Result<W> {
+        self.writer.finalize(footer)
+    }
+
+    /// Finalizes the FBX binary, and returns the inner sink after flushing.
+    ///
+    /// See [`Writer::finalize_and_flush`].
+    #[inline]
+    pub fn finalize_and_flush(self, footer: &FbxFooter<'_>) -> Result<W> {
+        self.writer.finalize_and_flush(footer)
+    }
+}
+
+impl<W: Write> private::Sealed<W> for TypedWriter<W> {
+    #[inline]
+    fn from_writer(writer: Writer<W>) -> Self {
+        Self { writer }
+    }
+
+    #[inline]
+    fn into_writer(self) -> Writer<W> {
+        self.writer
+    }
+}
+
+impl<W: Write> Depth<W> for TypedWriter<W> {
+    const DEPTH: usize = 0;
+}
+
+/// Typed writer for an open node.
+///
+/// `P` is the type of the parent writer, which is returned by
+/// [`end_node`][`Self::end_node`].
+///
+/// See [`TypedWriter`] for usage.
+#[derive(Debug)]
+pub struct NodeWriter<W: Write, P> {
+    /// Underlying writer.
+    writer: Writer<W>,
+    /// Parent writer type.
+    _parent: PhantomData<fn() -> P>,
+}
+
+impl<W: Write + Seek, P: Depth<W>> NodeWriter<W, P> {
+    /// Returns the depth of the node.
+    ///
+    /// Top-level nodes have depth 1.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        <Self as Depth<W>>::DEPTH
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn writer(&self) -> &Writer<W> {
+        &self.writer
+    }
+
+    /// Opens a new child node without attributes.
+    #[inline]
+    pub fn begin_node(
+        self,
+        name: &str,
+    ) -> std::result::Result<NodeWriter<W, Self>, TypedWriterError<W>> {
+        begin_node(self, name, |_| Ok(()))
+    }
+
+    /// Opens a new child node, and writes its attributes using the given
+    /// function.
+    #[inline]
+    pub fn begin_node_with<F>(
+        self,
+        name: &str,
+        f: F,
+    ) -> std::result::Result<NodeWriter<W, Self>, TypedWriterError<W>>
+    where
+        F: FnOnce(&mut AttributesWriter<'_, W>) -> Result<()>,
+    {
+        begin_node(self, name, f)
+    }
+
+    /// Closes the node, and returns the parent writer.
+    pub fn end_node(mut self) -> std::result::Result<P, TypedWriterError<W>> {
+        match self.writer.close_node() {
+            Ok(()) => Ok(P::from_writer(self.writer)),
+            Err(error) => Err(TypedWriterError::new(error, self.writer)),
+        }
+    }
+}
+
+impl<W: Write, P> private::Sealed<W> for NodeWriter<W, P> {
+    #[inline]
+    fn from_writer(writer: Writer<W>) -> Self {
+        Self {
+            writer,
+            _parent: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_writer(self) -> Writer<W> {
+        self.writer
+    }
+}
+
+impl<W: Write, P: Depth<W>> Depth<W> for NodeWriter<W, P> {
+    const DEPTH: usize = P::DEPTH + 1;
+}
+
+/// Opens a new child node of the given parent, and writes its attributes
+/// using the given function.
+fn begin_node<W, P, F>(
+    parent: P,
+    name: &str,
+    f: F,
+) -> std::result::Result<NodeWriter<W, P>, TypedWriterError<W>>
+where
+    W: Write + Seek,
+    P: Depth<W>,
+    F: FnOnce(&mut AttributesWriter<'_, W>) -> Result<()>,
+{
+    let mut writer = parent.into_writer();
+    let res = writer.new_node(name).and_then(|mut attrs| f(&mut attrs));
+    match res {
+        Ok(()) => Ok(NodeWriter::from_writer(writer)),
+        Err(error) => Err(TypedWriterError::new(error, writer)),
+    }
+}
@@ -0,0 +1,72 @@
+//! Verification of the written FBX data.
+
+use std::io::{Read, Seek};
+
+use crate::{
+    low::FbxVersion,
+    pull_parser::{
+        self,
+        any::AnyParser,
+        v7400::{attribute::loaders::DirectLoader, Event},
+        ParserSource, Warning,
+    },
+};
+
+/// Verification error.
+type VerificationError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Parses the FBX data and checks its structure.
+///
+/// Node attributes are decoded and discarded immediately.
+///
+/// Warnings about footer fields are ignored, since they can be caused by
+/// user-specified footer.
+/// Other warnings are treated as errors.
+pub(crate) fn verify<R: Read + Seek>(
+    reader: R,
+    fbx_version: FbxVersion,
+    end_pos: u64,
+) -> Result<(), VerificationError> {
+    let AnyParser::V7400(mut parser) = pull_parser::any::from_seekable_reader(reader)?;
+    if parser.fbx_version() != fbx_version {
+        return Err(format!(
+            "FBX version mismatch: expected {:?}, got {:?}",
+            fbx_version,
+            parser.fbx_version()
+        )
+        .into());
+    }
+    parser.set_warning_handler(|warning, _pos| match warning {
+        Warning::InvalidFooterPaddingLength(..) | Warning::UnexpectedFooterFieldValue => Ok(()),
+        warning => Err(warning.into()),
+    });
+
+    loop {
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                for attr in start
+                    .attributes()
+                    .into_iter(std::iter::repeat(DirectLoader))
+                {
+                    attr?;
+                }
+            }
+            Event::EndNode | Event::SkippedNode { .. } => {}
+            Event::EndFbx(footer) => {
+                footer?;
+                break;
+            }
+        }
+    }
+
+    let parsed_end_pos = parser.reader().position();
+    if parsed_end_pos != end_pos {
+        return Err(format!(
+            "FBX data length mismatch: expected {} bytes, got {} bytes",
+            end_pos, parsed_end_pos
+        )
+        .into());
+    }
+
+    Ok(())
+}
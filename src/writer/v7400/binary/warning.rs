@@ -0,0 +1,54 @@
+//! Binary writer warning.
+
+use std::{error, fmt};
+
+/// Writer warning.
+///
+/// Warnings are non-fatal conditions which may produce files rejected or
+/// misinterpreted by some importers.
+/// They are reported to the warning handler set by
+/// [`Writer::set_warning_handler`][`super::Writer::set_warning_handler`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// Array attribute has no elements.
+    EmptyArrayAttribute,
+    /// Node name is empty.
+    EmptyNodeName,
+    /// Forced footer padding length is different from the correct one.
+    ///
+    /// This contains the expected (correct) length and the forced length.
+    InvalidFooterPaddingLength(u8, u8),
+    /// Node name contains characters other than ASCII graphic characters
+    /// (such as whitespaces, control characters, or non-ASCII characters).
+    SuspiciousNodeName(String),
+    /// Top-level node is written out of the well-known order.
+    ///
+    /// This contains the node name, and the name of an already written node
+    /// which should follow it.
+    /// This is reported only when the top-level order check is disabled.
+    /// See [`Writer::set_toplevel_order_check`][`super::Writer::set_toplevel_order_check`].
+    ToplevelNodeOrder(String, String),
+}
+
+impl error::Error for Warning {}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::EmptyArrayAttribute => write!(f, "Array attribute has no elements"),
+            Warning::EmptyNodeName => write!(f, "Node name is empty"),
+            Warning::InvalidFooterPaddingLength(expected, got) => write!(
+                f,
+                "Invalid footer padding length: expected {} bytes, got {} bytes",
+                expected, got
+            ),
+            Warning::SuspiciousNodeName(name) => write!(f, "Suspicious node name: {:?}", name),
+            Warning::ToplevelNodeOrder(name, prev) => write!(
+                f,
+                "Top-level node `{}` should be written before `{}`",
+                name, prev
+            ),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests for conversion between FBX versions.
+#![cfg(all(feature = "tree", feature = "writer"))]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    convert::convert, low::FbxVersion, tree::any::AnyTree, tree_v7400,
+    writer::v7400::binary::Writer,
+};
+
+/// Converts 7.4 data to 7.5 and back, and compares the trees.
+#[test]
+fn convert_roundtrip_v7400_v7500() -> Result<(), Box<dyn std::error::Error>> {
+    let tree = tree_v7400! {
+        Node0: {
+            Node0_0: [vec![1.0f64; 32], "Hello"] {},
+        },
+        Node1: [true, 42i64] {},
+    };
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.write_tree(&tree)?;
+    let bin_7400 = writer.finalize_and_flush(&Default::default())?.into_inner();
+
+    let (source_version, sink) = convert(
+        Cursor::new(bin_7400),
+        Cursor::new(Vec::new()),
+        FbxVersion::V7_5,
+    )?;
+    assert_eq!(source_version, FbxVersion::V7_4);
+    let bin_7500 = sink.into_inner();
+    match AnyTree::from_seekable_reader(Cursor::new(&bin_7500))? {
+        AnyTree::V7400(ver, converted, footer) => {
+            assert_eq!(ver, FbxVersion::V7_5);
+            assert!(converted.strict_eq(&tree));
+            assert_eq!(footer?.fbx_version, FbxVersion::V7_5);
+        }
+        _ => panic!("Converted data should be loadable as v7400 tree"),
+    }
+
+    let (source_version, sink) = convert(
+        Cursor::new(bin_7500),
+        Cursor::new(Vec::new()),
+        FbxVersion::V7_4,
+    )?;
+    assert_eq!(source_version, FbxVersion::V7_5);
+    match AnyTree::from_seekable_reader(Cursor::new(sink.into_inner()))? {
+        AnyTree::V7400(ver, converted, _) => {
+            assert_eq!(ver, FbxVersion::V7_4);
+            assert!(converted.strict_eq(&tree));
+        }
+        _ => panic!("Converted data should be loadable as v7400 tree"),
+    }
+
+    Ok(())
+}
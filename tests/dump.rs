@@ -0,0 +1,54 @@
+//! Tests for parser events dump.
+#![cfg(feature = "writer")]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    debug::{dump, DumpFormat},
+    low::FbxVersion,
+    pull_parser::any::{from_seekable_reader, AnyParser},
+    write_v7400_binary,
+    writer::v7400::binary::Writer,
+};
+
+/// Writes a small document and dumps it in the given format.
+fn dump_sample(format: DumpFormat) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [42i32, "Hello"] {
+                Node0_0: [vec![1i32, 2, 3]] {}
+            }
+        },
+    )?;
+    let bin = writer.finalize_and_flush(&Default::default())?.into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let mut out = Vec::new();
+    dump(&mut parser, &mut out, format)?;
+
+    Ok(String::from_utf8(out)?)
+}
+
+#[test]
+fn dump_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let type_ = dump_sample(DumpFormat::Type)?;
+    assert!(type_.starts_with(
+        "Node start: \"Node0\"\n    Attribute: I32\n    Attribute: String\n    Node start: \"Node0_0\"\n        Attribute: ArrI32\n    Node end\nNode end\nFBX end\n"
+    ));
+
+    let length = dump_sample(DumpFormat::Length)?;
+    assert!(length.contains("    Attribute: I32(42)\n"));
+    assert!(length.contains("    Attribute: type=String, len=5\n"));
+    assert!(length.contains("        Attribute: type=ArrI32, len=3\n"));
+
+    let full = dump_sample(DumpFormat::Full)?;
+    assert!(full.contains("    Attribute: type=String, len=5, value=\"Hello\"\n"));
+    assert!(full.contains("        Attribute: type=ArrI32, len=3, value=[1, 2, 3]\n"));
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Tests for in-place editor.
+#![cfg(all(feature = "tree", feature = "writer"))]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    editor::{Editor, Error as EditorError},
+    low::{v7400::AttributeValue, FbxVersion},
+    tree::any::AnyTree,
+    tree_v7400,
+    writer::v7400::binary::Writer,
+};
+
+/// Patches attributes in place and checks the result with the tree loader.
+#[test]
+fn patch_attributes_in_place() -> Result<(), Box<dyn std::error::Error>> {
+    for &version in &[FbxVersion::V7_4, FbxVersion::V7_5] {
+        let tree = tree_v7400! {
+            Node0: {
+                Child: [1i32] {}
+            },
+            Node1: {
+                Child: [2i32, "abc"] {
+                    Grandchild: {}
+                }
+            },
+        };
+        let mut writer = Writer::new(Cursor::new(Vec::new()), version)?;
+        writer.write_tree(&tree)?;
+        let file = writer.finalize_and_flush(&Default::default())?;
+
+        let mut editor = Editor::new(file)?;
+        assert_eq!(editor.fbx_version(), version);
+        assert!(editor.locate(&["Node1", "Missing"])?.is_none());
+        assert!(editor.locate(&["Child"])?.is_none());
+
+        let location = editor.patch_attributes(
+            &["Node1", "Child"],
+            &[AttributeValue::I32(3), AttributeValue::String("xyz".into())],
+        )?;
+        assert_eq!(location.attributes_count(), 2);
+        // 4 bytes of `i32` can be replaced with 4 bytes of `f32`.
+        editor.patch_attributes(&["Node0", "Child"], &[AttributeValue::F32(1.5)])?;
+        match editor.patch_attributes(&["Node0", "Child"], &[AttributeValue::I64(1)]) {
+            Err(EditorError::AttributesSizeMismatch(5, 9)) => {}
+            v => panic!("Unexpected result: {:?}", v),
+        }
+        assert!(matches!(
+            editor.patch_attributes(&["Node2"], &[]),
+            Err(EditorError::NodeNotFound(_))
+        ));
+
+        let expected = tree_v7400! {
+            Node0: {
+                Child: [1.5f32] {}
+            },
+            Node1: {
+                Child: [3i32, "xyz"] {
+                    Grandchild: {}
+                }
+            },
+        };
+        let mut file = editor.into_inner();
+        file.set_position(0);
+        match AnyTree::from_seekable_reader(file)? {
+            AnyTree::V7400(_, patched, footer) => {
+                footer?;
+                assert!(patched.strict_eq(&expected), "{:?}", patched.debug_tree());
+            }
+            _ => panic!("Patched data should be loadable as v7400 tree"),
+        }
+    }
+
+    Ok(())
+}
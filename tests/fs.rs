@@ -0,0 +1,93 @@
+//! File system entry point test.
+#![cfg(all(feature = "fs", feature = "writer"))]
+
+use std::{fs::File, path::PathBuf};
+
+use fbxcel::{
+    low::FbxVersion,
+    pull_parser::{error::Limit, v7400::ParserConfig},
+    write_v7400_binary,
+    writer::v7400::binary::{FbxFooter, Writer},
+    Fbx,
+};
+
+/// Writes a test file and returns its path.
+fn write_test_file(name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path =
+        std::env::temp_dir().join(format!("fbxcel-test-{}-{}.fbx", name, std::process::id()));
+    let mut writer = Writer::new(File::create(&path)?, FbxVersion::V7_5)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: {
+                Node0_0: [42i32] {}
+            }
+        },
+    )?;
+    writer.finalize_and_flush(&FbxFooter::default())?;
+    Ok(path)
+}
+
+#[test]
+fn open_file() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::pull_parser::{
+        any::AnyParser,
+        error::{DataError, ErrorContainer},
+        v7400::Event,
+    };
+
+    let path = write_test_file("open")?;
+
+    let parser = Fbx::open(&path)?;
+    assert_eq!(parser.fbx_version(), FbxVersion::V7_5);
+
+    let mut config = ParserConfig::default();
+    config.max_depth = Some(1);
+    let mut parser = match Fbx::options()
+        .buffer_capacity(16)
+        .parser_config(config)
+        .open(&path)?
+    {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    assert!(matches!(parser.next_event()?, Event::StartNode(_)));
+    let err = match parser.next_event() {
+        Ok(ev) => panic!("Depth limit should be exceeded: {:?}", ev),
+        Err(e) => e,
+    };
+    assert!(matches!(
+        err.get_ref(),
+        ErrorContainer::Data(DataError::LimitExceeded(Limit::Depth(1)))
+    ));
+
+    std::fs::remove_file(&path)?;
+    assert!(Fbx::open(&path).is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "tree")]
+#[test]
+fn load_tree_from_file() -> Result<(), Box<dyn std::error::Error>> {
+    let path = write_test_file("load-tree")?;
+    let tree = Fbx::options().load_tree(&path)?;
+    assert_eq!(tree.fbx_version(), FbxVersion::V7_5);
+
+    let tree = fbxcel::tree::any::AnyTree::from_path(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(tree.fbx_version(), FbxVersion::V7_5);
+
+    Ok(())
+}
+
+#[test]
+fn parser_from_path() -> Result<(), Box<dyn std::error::Error>> {
+    let path = write_test_file("parser-from-path")?;
+    let parser = fbxcel::pull_parser::any::from_path(&path)?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(parser.fbx_version(), FbxVersion::V7_5);
+
+    Ok(())
+}
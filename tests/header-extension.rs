@@ -0,0 +1,83 @@
+//! Header extension loading test.
+#![cfg(feature = "writer")]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    low::{FbxHeader, FbxVersion},
+    pull_parser::v7400::{from_seekable_reader, load_header_extension, Event},
+    write_v7400_binary,
+    writer::v7400::binary::{FbxFooter, Writer},
+};
+
+/// Loads the header extension of the generated data.
+#[test]
+fn header_extension_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dest = Vec::new();
+    let cursor = Cursor::new(&mut dest);
+    let mut writer = Writer::new(cursor, FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            FBXHeaderExtension: {
+                FBXHeaderVersion: [1003i32] {}
+                FBXVersion: [7400i32] {}
+                CreationTimeStamp: {
+                    Version: [1000i32] {}
+                    Year: [2020i32] {}
+                    Month: [4i32] {}
+                    Day: [1i32] {}
+                    Hour: [12i32] {}
+                    Minute: [34i32] {}
+                    Second: [56i32] {}
+                    Millisecond: [789i32] {}
+                }
+                Creator: ["fbxcel test"] {}
+                SceneInfo: ["GlobalInfo\u{0}\u{1}SceneInfo", "UserData"] {
+                    Type: ["UserData"] {}
+                    MetaData: {
+                        Version: [100i32] {}
+                        Title: ["title"] {}
+                        Author: ["author"] {}
+                    }
+                }
+            }
+            Objects: {}
+        },
+    )?;
+    writer.finalize_and_flush(&FbxFooter::default())?;
+
+    let mut reader = Cursor::new(dest);
+    let header = FbxHeader::load(&mut reader)?;
+    let mut parser = from_seekable_reader(header, reader)?;
+    let ext = load_header_extension(&mut parser)?.expect("Header extension should exist");
+    assert_eq!(ext.header_version, Some(1003));
+    assert_eq!(ext.fbx_version, Some(7400));
+    assert_eq!(ext.creator.as_deref(), Some("fbxcel test"));
+    let timestamp = ext.creation_timestamp.expect("Time stamp should exist");
+    assert_eq!(
+        (timestamp.year, timestamp.month, timestamp.day),
+        (2020, 4, 1)
+    );
+    assert_eq!(
+        (
+            timestamp.hour,
+            timestamp.minute,
+            timestamp.second,
+            timestamp.millisecond
+        ),
+        (12, 34, 56, 789)
+    );
+    let scene_info = ext.scene_info.expect("Scene info should exist");
+    assert_eq!(scene_info.title.as_deref(), Some("title"));
+    assert_eq!(scene_info.author.as_deref(), Some("author"));
+    assert_eq!(scene_info.subject, None);
+
+    // The parser can continue reading the rest of the document.
+    match parser.next_event()? {
+        Event::StartNode(start) => assert_eq!(start.name(), "Objects"),
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+
+    Ok(())
+}
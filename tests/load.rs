@@ -0,0 +1,75 @@
+//! Facade load test.
+#![cfg(feature = "writer")]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    load,
+    low::FbxVersion,
+    pull_parser::any::AnyParser,
+    write_v7400_binary,
+    writer::v7400::binary::{FbxFooter, Writer},
+    LoadLevel,
+};
+
+/// Writes FBX 7.4 data for the tests.
+fn test_data() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut dest = Vec::new();
+    let cursor = Cursor::new(&mut dest);
+    let mut writer = Writer::new(cursor, FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Objects: {
+                Model: [1i64, "Cube\u{0}\u{1}Model", "Mesh"] {}
+            }
+        },
+    )?;
+    writer.finalize_and_flush(&FbxFooter::default())?;
+
+    Ok(dest)
+}
+
+/// Loads the data as a parser.
+#[test]
+fn load_parser() -> Result<(), Box<dyn std::error::Error>> {
+    let loaded = load(Cursor::new(test_data()?), LoadLevel::Parser)?;
+    assert_eq!(loaded.fbx_version(), FbxVersion::V7_4);
+    let parser = loaded.into_parser().expect("Should be a parser");
+    match parser {
+        AnyParser::V7400(parser) => assert_eq!(parser.fbx_version(), FbxVersion::V7_4),
+        _ => panic!("FBX 7.4 data should be loaded by v7400 parser"),
+    }
+
+    Ok(())
+}
+
+/// Loads the data as a tree.
+#[cfg(feature = "tree")]
+#[test]
+fn load_tree() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::tree::any::AnyTree;
+
+    let loaded = load(Cursor::new(test_data()?), LoadLevel::Tree)?;
+    assert_eq!(loaded.fbx_version(), FbxVersion::V7_4);
+    match loaded.into_tree().expect("Should be a tree") {
+        AnyTree::V7400(_, tree, _) => {
+            let objects = tree
+                .root()
+                .first_child_by_name("Objects")
+                .expect("Objects should exist");
+            assert_eq!(objects.children().count(), 1);
+        }
+        _ => panic!("FBX 7.4 data should be loaded as v7400 tree"),
+    }
+
+    Ok(())
+}
+
+/// Reports an error for non-FBX data.
+#[test]
+fn load_invalid() {
+    let err = load(Cursor::new(b"not an FBX file".to_vec()), LoadLevel::Parser)
+        .expect_err("Non-FBX data should be rejected");
+    assert!(matches!(err, fbxcel::LoadError::ParserCreation(_)));
+}
@@ -0,0 +1,639 @@
+//! Parser configuration test.
+#![cfg(feature = "writer")]
+
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, Cursor},
+    num::NonZeroUsize,
+    rc::Rc,
+};
+
+use fbxcel::{
+    low::FbxVersion,
+    pull_parser::{
+        any::{from_reader, from_seekable_reader, AnyParser},
+        error::{DataError, ErrorContainer, Limit, Warning},
+        v7400::{attribute::loaders::DirectLoader, Event, Parser, ParserConfig},
+        ParserSource, SeekableParserSource,
+    },
+    write_v7400_binary,
+    writer::v7400::binary::{FbxFooter, Writer},
+};
+
+/// Creates a test data.
+fn test_data() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: {
+                Node0_0: {
+                    Node0_0_0: [vec![0i32; 16]] {}
+                }
+            }
+            Node1: ["Hello"] {}
+        },
+    )?;
+    Ok(writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner())
+}
+
+/// Creates a parser for the test data with the given configuration.
+fn parser(
+    config: ParserConfig,
+) -> Result<Parser<impl SeekableParserSource + std::fmt::Debug>, Box<dyn std::error::Error>> {
+    let mut parser = match from_seekable_reader(Cursor::new(test_data()?))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    parser.set_config(config);
+    Ok(parser)
+}
+
+/// Reads all events and returns the limit if exceeded.
+fn exceeded_limit<R: ParserSource>(parser: &mut Parser<R>) -> Option<Limit> {
+    loop {
+        match parser.next_event() {
+            Ok(Event::EndFbx(Ok(_))) => return None,
+            Err(e) | Ok(Event::EndFbx(Err(e))) => match e.get_ref() {
+                ErrorContainer::Data(DataError::LimitExceeded(limit)) => return Some(*limit),
+                _ => panic!("Unexpected error: {}", e),
+            },
+            Ok(_) => {}
+        }
+    }
+}
+
+#[test]
+fn no_limits() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(exceeded_limit(&mut parser(ParserConfig::default())?), None);
+
+    Ok(())
+}
+
+#[test]
+fn max_depth() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    config.max_depth = Some(3);
+    assert_eq!(exceeded_limit(&mut parser(config)?), None);
+    config.max_depth = Some(2);
+    assert_eq!(exceeded_limit(&mut parser(config)?), Some(Limit::Depth(2)));
+
+    Ok(())
+}
+
+#[test]
+fn max_node_count() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    config.max_node_count = Some(4);
+    assert_eq!(exceeded_limit(&mut parser(config)?), None);
+    config.max_node_count = Some(3);
+    assert_eq!(
+        exceeded_limit(&mut parser(config)?),
+        Some(Limit::NodeCount(3))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn max_attribute_bytelen() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    // Type code (1), array header (12), and elements (4 * 16).
+    config.max_attribute_bytelen = Some(77);
+    assert_eq!(exceeded_limit(&mut parser(config)?), None);
+    config.max_attribute_bytelen = Some(76);
+    assert_eq!(
+        exceeded_limit(&mut parser(config)?),
+        Some(Limit::AttributeBytelen(76))
+    );
+
+    Ok(())
+}
+
+/// Recorded warnings.
+type Warnings = Rc<RefCell<Vec<Warning>>>;
+
+/// Creates a parser which records warnings.
+fn recording_parser(
+    config: ParserConfig,
+) -> Result<
+    (
+        Parser<impl SeekableParserSource + std::fmt::Debug>,
+        Warnings,
+    ),
+    Box<dyn std::error::Error>,
+> {
+    let mut parser = parser(config)?;
+    let warnings = Rc::new(RefCell::new(Vec::new()));
+    parser.set_warning_handler({
+        let warnings = warnings.clone();
+        move |w, _| {
+            warnings.borrow_mut().push(w);
+            Ok(())
+        }
+    });
+    Ok((parser, warnings))
+}
+
+#[test]
+fn warn_unread_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    config.warn_unread_attributes = true;
+
+    let (mut parser, warnings) = recording_parser(config)?;
+    loop {
+        match parser.next_event()? {
+            // Explicitly skipped.
+            Event::StartNode(start) if start.name() == "Node0_0_0" => {
+                start.attributes().finish();
+            }
+            // Fully read.
+            Event::StartNode(start) if start.name() == "Node1" => {
+                let mut attrs = start.attributes();
+                assert!(attrs.load_next(DirectLoader)?.is_some());
+            }
+            // Nodes without attributes.
+            Event::StartNode(_) => {}
+            Event::EndNode | Event::SkippedNode { .. } => {}
+            Event::EndFbx(_) => break,
+        }
+    }
+    assert_eq!(parser.nodes_with_unread_attributes_count(), 0);
+    assert!(warnings.borrow().is_empty());
+
+    // Implicitly skipped.
+    let (mut parser, warnings) = recording_parser(config)?;
+    assert_eq!(exceeded_limit(&mut parser), None);
+    assert_eq!(parser.nodes_with_unread_attributes_count(), 2);
+    assert!(matches!(
+        warnings.borrow()[..],
+        [
+            Warning::UnreadAttributesSkipped(1),
+            Warning::UnreadAttributesSkipped(1)
+        ]
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn decompression_buffer_size() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::low::v7400::{ArrayAttributeEncoding, AttributeValue};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    for _ in 0..2 {
+        writer
+            .new_node("Node")?
+            .append_arr_i32_from_iter(Some(ArrayAttributeEncoding::Zlib), 0..1024)?;
+        writer.close_node()?;
+    }
+    let data = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(data))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let mut config = ParserConfig::default();
+    // Compressed data is read byte by byte.
+    config.decompression_buffer_size = NonZeroUsize::new(1);
+    parser.set_config(config);
+    let expected = AttributeValue::ArrI32((0..1024).collect());
+    loop {
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                let mut attrs = start.attributes();
+                assert_eq!(attrs.load_next(DirectLoader)?.as_ref(), Some(&expected));
+            }
+            Event::EndNode | Event::SkippedNode { .. } => {}
+            Event::EndFbx(_) => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn read_trailing_data() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = test_data()?;
+    data.extend_from_slice(b"junk");
+
+    let mut parser = match from_seekable_reader(Cursor::new(data.clone()))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let mut config = ParserConfig::default();
+    config.read_trailing_data = true;
+    parser.set_config(config);
+    let warnings = Rc::new(RefCell::new(Vec::new()));
+    parser.set_warning_handler({
+        let warnings = warnings.clone();
+        move |w, _| {
+            warnings.borrow_mut().push(w);
+            Ok(())
+        }
+    });
+    assert_eq!(exceeded_limit(&mut parser), None);
+    assert_eq!(parser.trailing_data(), Some(&b"junk"[..]));
+    assert!(matches!(warnings.borrow()[..], [Warning::TrailingData(4)]));
+
+    // Limited length.
+    let trailing_data_parser = |max_len: u64| -> Result<_, Box<dyn std::error::Error>> {
+        let mut parser = match from_seekable_reader(Cursor::new(data.clone()))? {
+            AnyParser::V7400(parser) => parser,
+            _ => panic!("Generated data should be parsable with v7400 parser"),
+        };
+        let mut config = config;
+        config.max_trailing_data_len = Some(max_len);
+        parser.set_config(config);
+        parser.set_warning_handler(|_, _| Ok(()));
+        Ok(parser)
+    };
+    assert_eq!(exceeded_limit(&mut trailing_data_parser(4)?), None);
+    assert_eq!(
+        exceeded_limit(&mut trailing_data_parser(3)?),
+        Some(Limit::TrailingDataLength(3))
+    );
+
+    // Disabled by default.
+    let (mut parser, warnings) = recording_parser(ParserConfig::default())?;
+    assert_eq!(exceeded_limit(&mut parser), None);
+    assert_eq!(parser.trailing_data(), None);
+    assert!(warnings.borrow().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn incorrect_boolean_array_representation() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::low::v7400::{ArrayAttributeEncoding, AttributeValue};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.new_node("Node")?.append_arr_bool_from_iter(
+        Some(ArrayAttributeEncoding::Direct),
+        [true, false, true, false].iter().copied(),
+    )?;
+    writer.close_node()?;
+    let mut data = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+    let pos = data
+        .windows(4)
+        .position(|w| w == b"YTYT")
+        .expect("Array should be written without compression");
+    data[(pos + 2)..(pos + 4)].copy_from_slice(&[1, 0]);
+
+    let parse = |config: ParserConfig| -> Result<Warnings, Box<dyn std::error::Error>> {
+        let mut parser = match from_seekable_reader(Cursor::new(data.clone()))? {
+            AnyParser::V7400(parser) => parser,
+            _ => panic!("Generated data should be parsable with v7400 parser"),
+        };
+        parser.set_config(config);
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        parser.set_warning_handler({
+            let warnings = warnings.clone();
+            move |w, _| {
+                warnings.borrow_mut().push(w);
+                Ok(())
+            }
+        });
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                let mut attrs = start.attributes();
+                // Skipping the attribute does not report warnings.
+                attrs.seek_to_attribute(1)?;
+                assert!(warnings.borrow().is_empty());
+                // Reading the attribute again does not report warnings again.
+                for _ in 0..2 {
+                    attrs.seek_to_attribute(0)?;
+                    assert_eq!(
+                        attrs.load_next(DirectLoader)?,
+                        Some(AttributeValue::ArrBool(vec![true, false, true, false]))
+                    );
+                }
+            }
+            ev => panic!("Unexpected event: {:?}", ev),
+        }
+        Ok(warnings)
+    };
+
+    let warnings = parse(ParserConfig::default())?;
+    assert!(matches!(
+        warnings.borrow()[..],
+        [Warning::IncorrectBooleanArrayRepresentation(2, 2)]
+    ));
+
+    let mut config = ParserConfig::default();
+    config.ignore_incorrect_boolean_representation = true;
+    assert!(parse(config)?.borrow().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn strict_attribute_count() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node: [1i32, 2i32] {}
+        },
+    )?;
+    let data = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+    // Node header (for FBX 7.4) starts right after the 27-byte FBX header:
+    // end offset, number of attributes, and byte length of attributes.
+    const NUM_ATTRIBUTES_POS: usize = 27 + 4;
+    const BYTELEN_ATTRIBUTES_POS: usize = 27 + 8;
+
+    let parse =
+        |data: &[u8], config: ParserConfig| -> Result<Warnings, Box<dyn std::error::Error>> {
+            let mut parser = match from_seekable_reader(Cursor::new(data.to_vec()))? {
+                AnyParser::V7400(parser) => parser,
+                _ => panic!("Generated data should be parsable with v7400 parser"),
+            };
+            parser.set_config(config);
+            let warnings = Rc::new(RefCell::new(Vec::new()));
+            parser.set_warning_handler({
+                let warnings = warnings.clone();
+                move |w, _| {
+                    warnings.borrow_mut().push(w);
+                    Ok(())
+                }
+            });
+            loop {
+                match parser.next_event()? {
+                    Event::StartNode(start) => {
+                        start.attributes().load_all_direct()?;
+                    }
+                    Event::EndNode | Event::SkippedNode { .. } => {}
+                    Event::EndFbx(_) => break,
+                }
+            }
+            Ok(warnings)
+        };
+    let mut strict = ParserConfig::default();
+    strict.strict_attribute_count = true;
+
+    assert!(parse(&data, strict)?.borrow().is_empty());
+
+    // Declares fewer attributes than the data.
+    let mut fewer = data.clone();
+    fewer[NUM_ATTRIBUTES_POS..(NUM_ATTRIBUTES_POS + 4)].copy_from_slice(&1u32.to_le_bytes());
+    assert!(matches!(
+        parse(&fewer, strict)?.borrow()[..],
+        [Warning::ExtraAttributeData(5)]
+    ));
+    assert!(parse(&fewer, ParserConfig::default())?.borrow().is_empty());
+
+    // Declares shorter byte length than the attributes.
+    let mut shorter = data.clone();
+    shorter[BYTELEN_ATTRIBUTES_POS..(BYTELEN_ATTRIBUTES_POS + 4)]
+        .copy_from_slice(&7u32.to_le_bytes());
+    assert!(matches!(
+        parse(&shorter, strict)?.borrow()[..],
+        [Warning::AttributesLengthExceeded(3)]
+    ));
+
+    // Declares byte length only for the first attribute.
+    let mut shorter = data.clone();
+    shorter[BYTELEN_ATTRIBUTES_POS..(BYTELEN_ATTRIBUTES_POS + 4)]
+        .copy_from_slice(&5u32.to_le_bytes());
+    assert!(matches!(
+        parse(&shorter, strict)?.borrow()[..],
+        [Warning::MissingAttributeData(1)]
+    ));
+
+    // Declares attributes without data.
+    let mut empty = data.clone();
+    empty[BYTELEN_ATTRIBUTES_POS..(BYTELEN_ATTRIBUTES_POS + 4)]
+        .copy_from_slice(&0u32.to_le_bytes());
+    assert!(matches!(
+        parse(&empty, strict)?.borrow()[..],
+        [Warning::MissingAttributeData(2)]
+    ));
+
+    // Declares more attributes than the data.
+    // The warning is reported before reading beyond the attributes.
+    let mut more = data;
+    more[NUM_ATTRIBUTES_POS..(NUM_ATTRIBUTES_POS + 4)].copy_from_slice(&3u32.to_le_bytes());
+    let mut parser = match from_seekable_reader(Cursor::new(more))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    parser.set_config(strict);
+    parser.set_warning_handler(|w, _| Err(w.into()));
+    let err = match parser.next_event()? {
+        Event::StartNode(start) => start
+            .attributes()
+            .load_all_direct()
+            .expect_err("Should fail"),
+        ev => panic!("Unexpected event: {:?}", ev),
+    };
+    assert!(
+        matches!(
+            err.get_ref(),
+            ErrorContainer::Warning(Warning::MissingAttributeData(1))
+        ),
+        "Unexpected error: {}",
+        err
+    );
+
+    Ok(())
+}
+
+#[test]
+fn report_skipped_nodes() -> Result<(), Box<dyn std::error::Error>> {
+    /// Opens `Node0` and `Node0_0`, and skips `Node0_0`.
+    fn skip_node0_0<R: ParserSource + std::fmt::Debug>(
+        parser: &mut Parser<R>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for name in ["Node0", "Node0_0"] {
+            match parser.next_event()? {
+                Event::StartNode(start) => assert_eq!(start.name(), name),
+                ev => panic!("Unexpected event: {:?}", ev),
+            }
+        }
+        parser.skip_current_node()?;
+        assert_eq!(parser.current_depth(), 1);
+        Ok(())
+    }
+
+    // Disabled by default.
+    let mut default_parser = parser(ParserConfig::default())?;
+    skip_node0_0(&mut default_parser)?;
+    assert!(matches!(default_parser.next_event()?, Event::EndNode));
+
+    let mut config = ParserConfig::default();
+    config.report_skipped_nodes = true;
+    let mut reporting_parser = parser(config)?;
+    skip_node0_0(&mut reporting_parser)?;
+    match reporting_parser.next_event()? {
+        Event::SkippedNode { name, bytelen } => {
+            assert_eq!(name, "Node0_0");
+            assert!(bytelen > 0);
+        }
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+    // The depth is not changed by `SkippedNode`, and the parser continues.
+    assert_eq!(reporting_parser.current_depth(), 1);
+    assert!(matches!(reporting_parser.next_event()?, Event::EndNode));
+    assert_eq!(exceeded_limit(&mut reporting_parser), None);
+
+    // Successively skipped nodes are reported in the skipped order.
+    let mut reporting_parser = parser(config)?;
+    skip_node0_0(&mut reporting_parser)?;
+    reporting_parser.skip_current_node()?;
+    assert_eq!(reporting_parser.current_depth(), 0);
+    for expected in ["Node0_0", "Node0"] {
+        match reporting_parser.next_event()? {
+            Event::SkippedNode { name, .. } => assert_eq!(name, expected),
+            ev => panic!("Unexpected event: {:?}", ev),
+        }
+    }
+    match reporting_parser.next_event()? {
+        Event::StartNode(start) => assert_eq!(start.name(), "Node1"),
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn max_warnings_per_kind() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    // Both `Node0_0_0` and `Node1` have unread attributes.
+    config.warn_unread_attributes = true;
+
+    let (mut unlimited_parser, warnings) = recording_parser(config)?;
+    assert_eq!(exceeded_limit(&mut unlimited_parser), None);
+    assert_eq!(warnings.borrow().len(), 2);
+    assert_eq!(unlimited_parser.suppressed_warnings().count(), 0);
+
+    config.max_warnings_per_kind = Some(1);
+    let (mut limited_parser, warnings) = recording_parser(config)?;
+    assert_eq!(exceeded_limit(&mut limited_parser), None);
+    assert!(matches!(
+        warnings.borrow()[..],
+        [Warning::UnreadAttributesSkipped(1)]
+    ));
+    let suppressed = limited_parser.suppressed_warnings().collect::<Vec<_>>();
+    match suppressed[..] {
+        [(Warning::UnreadAttributesSkipped(1), pos, 1)] => {
+            assert_eq!(pos.node_path().last().map(|v| v.1.as_str()), Some("Node1"));
+        }
+        ref v => panic!("Unexpected suppressed warnings: {:?}", v),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn max_warnings_per_kind_after_resume() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    // Both `Node0_0_0` and `Node1` have unread attributes.
+    config.warn_unread_attributes = true;
+    config.max_warnings_per_kind = Some(1);
+
+    let (mut parser, warnings) = recording_parser(config)?;
+    let bookmark = parser.bookmark();
+    assert_eq!(exceeded_limit(&mut parser), None);
+    parser.resume(&bookmark)?;
+    assert_eq!(exceeded_limit(&mut parser), None);
+    // The counts are reset on resume, so the second pass reports warnings
+    // in the same way as the first pass.
+    assert_eq!(warnings.borrow().len(), 2);
+    assert!(matches!(
+        parser.suppressed_warnings().collect::<Vec<_>>()[..],
+        [(Warning::UnreadAttributesSkipped(1), _, 1)]
+    ));
+
+    Ok(())
+}
+
+/// Reader which counts reads and seeks.
+struct CountingReader<R> {
+    /// Inner reader.
+    inner: R,
+    /// Number of reads and seeks.
+    counts: Rc<(Cell<usize>, Cell<usize>)>,
+}
+
+impl<R: io::Read> io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.counts.0.set(self.counts.0.get() + 1);
+        self.inner.read(buf)
+    }
+}
+
+impl<R: io::Seek> io::Seek for CountingReader<R> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.counts.1.set(self.counts.1.get() + 1);
+        self.inner.seek(pos)
+    }
+}
+
+/// Reads all events and returns the number of reads and seeks after the
+/// parser creation.
+fn count_io<R: ParserSource>(
+    parser: impl FnOnce(
+        CountingReader<Cursor<Vec<u8>>>,
+    ) -> fbxcel::pull_parser::any::Result<AnyParser<R>>,
+    config: ParserConfig,
+) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    let counts = Rc::new((Cell::new(0), Cell::new(0)));
+    let reader = CountingReader {
+        inner: Cursor::new(test_data()?),
+        counts: counts.clone(),
+    };
+    let mut parser = match parser(reader)? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    parser.set_config(config);
+    counts.0.set(0);
+    counts.1.set(0);
+    assert_eq!(exceeded_limit(&mut parser), None);
+    Ok((counts.0.get(), counts.1.get()))
+}
+
+#[test]
+fn skip_small_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    // Attributes of `Node0_0_0` are 77 bytes long, and attributes of `Node1`
+    // are 10 bytes long.
+    let (_, default_seeks) = count_io(from_seekable_reader, config)?;
+    config.skip_small_threshold = Some(0);
+    let (_, all_seeks) = count_io(from_seekable_reader, config)?;
+    assert!(
+        default_seeks < all_seeks,
+        "Short skips should be done by reading: default_seeks={}, all_seeks={}",
+        default_seeks,
+        all_seeks
+    );
+    config.skip_small_threshold = Some(u64::MAX);
+    assert_eq!(count_io(from_seekable_reader, config)?.1, 0);
+
+    Ok(())
+}
+
+#[test]
+fn skip_buffer_size() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = ParserConfig::default();
+    let (default_reads, _) = count_io(from_reader, config)?;
+    config.skip_buffer_size = NonZeroUsize::new(1);
+    let (small_reads, _) = count_io(from_reader, config)?;
+    // Attributes of `Node0_0_0` (77 bytes) are skipped by 1 byte.
+    assert!(
+        default_reads + 76 <= small_reads,
+        "Skips should use the small buffer: default_reads={}, small_reads={}",
+        default_reads,
+        small_reads
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Probe test.
+#![cfg(feature = "writer")]
+
+use std::io::Cursor;
+
+use fbxcel::{
+    low::FbxVersion,
+    probe, write_v7400_binary,
+    writer::v7400::binary::{FbxFooter, Writer},
+};
+
+/// Probes the generated data.
+#[test]
+fn probe_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dest = Vec::new();
+    let cursor = Cursor::new(&mut dest);
+    let mut writer = Writer::new(cursor, FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            FBXHeaderExtension: {
+                FBXHeaderVersion: [1003i32] {}
+            }
+            Objects: {
+                Geometry: [1i64, "Cube\u{0}\u{1}Geometry", "Mesh"] {
+                    Vertices: [vec![0.0f64; 24]] {}
+                }
+                Model: [2i64, "Cube\u{0}\u{1}Model", "Mesh"] {}
+            }
+            Connections: {}
+        },
+    )?;
+    writer.finalize_and_flush(&FbxFooter::default())?;
+
+    let info = probe(Cursor::new(dest))?;
+    assert_eq!(info.fbx_version(), FbxVersion::V7_4);
+    assert_eq!(
+        info.toplevel_node_names(),
+        ["FBXHeaderExtension", "Objects", "Connections"]
+    );
+    assert_eq!(info.toplevel_nodes_count(), 3);
+    assert_eq!(info.objects_count(), Some(2));
+    assert!(info.has_objects());
+    assert!(!info.has_takes());
+    assert_eq!(
+        info.footer().expect("Footer should be loaded").fbx_version,
+        FbxVersion::V7_4
+    );
+
+    Ok(())
+}
@@ -1,16 +1,22 @@
 //! Writer and parser test.
 #![cfg(feature = "writer")]
 
-use std::{cell::RefCell, io::Cursor, iter, rc::Rc};
+use std::{
+    cell::RefCell,
+    io::{self, Cursor, Read},
+    iter,
+    rc::Rc,
+};
 
 use fbxcel::{
     low::{v7400::AttributeValue, FbxVersion},
     pull_parser::{
-        any::{from_seekable_reader, AnyParser},
+        any::{from_reader, from_seekable_reader, AnyParser},
+        error::{DataError, ErrorContainer},
         v7400::attribute::loaders::DirectLoader,
     },
     write_v7400_binary,
-    writer::v7400::binary::{FbxFooter, Writer},
+    writer::v7400::binary::{Error, FbxFooter, Writer},
 };
 
 use self::v7400::writer::{
@@ -271,3 +277,1170 @@ fn macro_v7400_idempotence() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Verifies the written data on finalization.
+#[test]
+fn verified_write_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: {
+                Node0_0: [true, 42i16, 1.234f64] {}
+                Node0_1: [vec![1i32, 2, 4, 8], "Hello"] {
+                    Node0_1_0: {}
+                }
+            }
+            Node1: ["Hello, world"] {}
+        },
+    )?;
+    let sink = writer.finalize_with_verification(&FbxFooter::default())?;
+
+    let len = sink.get_ref().len() as u64;
+    assert_eq!(sink.position(), len);
+    assert_eq!(len % 16, 0);
+
+    Ok(())
+}
+
+/// Reports the byte sizes of the top-level nodes.
+#[test]
+fn toplevel_node_sizes_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: {
+                Node0_0: [vec![0.0f64; 64]] {}
+            }
+            Node1: ["Hello"] {}
+        },
+    )?;
+    writer.new_node("Node2")?;
+    {
+        let sizes = writer.toplevel_node_sizes();
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0].name(), "Node0");
+        assert_eq!(sizes[0].offset(), 27);
+        // Header (13 + 5), child header (13 + 7), attribute (1 + 12 + 512),
+        // and end marker (13).
+        // `Node0_0` has no end marker since it has attributes and no children.
+        assert_eq!(sizes[0].bytelen(), 18 + 20 + 525 + 13);
+        assert_eq!(sizes[1].name(), "Node1");
+        assert_eq!(sizes[1].offset(), sizes[0].offset() + sizes[0].bytelen());
+        // Header (13 + 5), and attribute (1 + 4 + 5).
+        assert_eq!(sizes[1].bytelen(), 18 + 10);
+    }
+    writer.close_node()?;
+    assert_eq!(writer.toplevel_node_sizes().len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn toplevel_order_check_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.set_toplevel_order_check(true);
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            FBXHeaderExtension: {}
+            // Unknown nodes can be placed anywhere.
+            UnknownNode: {}
+            Objects: {}
+            Takes: {}
+        },
+    )?;
+    match writer.new_node("Connections") {
+        Err(Error::ToplevelNodeOrder(name, prev)) => {
+            assert_eq!(name, "Connections");
+            assert_eq!(prev, "Takes");
+        }
+        v => panic!("Unexpected result: {:?}", v.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn owned_events_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::pull_parser::v7400::{AttributeLoadPolicy, OwnedEvent};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [42i64, "Hello"] {
+                Node0_0: [vec![1.0f32; 4]] {}
+            }
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let consumer = std::thread::spawn(move || receiver.into_iter().collect::<Vec<OwnedEvent>>());
+    loop {
+        let policy = if parser.current_depth() == 0 {
+            AttributeLoadPolicy::Load
+        } else {
+            AttributeLoadPolicy::Skip
+        };
+        let event = parser.next_owned_event(policy)?;
+        let is_end = matches!(event, OwnedEvent::EndFbx(_));
+        sender.send(event)?;
+        if is_end {
+            break;
+        }
+    }
+    drop(sender);
+    let events = consumer.join().expect("Consumer thread should not panic");
+
+    match &events[..] {
+        [OwnedEvent::StartNode(node0), OwnedEvent::StartNode(node0_0), OwnedEvent::EndNode, OwnedEvent::EndNode, OwnedEvent::EndFbx(Ok(_))] =>
+        {
+            assert_eq!(node0.name(), "Node0");
+            assert_eq!(
+                node0.attributes(),
+                [
+                    AttributeValue::I64(42),
+                    AttributeValue::String("Hello".into())
+                ]
+            );
+            assert_eq!(node0_0.name(), "Node0_0");
+            assert_eq!(node0_0.attributes_count(), 1);
+            assert!(node0_0.attributes().is_empty());
+        }
+        v => panic!("Unexpected events: {:?}", v),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn zlib_arrays_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::low::v7400::ArrayAttributeEncoding;
+
+    // Large enough to be compressed beyond the size of the write buffer.
+    let f64_values = (0..10_000).map(|v| f64::from(v).sin()).collect::<Vec<_>>();
+    let i32_values = (0..200).collect::<Vec<i32>>();
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    {
+        let mut attrs = writer.new_node("Arrays")?;
+        attrs.append_arr_f64_from_iter(
+            Some(ArrayAttributeEncoding::Zlib),
+            f64_values.iter().cloned(),
+        )?;
+        attrs.append_arr_i32_from_iter(
+            Some(ArrayAttributeEncoding::Zlib),
+            i32_values.iter().cloned(),
+        )?;
+        attrs.append_arr_bool_from_iter(Some(ArrayAttributeEncoding::Zlib), vec![true, false])?;
+    }
+    writer.close_node()?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    {
+        let mut attrs = expect_node_start(&mut parser, "Arrays")?;
+        let values = attrs
+            .iter(iter::repeat(DirectLoader))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            values,
+            [
+                AttributeValue::ArrF64(f64_values),
+                AttributeValue::ArrI32(i32_values),
+                AttributeValue::ArrBool(vec![true, false]),
+            ]
+        );
+    }
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn attributes_iter_with_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::low::v7400::AttributeType;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            P: ["Lcl Translation", 1.0f64, vec![2i32, 3]] {}
+            P: [42i64] {}
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let expect_string = |index, ty| match (index, ty) {
+        (0, AttributeType::String) | (1.., _) => Ok(DirectLoader),
+        (_, ty) => Err(DataError::UnexpectedAttribute(
+            "string".into(),
+            format!("{:?}", ty),
+        )),
+    };
+    {
+        let mut attrs = expect_node_start(&mut parser, "P")?;
+        let mut types = Vec::new();
+        let values = attrs
+            .iter_with(|index, ty| {
+                types.push(ty);
+                expect_string(index, ty).map_err(Into::into)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            types,
+            [
+                AttributeType::String,
+                AttributeType::F64,
+                AttributeType::ArrI32
+            ]
+        );
+        assert_eq!(
+            values,
+            [
+                AttributeValue::String("Lcl Translation".into()),
+                AttributeValue::F64(1.0),
+                AttributeValue::ArrI32(vec![2, 3]),
+            ]
+        );
+    }
+    expect_node_end(&mut parser)?;
+    {
+        let mut attrs = expect_node_start(&mut parser, "P")?;
+        let err = attrs
+            .iter_with(|index, ty| expect_string(index, ty).map_err(Into::into))
+            .collect::<Result<Vec<_>, _>>()
+            .expect_err("The factory should reject `i64`");
+        assert!(matches!(
+            err.get_ref(),
+            ErrorContainer::Data(DataError::UnexpectedAttribute(..))
+        ));
+        assert_eq!(
+            err.position().and_then(|pos| pos.attribute_index()),
+            Some(0)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn properties_writer_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::writer::v7400::binary::PropertiesWriter;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    {
+        let mut props = PropertiesWriter::new(&mut writer)?;
+        props.double("Intensity", 100.0)?;
+        props.color_rgb("Color", [1.0, 0.5, 0.0])?;
+        props.bool("CastShadows", true)?;
+        props.enum_("LightType", 2)?;
+        props.kstring("Comment", "fbxcel")?;
+        props.finish()?;
+    }
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let string = |s: &str| AttributeValue::String(s.into());
+    let expected = [
+        vec![
+            string("Intensity"),
+            string("double"),
+            string("Number"),
+            string(""),
+            AttributeValue::F64(100.0),
+        ],
+        vec![
+            string("Color"),
+            string("ColorRGB"),
+            string("Color"),
+            string(""),
+            AttributeValue::F64(1.0),
+            AttributeValue::F64(0.5),
+            AttributeValue::F64(0.0),
+        ],
+        vec![
+            string("CastShadows"),
+            string("bool"),
+            string(""),
+            string(""),
+            AttributeValue::I32(1),
+        ],
+        vec![
+            string("LightType"),
+            string("enum"),
+            string(""),
+            string(""),
+            AttributeValue::I32(2),
+        ],
+        vec![
+            string("Comment"),
+            string("KString"),
+            string(""),
+            string(""),
+            string("fbxcel"),
+        ],
+    ];
+    {
+        let attrs = expect_node_start(&mut parser, "Properties70")?;
+        assert_eq!(attrs.total_count(), 0);
+    }
+    for expected in &expected {
+        {
+            let mut attrs = expect_node_start(&mut parser, "P")?;
+            let values = attrs
+                .iter(iter::repeat(DirectLoader))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(&values, expected);
+        }
+        expect_node_end(&mut parser)?;
+    }
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn properties70_and_connections_macros_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::{write_v7400_connections, write_v7400_properties70};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_properties70!(
+        writer=writer,
+        properties={
+            "DiffuseColor": color(1.0, 0.0, 0.0),
+            "LightType": enum_(1)
+        },
+    )?;
+    let prop = "Lcl Translation";
+    write_v7400_connections!(
+        writer=writer,
+        connections={
+            OO 0 <- 1
+            OP 1 <- (1 + 1) @ prop,
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let string = |s: &str| AttributeValue::String(s.into());
+    let expected = [
+        (
+            "Properties70",
+            "P",
+            vec![
+                vec![
+                    string("DiffuseColor"),
+                    string("ColorRGB"),
+                    string("Color"),
+                    string(""),
+                    AttributeValue::F64(1.0),
+                    AttributeValue::F64(0.0),
+                    AttributeValue::F64(0.0),
+                ],
+                vec![
+                    string("LightType"),
+                    string("enum"),
+                    string(""),
+                    string(""),
+                    AttributeValue::I32(1),
+                ],
+            ],
+        ),
+        (
+            "Connections",
+            "C",
+            vec![
+                vec![string("OO"), AttributeValue::I64(1), AttributeValue::I64(0)],
+                vec![
+                    string("OP"),
+                    AttributeValue::I64(2),
+                    AttributeValue::I64(1),
+                    string("Lcl Translation"),
+                ],
+            ],
+        ),
+    ];
+    for (name, child_name, children) in &expected {
+        {
+            let attrs = expect_node_start(&mut parser, name)?;
+            assert_eq!(attrs.total_count(), 0);
+        }
+        for expected in children {
+            {
+                let mut attrs = expect_node_start(&mut parser, child_name)?;
+                let values = attrs
+                    .iter(iter::repeat(DirectLoader))
+                    .collect::<Result<Vec<_>, _>>()?;
+                assert_eq!(&values, expected);
+            }
+            expect_node_end(&mut parser)?;
+        }
+        expect_node_end(&mut parser)?;
+    }
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn toc_recording_toggled_with_open_nodes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.set_toc_recording(true);
+    writer.new_node("A")?;
+    writer.new_node("B")?;
+    writer.set_toc_recording(false);
+    writer.set_toc_recording(true);
+    writer.new_node("C")?;
+    writer.close_node()?;
+    writer.close_node()?;
+    writer.close_node()?;
+    let (_sink, toc) = writer.finalize_with_toc(&FbxFooter::default())?;
+
+    // Entries of `A` and `B` are discarded by disabling the recording.
+    let names = toc.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["C"]);
+    assert!(toc[0].header_offset() < toc[0].end_offset());
+
+    Ok(())
+}
+
+#[test]
+fn append_raw_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::low::v7400::AttributeType;
+
+    let arr_payload = {
+        let mut payload = Vec::new();
+        // Number of elements, encoding (direct), and byte length.
+        for v in [3u32, 0, 12] {
+            payload.extend_from_slice(&v.to_le_bytes());
+        }
+        for v in [1i32, 2, 3] {
+            payload.extend_from_slice(&v.to_le_bytes());
+        }
+        payload
+    };
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    {
+        let mut attrs = writer.new_node("Node")?;
+        attrs.append_raw(AttributeType::Bool, b"Y")?;
+        attrs.append_raw(AttributeType::F64, &1.5f64.to_le_bytes())?;
+        attrs.append_raw(AttributeType::ArrI32, &arr_payload)?;
+        attrs.append_raw(AttributeType::String, b"\x02\x00\x00\x00ab")?;
+
+        for (ty, payload) in [
+            (AttributeType::I32, &b"\x00\x00"[..]),
+            (AttributeType::Binary, &b"\x00\x00"[..]),
+            (
+                AttributeType::ArrI32,
+                &arr_payload[..(arr_payload.len() - 4)],
+            ),
+            (AttributeType::ArrI64, &arr_payload[..]),
+        ] {
+            match attrs.append_raw(ty, payload) {
+                Err(Error::InvalidRawAttribute(got_ty, len)) => {
+                    assert_eq!(got_ty, ty);
+                    assert_eq!(len, payload.len());
+                }
+                v => panic!("Invalid payload should be rejected: {:?}", v),
+            }
+        }
+    }
+    writer.close_node()?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    {
+        let mut attrs = expect_node_start(&mut parser, "Node")?;
+        let values = attrs
+            .iter(iter::repeat(DirectLoader))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            values,
+            [
+                AttributeValue::Bool(true),
+                AttributeValue::F64(1.5),
+                AttributeValue::ArrI32(vec![1, 2, 3]),
+                AttributeValue::String("ab".into()),
+            ]
+        );
+    }
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn writer_warning_footer_padding_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::writer::v7400::binary::{FbxFooterPaddingLength, Warning};
+
+    let footer = FbxFooter {
+        padding_len: FbxFooterPaddingLength::Forced(16),
+        ..Default::default()
+    };
+
+    // Warnings are ignored by default.
+    let writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.finalize_and_flush(&footer)?;
+
+    // Warnings are reported to the handler.
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    writer.set_warning_handler({
+        let warnings = warnings.clone();
+        move |warning| {
+            warnings.lock().unwrap().push(warning);
+            Ok(())
+        }
+    });
+    writer.finalize_and_flush(&footer)?;
+    match &warnings.lock().unwrap()[..] {
+        [Warning::InvalidFooterPaddingLength(_, 16)] => {}
+        v => panic!("Unexpected warnings: {:?}", v),
+    }
+
+    // The handler can abort writing.
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.set_warning_handler(|warning| Err(warning.into()));
+    match writer.finalize_and_flush(&footer) {
+        Err(Error::Warning(Warning::InvalidFooterPaddingLength(_, 16))) => {}
+        v => panic!("Unexpected result: {:?}", v.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn writer_warnings_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::writer::v7400::binary::Warning;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    writer.set_warning_handler({
+        let warnings = warnings.clone();
+        move |warning| {
+            warnings.lock().unwrap().push(warning);
+            Ok(())
+        }
+    });
+    writer.new_node("Objects")?;
+    writer.close_node()?;
+    writer.new_node("FBXHeaderExtension")?;
+    writer.close_node()?;
+    writer.new_node("")?;
+    writer.close_node()?;
+    writer
+        .new_node("Bad Name")?
+        .append_arr_i32_from_iter(None, iter::empty())?;
+    writer.close_node()?;
+    writer.finalize_and_flush(&FbxFooter::default())?;
+
+    assert_eq!(
+        &warnings.lock().unwrap()[..],
+        [
+            Warning::ToplevelNodeOrder("FBXHeaderExtension".into(), "Objects".into()),
+            Warning::EmptyNodeName,
+            Warning::SuspiciousNodeName("Bad Name".into()),
+            Warning::EmptyArrayAttribute,
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn start_node_layout_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::pull_parser::v7400::Event;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [vec![0.0f64; 64], "Hello"] {
+                Node0_0: {}
+            }
+        },
+    )?;
+    let sizes = writer.toplevel_node_sizes().to_vec();
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    match parser.next_event()? {
+        Event::StartNode(start) => {
+            assert_eq!(start.start_offset(), sizes[0].offset());
+            assert_eq!(start.bytelen(), sizes[0].bytelen());
+            assert_eq!(start.end_offset(), sizes[0].offset() + sizes[0].bytelen());
+            assert_eq!(start.attributes_count(), 2);
+            // Array (1 + 12 + 512) and string (1 + 4 + 5).
+            assert_eq!(start.attributes_bytelen(), 525 + 10);
+        }
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+    parser.skip_current_node()?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn error_codes_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::{ErrorCategory, ErrorCode};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    let err = writer
+        .close_node()
+        .expect_err("There are no nodes to close");
+    assert_eq!(err.code(), ErrorCode::NoNodesToClose);
+    assert_eq!(err.code().category(), ErrorCategory::Write);
+    assert_eq!(err.code().as_str(), "write.no_nodes_to_close");
+
+    let err = match from_seekable_reader(Cursor::new(vec![0u8; 64])) {
+        Ok(_) => panic!("Magic binary should not be detected"),
+        Err(e) => e,
+    };
+    assert_eq!(err.code(), ErrorCode::MagicNotDetected);
+    assert_eq!(err.code().category(), ErrorCategory::Header);
+
+    Ok(())
+}
+
+#[test]
+fn seek_to_attribute_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::{
+        low::v7400::AttributeType,
+        pull_parser::v7400::{attribute::loaders::TypeLoader, Event},
+        ErrorCode,
+    };
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [vec![1i32, 2, 3], "Hello", 42i64] {}
+            Node1: {}
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    parser.set_warning_handler(|w, pos| panic!("Unexpected warning: {:?} at {:?}", w, pos));
+    match parser.next_event()? {
+        Event::StartNode(start) => {
+            let mut attrs = start.attributes();
+            assert_eq!(attrs.load_next(TypeLoader)?, Some(AttributeType::ArrI32));
+            assert_eq!(attrs.load_next(TypeLoader)?, Some(AttributeType::String));
+            assert_eq!(attrs.position(), 2);
+
+            attrs.seek_to_attribute(1)?;
+            assert_eq!(attrs.position(), 1);
+            assert_eq!(
+                attrs.load_next(DirectLoader)?,
+                Some(AttributeValue::String("Hello".into()))
+            );
+            attrs.seek_to_attribute(0)?;
+            assert_eq!(
+                attrs.load_next(DirectLoader)?,
+                Some(AttributeValue::ArrI32(vec![1, 2, 3]))
+            );
+            attrs.seek_to_attribute(2)?;
+            assert_eq!(
+                attrs.load_next(DirectLoader)?,
+                Some(AttributeValue::I64(42))
+            );
+            assert_eq!(attrs.rest_count(), 0);
+
+            let err = attrs
+                .seek_to_attribute(4)
+                .expect_err("Attribute index should be out of range");
+            assert_eq!(err.code(), ErrorCode::AttributeIndexOutOfRange);
+        }
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+    expect_node_end(&mut parser)?;
+    expect_node_start(&mut parser, "Node1")?;
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+    assert_eq!(parser.nodes_with_unread_attributes_count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn copy_node_from_parser_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::pull_parser::v7400::Event;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [vec![0.5f64; 64], "Hello"] {
+                Node0_0: {}
+                Node0_1: [42i32] {}
+            }
+            Node1: [true] {}
+        },
+    )?;
+    let source = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let copy = |fbx_version| -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut parser = match from_seekable_reader(Cursor::new(source.clone()))? {
+            AnyParser::V7400(parser) => parser,
+            _ => panic!("Generated data should be parsable with v7400 parser"),
+        };
+        let mut writer = Writer::new(Cursor::new(Vec::new()), fbx_version)?;
+        while let Event::StartNode(_) = parser.next_event()? {
+            writer.copy_node_from_parser(&mut parser)?;
+        }
+        Ok(writer
+            .finalize_and_flush(&FbxFooter::default())?
+            .into_inner())
+    };
+
+    assert_eq!(copy(FbxVersion::V7_4)?, source);
+
+    let copied = copy(FbxVersion::V7_5)?;
+    let mut parser = match from_seekable_reader(Cursor::new(copied))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    assert_eq!(parser.fbx_version(), FbxVersion::V7_5);
+    {
+        let attrs = expect_node_start(&mut parser, "Node0")?;
+        assert_eq!(
+            attrs
+                .into_iter(iter::repeat(DirectLoader))
+                .collect::<Result<Vec<_>, _>>()?,
+            [
+                AttributeValue::ArrF64(vec![0.5; 64]),
+                AttributeValue::String("Hello".into())
+            ]
+        );
+    }
+    expect_node_start(&mut parser, "Node0_0")?;
+    expect_node_end(&mut parser)?;
+    expect_node_start(&mut parser, "Node0_1")?;
+    expect_node_end(&mut parser)?;
+    expect_node_end(&mut parser)?;
+    expect_node_start(&mut parser, "Node1")?;
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+#[test]
+fn copy_node_from_parser_errors_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::{pull_parser::v7400::Event, writer::v7400::binary::CopyError};
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node: [42i32] {}
+        },
+    )?;
+    let source = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let copy = |source: Vec<u8>,
+                read_attribute: bool|
+     -> Result<Result<(), Error>, Box<dyn std::error::Error>> {
+        let mut parser = match from_seekable_reader(Cursor::new(source))? {
+            AnyParser::V7400(parser) => parser,
+            _ => panic!("Generated data should be parsable with v7400 parser"),
+        };
+        let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+        match parser.next_event()? {
+            Event::StartNode(start) => {
+                if read_attribute {
+                    start.attributes().load_next(DirectLoader)?;
+                }
+            }
+            ev => panic!("Unexpected event: {:?}", ev),
+        }
+        Ok(writer.copy_node_from_parser(&mut parser))
+    };
+
+    assert!(matches!(
+        copy(source.clone(), true)?,
+        Err(Error::Copy(CopyError::AttributesAlreadyRead))
+    ));
+
+    // Node header (for FBX 7.4) starts right after the 27-byte FBX header,
+    // and the number of attributes follows the end offset.
+    const NUM_ATTRIBUTES_POS: usize = 27 + 4;
+    let mut inconsistent = source.clone();
+    inconsistent[NUM_ATTRIBUTES_POS..(NUM_ATTRIBUTES_POS + 4)].copy_from_slice(&0u32.to_le_bytes());
+    assert!(matches!(
+        copy(inconsistent, false)?,
+        Err(Error::Copy(CopyError::InconsistentAttributes(0, 5)))
+    ));
+
+    // The node has two attributes in the header, but only one in the data.
+    let mut inconsistent = source.clone();
+    inconsistent[NUM_ATTRIBUTES_POS..(NUM_ATTRIBUTES_POS + 4)].copy_from_slice(&2u32.to_le_bytes());
+    assert!(matches!(
+        copy(inconsistent, false)?,
+        Err(Error::Copy(CopyError::InconsistentAttributes(2, 5)))
+    ));
+
+    // The first attribute follows the 13-byte node header and the 4-byte
+    // node name.
+    const TYPE_CODE_POS: usize = 27 + 13 + 4;
+    let mut invalid = source;
+    invalid[TYPE_CODE_POS] = b'?';
+    assert!(matches!(
+        copy(invalid, false)?,
+        Err(Error::Copy(CopyError::InvalidAttributeTypeCode(b'?')))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn event_stream_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::pull_parser::v7400::OwnedEvent;
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [42i32] {
+                Node0_0: {}
+            }
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let mut stream = parser.into_event_stream();
+    match stream.next().transpose()? {
+        Some(OwnedEvent::StartNode(start)) => {
+            assert_eq!(start.name(), "Node0");
+            assert_eq!(start.attributes(), [AttributeValue::I32(42)]);
+        }
+        ev => panic!("Unexpected event: {:?}", ev),
+    }
+    let rest = stream.by_ref().collect::<Result<Vec<_>, _>>()?;
+    assert!(matches!(
+        rest[..],
+        [
+            OwnedEvent::StartNode(_),
+            OwnedEvent::EndNode,
+            OwnedEvent::EndNode,
+            OwnedEvent::EndFbx(Ok(_))
+        ]
+    ));
+    assert!(stream.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn truncated_data_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: [vec![0i32; 256]] {}
+        },
+    )?;
+    let mut bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+    // Truncate in the middle of the array attribute.
+    bin.truncate(bin.len() / 2);
+
+    // Seekable source knows the total length, and detects it early.
+    let mut parser = match from_seekable_reader(Cursor::new(&bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    match parser.next_event() {
+        Err(e) => match e.get_ref() {
+            ErrorContainer::Data(DataError::NodeLengthMismatch(_, Some(file_len))) => {
+                assert_eq!(*file_len, bin.len() as u64);
+            }
+            _ => panic!("Unexpected error: {}", e),
+        },
+        Ok(ev) => panic!("Unexpected event: {:?}", ev),
+    }
+
+    // Plain source doesn't know the total length.
+    let mut parser = match from_reader(&bin[..])? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    assert!(parser.next_event().is_ok());
+
+    Ok(())
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn append_arr_from_ndarray_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::writer::v7400::binary::ElementOrder;
+
+    let array = ndarray::arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    {
+        let mut attrs = writer.new_node("Node")?;
+        attrs.append_arr_f64_from_array(None, &array, ElementOrder::RowMajor)?;
+        attrs.append_arr_f64_from_array(None, &array, ElementOrder::ColumnMajor)?;
+        // Memory layout of the source doesn't matter.
+        attrs.append_arr_f64_from_array(None, &array.t(), ElementOrder::RowMajor)?;
+    }
+    writer.close_node()?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    let attrs = expect_node_start(&mut parser, "Node")?.load_all_direct()?;
+    assert_eq!(
+        attrs,
+        [
+            AttributeValue::ArrF64(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+            AttributeValue::ArrF64(vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]),
+            AttributeValue::ArrF64(vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bookmark_and_resume_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    write_v7400_binary!(
+        writer=writer,
+        tree={
+            Node0: {
+                Node0_0: [1i32] {}
+                Node0_1: [2i32] {}
+            }
+        },
+    )?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    expect_node_start(&mut parser, "Node0")?;
+    let bookmark = parser.bookmark();
+    assert_eq!(bookmark.depth(), 1);
+    assert!(bookmark.node_path().eq(["Node0"]));
+
+    // First pass.
+    expect_node_start(&mut parser, "Node0_0")?;
+    expect_node_end(&mut parser)?;
+    expect_node_start(&mut parser, "Node0_1")?;
+    expect_node_end(&mut parser)?;
+    expect_node_end(&mut parser)?;
+    expect_fbx_end(&mut parser)??;
+
+    // Second pass.
+    parser.resume(&bookmark)?;
+    assert_eq!(parser.current_depth(), 1);
+    assert_eq!(
+        expect_node_start(&mut parser, "Node0_0")?.load_all_direct()?,
+        [AttributeValue::I32(1)]
+    );
+    expect_node_end(&mut parser)?;
+    assert_eq!(
+        expect_node_start(&mut parser, "Node0_1")?.load_all_direct()?,
+        [AttributeValue::I32(2)]
+    );
+    expect_node_end(&mut parser)?;
+    expect_node_end(&mut parser)?;
+    expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
+
+/// Sink which discards the data and only tracks the position.
+#[derive(Default, Debug)]
+struct DiscardingSink {
+    /// Current position.
+    pos: u64,
+    /// Length of the written data.
+    len: u64,
+}
+
+impl io::Write for DiscardingSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Seek for DiscardingSink {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.pos = match pos {
+            io::SeekFrom::Start(v) => v,
+            io::SeekFrom::End(v) => (self.len as i64 + v) as u64,
+            io::SeekFrom::Current(v) => (self.pos as i64 + v) as u64,
+        };
+        Ok(self.pos)
+    }
+}
+
+/// Reader which returns the given number of unspecified bytes without
+/// filling the buffer.
+#[derive(Debug)]
+struct UnfilledReader(u64);
+
+impl Read for UnfilledReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = (buf.len() as u64).min(self.0);
+        self.0 -= len;
+        Ok(len as usize)
+    }
+}
+
+#[test]
+fn file_too_large_detected_early_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    /// Length of a binary attribute, which fits in the special attribute
+    /// header.
+    const CHUNK_LEN: u64 = 1 << 31;
+
+    let mut writer = Writer::new(DiscardingSink::default(), FbxVersion::V7_4)?;
+    let mut attrs = writer.new_node("Node")?;
+    attrs.append_binary_from_reader(UnfilledReader(CHUNK_LEN))?;
+    match attrs.append_binary_from_reader(UnfilledReader(CHUNK_LEN)) {
+        Err(Error::FileTooLarge(offset)) => assert!(offset > u64::from(u32::MAX)),
+        v => panic!("Expected `FileTooLarge` error, but got {:?}", v),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn file_too_large_detected_on_single_value_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    /// Length of a binary attribute, which fits in the special attribute
+    /// header.
+    const CHUNK_LEN: u64 = 1 << 31;
+    /// Margin left before the 4 GiB limit.
+    const MARGIN: u64 = 1024;
+
+    let mut writer = Writer::new(DiscardingSink::default(), FbxVersion::V7_4)?;
+    let mut attrs = writer.new_node("Node")?;
+    attrs.append_binary_from_reader(UnfilledReader(CHUNK_LEN))?;
+    attrs.append_binary_from_reader(UnfilledReader(CHUNK_LEN - MARGIN))?;
+    // Each `i32` attribute takes 5 bytes.
+    for _ in 0..(MARGIN / 5 + 1) {
+        match attrs.append_i32(42) {
+            Ok(()) => {}
+            Err(Error::FileTooLarge(offset)) => {
+                assert!(offset > u64::from(u32::MAX));
+                return Ok(());
+            }
+            Err(e) => panic!("Expected `FileTooLarge` error, but got {:?}", e),
+        }
+    }
+    panic!("Expected `FileTooLarge` error");
+}
+
+#[test]
+fn typed_writer_returns_writer_on_error_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    use fbxcel::writer::v7400::binary::TypedWriter;
+
+    let writer = TypedWriter::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    let err = writer
+        .begin_node_with("Node", |attrs| {
+            attrs.append_i32(42)?;
+            Err(io::Error::new(io::ErrorKind::Other, "Attribute error").into())
+        })
+        .expect_err("Should fail");
+    assert!(matches!(err.error(), Error::Io(_)));
+    // The node has been opened before the error.
+    let (_, mut writer) = err.into_parts();
+    assert_eq!(writer.current_depth(), 1);
+    writer.close_node()?;
+    let bin = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut parser = match from_seekable_reader(Cursor::new(bin))? {
+        AnyParser::V7400(parser) => parser,
+        _ => panic!("Generated data should be parsable with v7400 parser"),
+    };
+    {
+        let mut attrs = expect_node_start(&mut parser, "Node")?;
+        assert_eq!(
+            attrs.load_next(DirectLoader)?,
+            Some(AttributeValue::I32(42))
+        );
+    }
+    expect_node_end(&mut parser)?;
+    let _: Box<fbxcel::low::v7400::FbxFooter> = expect_fbx_end(&mut parser)??;
+
+    Ok(())
+}
@@ -4,11 +4,20 @@
 use std::{cell::RefCell, io::Cursor, rc::Rc};
 
 use fbxcel::{
-    low::FbxVersion,
-    pull_parser::any::{from_seekable_reader, AnyParser},
-    tree::v7400::Loader as TreeLoader,
+    low::{
+        v7400::{ArrayAttributeEncoding, AttributeValue},
+        FbxVersion,
+    },
+    pull_parser::{
+        any::{from_seekable_reader, AnyParser},
+        error::Warning,
+    },
+    tree::{
+        any::AnyTree,
+        v7400::{Loader as TreeLoader, Tree},
+    },
     tree_v7400,
-    writer::v7400::binary::Writer,
+    writer::v7400::binary::{FbxFooter, FbxFooterPaddingLength, FooterPaddingRewrite, Writer},
 };
 
 /// Construct tree, export it to binary, parse it and construct tree, and
@@ -54,3 +63,191 @@ fn tree_write_parse_idempotence_v7500() -> Result<(), Box<dyn std::error::Error>
 
     Ok(())
 }
+
+/// Construct tree, export it to binary with broken footer, and load it with
+/// the report.
+#[test]
+fn any_tree_load_report_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let tree1 = tree_v7400! {
+        Node0: [42i64] {}
+    };
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.write_tree(&tree1)?;
+    let footer = FbxFooter {
+        padding_len: FbxFooterPaddingLength::Forced(15),
+        ..Default::default()
+    };
+    let bin = writer.finalize_and_flush(&footer)?.into_inner();
+
+    let (tree2, report) = AnyTree::from_seekable_reader_with_report(Cursor::new(bin))?;
+    assert_eq!(tree2.fbx_version(), FbxVersion::V7_4);
+    assert!(tree2.footer().is_ok());
+    match report.warnings() {
+        [(Warning::InvalidFooterPaddingLength(..), _)] => {}
+        v => panic!("Unexpected warnings: {:?}", v),
+    }
+
+    match tree2 {
+        AnyTree::V7400(_, tree2, _) => assert!(tree1.strict_eq(&tree2)),
+        _ => panic!("Generated data should be loaded as v7400 tree"),
+    }
+
+    Ok(())
+}
+
+/// Write nodes with empty names, and load them with warnings.
+#[test]
+fn empty_node_names_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut tree1 = Tree::default();
+    let root = tree1.root().node_id();
+    let node0 = tree1.append_new(root, "Node0");
+    let empty0 = tree1.append_new(node0, "");
+    tree1.append_attribute(empty0, 0i32);
+    let empty1 = tree1.append_new(node0, "");
+    tree1.append_attribute(empty1, 1i32);
+    tree1.append_new(root, "");
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.write_tree(&tree1)?;
+    let bin = writer.finalize_and_flush(&Default::default())?.into_inner();
+
+    let (tree2, report) = AnyTree::from_seekable_reader_with_report(Cursor::new(bin))?;
+    let paths = report
+        .warnings()
+        .iter()
+        .map(|(warning, pos)| {
+            assert!(
+                matches!(warning, Warning::EmptyNodeName),
+                "Unexpected warning: {:?}",
+                warning
+            );
+            pos.node_path().to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            vec![(0, "Node0".to_owned()), (0, String::new())],
+            vec![(0, "Node0".to_owned()), (1, String::new())],
+            vec![(1, String::new())],
+        ]
+    );
+
+    let tree2 = match tree2 {
+        AnyTree::V7400(_, tree2, _) => tree2,
+        _ => panic!("Generated data should be loaded as v7400 tree"),
+    };
+    assert!(tree1.strict_eq(&tree2));
+    let empty_children = tree2
+        .root()
+        .first_child_by_name("Node0")
+        .expect("Should exist")
+        .children_by_name("")
+        .map(|node| node.attributes()[0].get_i32())
+        .collect::<Vec<_>>();
+    assert_eq!(empty_children, [Some(0), Some(1)]);
+
+    Ok(())
+}
+
+/// Load a tree with broken footer padding, and write it again preserving or
+/// correcting the padding.
+#[test]
+fn footer_padding_rewrite_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.write_tree(&tree_v7400! { Node0: [42i64] {} })?;
+    let footer = FbxFooter {
+        padding_len: FbxFooterPaddingLength::Forced(15),
+        ..Default::default()
+    };
+    let bin = writer.finalize_and_flush(&footer)?.into_inner();
+    let (source, _) = AnyTree::from_seekable_reader_with_report(Cursor::new(&bin[..]))?;
+    let source_footer = source.footer().expect("Footer should be loadable");
+    assert_eq!(source_footer.padding_len, 15);
+    let tree = match &source {
+        AnyTree::V7400(_, tree, _) => tree,
+        _ => panic!("Generated data should be loaded as v7400 tree"),
+    };
+
+    let rewrite = |padding| -> Result<_, Box<dyn std::error::Error>> {
+        let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+        writer.write_tree(tree)?;
+        let footer = FbxFooter::from_parsed(source_footer, padding);
+        let rewritten = writer.finalize_and_flush(&footer)?.into_inner();
+        Ok(AnyTree::from_seekable_reader_with_report(Cursor::new(
+            rewritten,
+        ))?)
+    };
+
+    // Byte-faithful rewrite.
+    let (preserved, report) = rewrite(FooterPaddingRewrite::Preserve)?;
+    assert_eq!(
+        preserved.footer().expect("Should be loadable").padding_len,
+        15
+    );
+    match report.warnings() {
+        [(Warning::InvalidFooterPaddingLength(..), _)] => {}
+        v => panic!("Unexpected warnings: {:?}", v),
+    }
+
+    // Corrected rewrite.
+    let (corrected, report) = rewrite(FooterPaddingRewrite::Correct)?;
+    assert_ne!(
+        corrected.footer().expect("Should be loadable").padding_len,
+        15
+    );
+    assert!(report.warnings().is_empty());
+
+    Ok(())
+}
+
+/// Load a tree with compressed arrays, modify another node, and write it
+/// again.
+#[test]
+fn tree_array_encoding_preserved_v7400() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer
+        .new_node("Node0")?
+        .append_arr_i32_from_iter(ArrayAttributeEncoding::Zlib, 0..64)?;
+    writer.close_node()?;
+    writer
+        .new_node("Node1")?
+        .append_arr_f64_from_iter(ArrayAttributeEncoding::Direct, vec![1.0; 4])?;
+    writer.close_node()?;
+    writer.new_node("Node2")?.append_i32(1)?;
+    writer.close_node()?;
+    let bin1 = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+
+    let mut tree = match AnyTree::from_seekable_reader(Cursor::new(&bin1))? {
+        AnyTree::V7400(_, tree, _) => tree,
+        _ => panic!("Generated data should be loaded as v7400 tree"),
+    };
+    let nodes = tree.root().children().collect::<Vec<_>>();
+    assert_eq!(
+        nodes[0].array_encoding(0),
+        Some(ArrayAttributeEncoding::Zlib)
+    );
+    assert_eq!(
+        nodes[1].array_encoding(0),
+        Some(ArrayAttributeEncoding::Direct)
+    );
+    assert_eq!(nodes[2].array_encoding(0), None);
+    let node2 = nodes[2].node_id();
+    *tree
+        .get_attribute_mut(node2, 0)
+        .expect("Attribute should exist") = AttributeValue::I32(2);
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), FbxVersion::V7_4)?;
+    writer.write_tree(&tree)?;
+    let bin2 = writer
+        .finalize_and_flush(&FbxFooter::default())?
+        .into_inner();
+    assert_eq!(bin1.len(), bin2.len());
+    let diff = bin1.iter().zip(&bin2).filter(|(a, b)| a != b).count();
+    assert_eq!(diff, 1, "Only the modified attribute should differ");
+
+    Ok(())
+}